@@ -0,0 +1,62 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use signed_distance_fields::{
+    objects::{
+        primitives::{Circle, Rectangle},
+        SignedDistanceField, SignedDistanceFieldExt,
+    },
+    render::{
+        self,
+        adaptive::{self, Fill},
+    },
+    Domain, Vec2,
+};
+
+fn scene() -> impl SignedDistanceField {
+    (Circle { r: 6.0 } - Rectangle { w: 4.0, h: 20.0 })
+        .smooth_union(Circle { r: 2.0 }.translate(Vec2::new(-8.0, 7.0)), 1.0)
+        .rotate(0.3)
+}
+
+/// Compares the uniform and the adaptive renderer on a 4k x 4k domain
+fn uniform_vs_adaptive(c: &mut Criterion) {
+    let scene = scene();
+    let domain = Domain {
+        p0: Vec2::new(-12.0, -12.0),
+        p1: Vec2::new(12.0, 12.0),
+        steps: Vec2::new(4096, 4096),
+    };
+
+    let mut group = c.benchmark_group("render 4096x4096");
+    group.sample_size(10);
+    group.bench_function("uniform", |b| b.iter(|| render::matrix(&scene, &domain)));
+    group.bench_function("adaptive", |b| {
+        b.iter(|| adaptive::matrix(&scene, &domain, Fill::Interpolate))
+    });
+    group.finish();
+}
+
+/// Compares a call per point with a single batch through a `dyn` pointer
+fn call_vs_batch(c: &mut Criterion) {
+    let scene = scene();
+    let sdf: &dyn SignedDistanceField = &scene;
+    let points: Vec<Vec2> = (0..65536)
+        .map(|i| Vec2::new((i % 256) as f64 * 0.1 - 12.8, (i / 256) as f64 * 0.1 - 12.8))
+        .collect();
+    let mut out = vec![0.0; points.len()];
+
+    let mut group = c.benchmark_group("65536 points");
+    group.bench_function("call", |b| {
+        b.iter(|| {
+            for (p, out) in points.iter().zip(out.iter_mut()) {
+                *out = sdf.call(*p);
+            }
+        })
+    });
+    group.bench_function("call_batch", |b| {
+        b.iter(|| sdf.call_batch(&points, &mut out))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, uniform_vs_adaptive, call_vs_batch);
+criterion_main!(benches);
@@ -1,11 +1,10 @@
-use math_vector::Vector;
-use signed_distance_fields::{Domain, objects::{primitives::{Circle, Rectangle}, operators::boolean::{Union, Intersection, Difference}}, render::{self, text_mappers}};
+use signed_distance_fields::{Domain, Vec2, objects::{primitives::{Circle, Rectangle}, SignedDistanceFieldExt}, render::{self, text_mappers}};
 
 fn main() {
     let domain = Domain {
-        p0: Vector { x: -10.0, y: -10.0, z: 0.0 },
-        p1: Vector { x: 10.0, y: 10.0, z: 0.0 },
-        steps: Vector { x: 100, y: 50, z: 0 }
+        p0: Vec2::new(-10.0, -10.0),
+        p1: Vec2::new(10.0, 10.0),
+        steps: Vec2::new(100, 50)
     };
     
     let a = Circle { r: 5.0 };
@@ -16,16 +15,20 @@ fn main() {
 
     println!("b:\n{}", render::text(&b, &domain, &text_mappers::default));
 
-    let union = Union { a: Box::new(&a), b: Box::new(&b) };
+    let union = a | b;
 
     println!("a ∪ b:\n{}", render::text(&union, &domain, &text_mappers::default));
     
-    let intersection = Intersection { a: Box::new(&a), b: Box::new(&b) };
+    let intersection = a & b;
     
     println!("a ∩ b:\n{}", render::text(&intersection, &domain, &text_mappers::default));
 
-    let difference = Difference { a: Box::new(&a), b: Box::new(&b) };
+    let difference = a - b;
 
     println!("a\\b:\n{}", render::text(&difference, &domain, &text_mappers::default));
 
+    let smooth_union = a.smooth_union(b, 2.0);
+
+    println!("a ∪ b (smooth):\n{}", render::text(&smooth_union, &domain, &text_mappers::default));
+
 }
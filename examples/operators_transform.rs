@@ -1,38 +1,35 @@
-use math_vector::Vector;
-use signed_distance_fields::{objects::{SignedDistanceField, primitives::Rectangle, operators::transforms::{Translate, Rotate, Scale, Matrix}}, Domain, render::{self, text_mappers}};
+use signed_distance_fields::{objects::{SignedDistanceField, primitives::Rectangle, operators::transforms::{Translate, Rotate, Scale, Matrix}}, Domain, Vec2, render::{self, text_mappers}};
 
 fn main() {
     let domain = Domain {
-        p0: Vector { x: -10.0, y: -10.0, z: 0.0 },
-        p1: Vector { x: 10.0, y: 10.0, z: 0.0 },
-        steps: Vector { x: 100, y: 50, z: 0 }
+        p0: Vec2::new(-10.0, -10.0),
+        p1: Vec2::new(10.0, 10.0),
+        steps: Vec2::new(100, 50)
     };
     
-    let none = none();
-
     println!(
         "None:\n{}",
-        render::text(&none, &domain, &text_mappers::default)
+        render::text(&none(), &domain, &text_mappers::default)
     );
 
     println!(
         "Translate:\n{}",
-        render::text(&translate(&none), &domain, &text_mappers::default)
+        render::text(&translate(none()), &domain, &text_mappers::default)
     );
     
     println!(
         "Rotate:\n{}",
-        render::text(&rotate(&none), &domain, &text_mappers::default)
+        render::text(&rotate(none()), &domain, &text_mappers::default)
     );
 
     println!(
         "Scale:\n{}",
-        render::text(&scale(&none), &domain, &text_mappers::default)
+        render::text(&scale(none()), &domain, &text_mappers::default)
     );
 
     println!(
         "Matrix:\n{}",
-        render::text(&matrix(&none), &domain, &text_mappers::default)
+        render::text(&matrix(none()), &domain, &text_mappers::default)
     );
 }
 
@@ -41,46 +38,33 @@ fn none() -> impl SignedDistanceField {
 }
 
 
-fn translate<'a>(rectangle: &'a dyn SignedDistanceField) -> impl SignedDistanceField + 'a {
+fn translate(rectangle: impl SignedDistanceField) -> impl SignedDistanceField {
     Translate { 
-        sdf: Box::new(rectangle),
-        p: Vector {
-            x: -5.0,
-            y: 3.0,
-            z: 0.0
-        }
+        sdf: rectangle,
+        p: Vec2::new(-5.0, 3.0)
     } 
 }
 
-fn rotate<'a>(rectangle: &'a dyn SignedDistanceField) -> impl SignedDistanceField + 'a {
+fn rotate(rectangle: impl SignedDistanceField) -> impl SignedDistanceField {
     Rotate { 
-        sdf: Box::new(rectangle),
-        axis: Vector {
-            x: 0.0,
-            y: 0.0,
-            z: 1.0
-        },
+        sdf: rectangle,
         alpha: 5.0
     }
 }
 
-fn scale<'a>(rectangle: &'a dyn SignedDistanceField) -> impl SignedDistanceField + 'a {
+fn scale(rectangle: impl SignedDistanceField) -> impl SignedDistanceField {
     Scale { 
-        sdf: Box::new(rectangle),
-        scale: Vector {
-            x: 0.5,
-            y: 2.0,
-            z: 1.0
-        }
+        sdf: rectangle,
+        scale: Vec2::new(0.5, 2.0)
     }
 }
 
-fn matrix<'a>(rectangle: &'a dyn SignedDistanceField) -> impl SignedDistanceField + 'a {
-    Matrix {
-        sdf: Box::new(rectangle),
-        matrix: [
+fn matrix(rectangle: impl SignedDistanceField) -> impl SignedDistanceField {
+    Matrix::new(
+        [
             [1.0, -1.1], 
             [0.3, -2.0]
-        ]
-    }
+        ],
+        rectangle
+    )
 }
@@ -1,27 +1,14 @@
-use math_vector::Vector;
 use signed_distance_fields::{
     objects::primitives::{Circle, Rectangle, Line, Straight, Plane},
     render::{self, text_mappers},
-    Domain,
+    Domain, Vec2,
 };
 
 fn main() {
     let domain = Domain {
-        p0: Vector {
-                x: -10.0,
-                y: -10.0,
-                z: 0.0,
-            },
-            p1: Vector {
-                x: 10.0,
-                y: 10.0,
-                z: 0.0,
-            },
-            steps: Vector {
-                x: 100,
-                y: 50,
-                z: 0,
-            },
+        p0: Vec2::new(-10.0, -10.0),
+        p1: Vec2::new(10.0, 10.0),
+        steps: Vec2::new(100, 50),
     };
 
 
@@ -0,0 +1,33 @@
+use signed_distance_fields::{
+    objects::{
+        primitives3::{Cuboid, Sphere, Torus},
+        SignedDistanceField3Ext,
+    },
+    render::raymarch::{self, Camera, Settings},
+    Vec3,
+};
+
+fn main() {
+    let scene = Cuboid {
+        w: 2.0,
+        h: 2.0,
+        d: 2.0,
+    }
+    .round(0.1)
+    .smooth_union(Sphere { r: 0.8 }.translate(Vec3::new(0.0, 0.0, 1.2)), 0.3)
+    .union(Torus {
+        major: 2.0,
+        minor: 0.25,
+    });
+
+    let camera = Camera {
+        position: Vec3::new(5.0, -6.0, 4.0),
+        look_at: Vec3::new(0.0, 0.0, 0.5),
+        up: Vec3::new(0.0, 0.0, 1.0),
+        fov: 0.7,
+    };
+
+    let image = raymarch::image(&scene, &camera, 640, 480, &Settings::default());
+    image.save("raymarch.png").unwrap();
+    println!("Saved raymarch.png");
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use crate::{objects::SignedDistanceField, render, Domain, Vec2};
+
+/// Number of bisection steps used to move a crossing onto the isoline
+const BISECTION_STEPS: usize = 48;
+
+/// A cell edge of the sampling grid, identified by its lower left sample and its direction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Edge {
+    /// From sample `(i, j)` to `(i + 1, j)`
+    Horizontal(usize, usize),
+    /// From sample `(i, j)` to `(i, j + 1)`
+    Vertical(usize, usize),
+}
+
+/// Extracts the isoline `sdf(p) = iso` with marching squares over the samples of `domain`
+///
+/// Every crossing found on the grid is refined with bisection on the SDF itself, so the points lie
+/// on the isoline up to floating point precision. Closed polylines end with their first point,
+/// open ones start and end on the border of the domain. An `iso` of `0.0` gives the outline of
+/// the shape, other values give offset outlines.
+pub fn polylines(sdf: &dyn SignedDistanceField, domain: &Domain, iso: f64) -> Vec<Vec<[f64; 2]>> {
+    if domain.steps.x < 2 || domain.steps.y < 2 {
+        return Vec::new();
+    }
+
+    let field = render::matrix(sdf, domain);
+    let inside = |i: usize, j: usize| field[(i, j)] < iso;
+
+    let mut points: Vec<Vec2> = Vec::new();
+    let mut indices: HashMap<Edge, usize> = HashMap::new();
+    let mut neighbours: Vec<Vec<usize>> = Vec::new();
+
+    let mut crossing = |edge: Edge| -> usize {
+        *indices.entry(edge).or_insert_with(|| {
+            let (a, b) = match edge {
+                Edge::Horizontal(i, j) => (field.to_world(i, j), field.to_world(i + 1, j)),
+                Edge::Vertical(i, j) => (field.to_world(i, j), field.to_world(i, j + 1)),
+            };
+            points.push(bisect(sdf, iso, a, b));
+            neighbours.push(Vec::new());
+            points.len() - 1
+        })
+    };
+    let mut segments = Vec::new();
+
+    for i in 0..domain.steps.x - 1 {
+        for j in 0..domain.steps.y - 1 {
+            let corners = [
+                inside(i, j),
+                inside(i + 1, j),
+                inside(i + 1, j + 1),
+                inside(i, j + 1),
+            ];
+            // Edge k lies between the corners k and k + 1
+            let edges = [
+                Edge::Horizontal(i, j),
+                Edge::Vertical(i + 1, j),
+                Edge::Horizontal(i, j + 1),
+                Edge::Vertical(i, j),
+            ];
+            let crossed: Vec<usize> = (0..4)
+                .filter(|&k| corners[k] != corners[(k + 1) % 4])
+                .collect();
+
+            match crossed.len() {
+                2 => segments.push((crossing(edges[crossed[0]]), crossing(edges[crossed[1]]))),
+                4 => {
+                    // Saddle: cut off the two corners, that are on the other side than the center
+                    let center = (field.to_world(i, j) + field.to_world(i + 1, j + 1)) * 0.5;
+                    let center_inside = sdf.call(center) < iso;
+
+                    for k in (0..4).filter(|&k| corners[k] != center_inside) {
+                        segments.push((crossing(edges[(k + 3) % 4]), crossing(edges[k])));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    for &(a, b) in &segments {
+        neighbours[a].push(b);
+        neighbours[b].push(a);
+    }
+
+    link(&points, &neighbours)
+}
+
+/// Finds the point between `a` and `b` where the SDF crosses `iso`
+pub(crate) fn bisect(sdf: &dyn SignedDistanceField, iso: f64, mut a: Vec2, mut b: Vec2) -> Vec2 {
+    let a_inside = sdf.call(a) < iso;
+
+    for _ in 0..BISECTION_STEPS {
+        let middle = (a + b) * 0.5;
+        if (sdf.call(middle) < iso) == a_inside {
+            a = middle;
+        } else {
+            b = middle;
+        }
+    }
+
+    (a + b) * 0.5
+}
+
+/// Walks along the segments to join them into polylines
+fn link(points: &[Vec2], neighbours: &[Vec<usize>]) -> Vec<Vec<[f64; 2]>> {
+    let mut visited = vec![false; points.len()];
+    let mut polylines = Vec::new();
+
+    // Open polylines have to start at one of their ends, so those are walked first
+    let ends = (0..points.len()).filter(|&i| neighbours[i].len() == 1);
+    let rest = (0..points.len()).filter(|&i| neighbours[i].len() > 1);
+
+    for start in ends.chain(rest) {
+        if visited[start] {
+            continue;
+        }
+
+        let mut polyline = vec![start];
+        visited[start] = true;
+        let mut current = start;
+
+        while let Some(&next) = neighbours[current].iter().find(|&&n| !visited[n]) {
+            visited[next] = true;
+            polyline.push(next);
+            current = next;
+        }
+
+        if polyline.len() > 2 && neighbours[current].contains(&start) {
+            polyline.push(start);
+        }
+
+        polylines.push(polyline.into_iter().map(|i| points[i].into()).collect());
+    }
+
+    polylines
+}
@@ -0,0 +1,188 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{Domain, Vec2};
+
+/// Samples of a SDF on the grid of a [`Domain`], see [`render::matrix`](crate::render::matrix)
+///
+/// The samples are stored contiguously, row by row. Like the pixels of an image they are indexed
+/// with `(x, y)`, where `x` counts along `domain.steps.x` and `y` along `domain.steps.y`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SampledField {
+    domain: Domain,
+    values: Vec<f64>,
+}
+
+/// Summary of the values of a [`SampledField`], see [`SampledField::stats`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    /// Smallest value
+    pub min: f64,
+    /// Largest value
+    pub max: f64,
+    /// Average of all values
+    pub mean: f64,
+    /// Number of samples inside of the shape (below 0)
+    pub inside: usize,
+}
+
+impl SampledField {
+    /// A field with the value `f(x, y)` for every sample of `domain`
+    pub fn from_fn(domain: Domain, mut f: impl FnMut(usize, usize) -> f64) -> Self {
+        let (width, height) = (domain.steps.x, domain.steps.y);
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+
+        SampledField { domain, values }
+    }
+
+    /// A field with the given values, row by row
+    ///
+    /// Panics if there isn't exactly one value for every sample of `domain`.
+    pub fn from_values(domain: Domain, values: Vec<f64>) -> Self {
+        assert_eq!(
+            values.len(),
+            domain.steps.x * domain.steps.y,
+            "there has to be exactly one value for every sample"
+        );
+
+        SampledField { domain, values }
+    }
+
+    /// The domain the samples were taken from
+    pub fn domain(&self) -> &Domain {
+        &self.domain
+    }
+
+    /// Number of samples along x
+    pub fn width(&self) -> usize {
+        self.domain.steps.x
+    }
+
+    /// Number of samples along y
+    pub fn height(&self) -> usize {
+        self.domain.steps.y
+    }
+
+    /// All values, row by row
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    /// All values, row by row
+    pub fn values_mut(&mut self) -> &mut [f64] {
+        &mut self.values
+    }
+
+    /// The value of the sample `(x, y)`, if it exists
+    pub fn get(&self, x: usize, y: usize) -> Option<f64> {
+        if x < self.width() && y < self.height() {
+            Some(self.values[y * self.width() + x])
+        } else {
+            None
+        }
+    }
+
+    /// The point of the domain the sample `(x, y)` was taken at
+    ///
+    /// The samples are spread evenly from `domain.p0` to `domain.p1`, including both of them.
+    pub fn to_world(&self, x: usize, y: usize) -> Vec2 {
+        let step = self.step();
+
+        Vec2::new(
+            self.domain.p0.x + step.x * x as f64,
+            self.domain.p0.y + step.y * y as f64,
+        )
+    }
+
+    /// The (fractional) sample coordinates of `p`, the inverse of [`to_world`](Self::to_world)
+    ///
+    /// Along axes with a single sample every point maps to `0`.
+    pub fn to_pixel(&self, p: Vec2) -> Vec2 {
+        let step = self.step();
+        let axis = |v: f64, p0: f64, step: f64| if step == 0.0 { 0.0 } else { (v - p0) / step };
+
+        Vec2::new(
+            axis(p.x, self.domain.p0.x, step.x),
+            axis(p.y, self.domain.p0.y, step.y),
+        )
+    }
+
+    /// Distance between neighbouring samples, which is the same as in
+    /// [`linspace`](itertools_num::linspace)
+    fn step(&self) -> Vec2 {
+        let axis = |p0: f64, p1: f64, steps: usize| {
+            if steps > 1 {
+                (p1 - p0) / (steps as f64 - 1.0)
+            } else {
+                0.0
+            }
+        };
+
+        Vec2::new(
+            axis(self.domain.p0.x, self.domain.p1.x, self.width()),
+            axis(self.domain.p0.y, self.domain.p1.y, self.height()),
+        )
+    }
+
+    /// The values of the row `y`
+    pub fn row(&self, y: usize) -> &[f64] {
+        &self.values[y * self.width()..(y + 1) * self.width()]
+    }
+
+    /// The values of the column `x`, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = f64> + '_ {
+        assert!(x < self.width(), "the column {x} doesn't exist");
+
+        self.values.iter().skip(x).step_by(self.width()).copied()
+    }
+
+    /// All rows, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[f64]> + '_ {
+        (0..self.height()).map(|y| self.row(y))
+    }
+
+    /// All columns, from left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = f64> + '_> + '_ {
+        (0..self.width()).map(|x| self.column(x))
+    }
+
+    /// The smallest value, `None` for empty fields
+    pub fn min(&self) -> Option<f64> {
+        self.values.iter().copied().reduce(f64::min)
+    }
+
+    /// The largest value, `None` for empty fields
+    pub fn max(&self) -> Option<f64> {
+        self.values.iter().copied().reduce(f64::max)
+    }
+
+    /// Summary of all values, `None` for empty fields
+    pub fn stats(&self) -> Option<Stats> {
+        Some(Stats {
+            min: self.min()?,
+            max: self.max()?,
+            mean: self.values.iter().sum::<f64>() / self.values.len() as f64,
+            inside: self.values.iter().filter(|&&v| v < 0.0).count(),
+        })
+    }
+}
+
+impl Index<(usize, usize)> for SampledField {
+    type Output = f64;
+
+    fn index(&self, (x, y): (usize, usize)) -> &f64 {
+        assert!(x < self.width(), "x = {x} is out of bounds");
+
+        &self.values[y * self.width() + x]
+    }
+}
+impl IndexMut<(usize, usize)> for SampledField {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut f64 {
+        assert!(x < self.width(), "x = {x} is out of bounds");
+        let width = self.width();
+
+        &mut self.values[y * width + x]
+    }
+}
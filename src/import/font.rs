@@ -0,0 +1,235 @@
+use std::{
+    fmt,
+    io::{self, Read},
+};
+
+use ttf_parser::{Face, FaceParsingError, GlyphId, OutlineBuilder};
+
+use crate::{
+    objects::primitives::{BezierSegment, BezierSpline, CubicBezier, QuadraticBezier},
+    Vec2,
+};
+
+/// A TrueType or OpenType font, whose glyphs become SDFs
+///
+/// The outlines come from `glyf` or `CFF` tables and the kerning from the `kern` table.
+#[derive(Clone)]
+pub struct Font {
+    data: Vec<u8>,
+    index: u32,
+}
+impl Font {
+    /// The first font of `data`, which is the content of a TTF, OTF or font collection file
+    pub fn new(data: Vec<u8>) -> Result<Self, Error> {
+        Self::with_index(data, 0)
+    }
+
+    /// The font at `index` of a font collection (TTC or OTC file)
+    pub fn with_index(data: Vec<u8>, index: u32) -> Result<Self, Error> {
+        Face::parse(&data, index)?;
+
+        Ok(Font { data, index })
+    }
+
+    /// Reads a font file, see [`new`](Font::new)
+    pub fn read<R: Read>(reader: &mut R) -> Result<Self, Error> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        Self::new(data)
+    }
+
+    fn face(&self) -> Face<'_> {
+        Face::parse(&self.data, self.index).expect("the font was parsed when it was created")
+    }
+
+    /// Number of font units in an em, the size of the font
+    pub fn units_per_em(&self) -> u16 {
+        self.face().units_per_em()
+    }
+
+    /// The outlines of `text` in a font size of `size`, as one SDF
+    ///
+    /// The baseline of the first line starts at `position`. Like in images y points down, so
+    /// further lines (after `\n`) go below the first one. Characters the font doesn't have are
+    /// drawn with its replacement glyph.
+    pub fn text(&self, text: &str, size: f64, position: Vec2) -> BezierSpline {
+        let face = self.face();
+        let mut outline = Outline {
+            segments: Vec::new(),
+            start: Vec2::ZERO,
+            current: Vec2::ZERO,
+            origin: Vec2::ZERO,
+            scale: size / f64::from(face.units_per_em()),
+            position,
+        };
+
+        for (glyph, origin) in layout(&face, text).0 {
+            outline.origin = origin;
+            face.outline_glyph(glyph, &mut outline);
+            outline.close();
+        }
+
+        BezierSpline::new(outline.segments)
+    }
+
+    /// How far the longest line of `text` advances in a font size of `size`
+    pub fn width(&self, text: &str, size: f64) -> f64 {
+        let face = self.face();
+
+        layout(&face, text).1 * size / f64::from(face.units_per_em())
+    }
+}
+impl fmt::Debug for Font {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Font")
+            .field("bytes", &self.data.len())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// Errors of reading fonts
+#[derive(Debug)]
+pub enum Error {
+    /// The reader failed
+    Io(io::Error),
+    /// The data isn't a font
+    Font(FaceParsingError),
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "{error}"),
+            Error::Font(error) => write!(f, "{error}"),
+        }
+    }
+}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::Font(error) => Some(error),
+        }
+    }
+}
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+impl From<FaceParsingError> for Error {
+    fn from(error: FaceParsingError) -> Self {
+        Error::Font(error)
+    }
+}
+
+/// The glyphs of `text` with their origins and the advance of the longest line, all in font
+/// units with y pointing up
+fn layout(face: &Face, text: &str) -> (Vec<(GlyphId, Vec2)>, f64) {
+    let line_height =
+        f64::from(face.ascender()) - f64::from(face.descender()) + f64::from(face.line_gap());
+    let mut glyphs = Vec::new();
+    let mut width: f64 = 0.0;
+
+    for (i, line) in text.lines().enumerate() {
+        let y = -line_height * i as f64;
+        let mut x = 0.0;
+        let mut previous = None;
+
+        for c in line.chars() {
+            let glyph = face.glyph_index(c).unwrap_or(GlyphId(0));
+            if let Some(previous) = previous {
+                x += kerning(face, previous, glyph);
+            }
+            glyphs.push((glyph, Vec2::new(x, y)));
+
+            x += f64::from(face.glyph_hor_advance(glyph).unwrap_or(0));
+            previous = Some(glyph);
+        }
+        width = width.max(x);
+    }
+
+    (glyphs, width)
+}
+
+/// The kerning between two neighbouring glyphs from the `kern` table, in font units
+fn kerning(face: &Face, left: GlyphId, right: GlyphId) -> f64 {
+    let Some(kern) = face.tables().kern else {
+        return 0.0;
+    };
+
+    kern.subtables
+        .into_iter()
+        .filter(|subtable| subtable.horizontal && !subtable.variable && !subtable.has_cross_stream)
+        .filter_map(|subtable| subtable.glyphs_kerning(left, right))
+        .map(f64::from)
+        .sum()
+}
+
+/// Collects the segments of the contours of glyphs, in the coordinates of the text
+struct Outline {
+    segments: Vec<BezierSegment>,
+    /// Where the current contour started
+    start: Vec2,
+    current: Vec2,
+    /// Origin of the current glyph in font units
+    origin: Vec2,
+    /// Size of a font unit
+    scale: f64,
+    position: Vec2,
+}
+impl Outline {
+    /// A point of a glyph, with y flipped to point down
+    fn point(&self, x: f32, y: f32) -> Vec2 {
+        let p = self.origin + Vec2::new(f64::from(x), f64::from(y));
+
+        self.position + Vec2::new(p.x, -p.y) * self.scale
+    }
+}
+impl OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        self.start = self.point(x, y);
+        self.current = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        if p != self.current {
+            self.segments.push(BezierSegment::Linear([self.current, p]));
+        }
+        self.current = p;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (p1, p2) = (self.point(x1, y1), self.point(x, y));
+        self.segments
+            .push(BezierSegment::Quadratic(QuadraticBezier {
+                p0: self.current,
+                p1,
+                p2,
+            }));
+        self.current = p2;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p1, p2, p3) = (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        self.segments.push(BezierSegment::Cubic(CubicBezier {
+            p0: self.current,
+            p1,
+            p2,
+            p3,
+        }));
+        self.current = p3;
+    }
+
+    /// Closes the contour with a straight segment back to its start
+    fn close(&mut self) {
+        if self.current != self.start {
+            self.segments
+                .push(BezierSegment::Linear([self.current, self.start]));
+        }
+        self.current = self.start;
+    }
+}
@@ -0,0 +1,6 @@
+/// Glyphs of TrueType and OpenType fonts, with the `font` feature
+#[cfg(feature = "font")]
+pub mod font;
+/// Filled shapes of SVG documents and path data, with the `svg` feature
+#[cfg(feature = "svg")]
+pub mod svg;
@@ -0,0 +1,701 @@
+use std::{
+    f64::consts::{FRAC_PI_2, TAU},
+    fmt,
+    io::{self, Read},
+};
+
+use roxmltree::{Document, Node};
+
+use crate::{
+    objects::{
+        operators::transforms::Transform2,
+        primitives::{BezierSegment, BezierSpline, CubicBezier, FillRule, QuadraticBezier},
+        SignedDistanceField,
+    },
+    scalar::Scalar,
+    Vec2,
+};
+
+/// Namespace of SVG elements
+const NAMESPACE: &str = "http://www.w3.org/2000/svg";
+
+/// Elements, whose content isn't drawn where it is defined
+const NOT_RENDERED: [&str; 9] = [
+    "clipPath", "defs", "marker", "mask", "metadata", "pattern", "symbol", "style", "title",
+];
+
+/// The filled shapes of an SVG document, as the union of their SDFs
+///
+/// The coordinates are the user units of the document, so y points down like in images.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Drawing {
+    /// Every filled element of the document, with its transform and fill rule applied
+    pub shapes: Vec<BezierSpline>,
+}
+impl<S: Scalar> SignedDistanceField<S> for Drawing {
+    fn call(&self, p: Vec2<S>) -> S {
+        self.shapes
+            .iter()
+            .map(|shape| shape.call(p))
+            .fold(S::from_f64(f64::INFINITY), S::min)
+    }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        self.call_with_gradient(p).1
+    }
+
+    fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+        self.shapes
+            .iter()
+            .map(|shape| shape.call_with_gradient(p))
+            .fold(
+                (S::from_f64(f64::INFINITY), Vec2::splat(S::ZERO)),
+                |closest, shape| if shape.0 < closest.0 { shape } else { closest },
+            )
+    }
+}
+
+/// Errors of reading SVG documents and path data
+#[derive(Debug)]
+pub enum Error {
+    /// The reader failed
+    Io(io::Error),
+    /// The document isn't valid XML
+    Xml(roxmltree::Error),
+    /// The document or the path data doesn't follow SVG
+    Parse(String),
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "{error}"),
+            Error::Xml(error) => write!(f, "{error}"),
+            Error::Parse(message) => write!(f, "{message}"),
+        }
+    }
+}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            Error::Xml(error) => Some(error),
+            Error::Parse(_) => None,
+        }
+    }
+}
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+impl From<roxmltree::Error> for Error {
+    fn from(error: roxmltree::Error) -> Self {
+        Error::Xml(error)
+    }
+}
+
+/// The SDF of the path data `d` (like in the `d` attribute of `<path>` elements), filled by
+/// `fill_rule`
+///
+/// All commands are supported in their absolute and relative form. Subpaths that aren't closed
+/// with `Z` are closed with a straight segment, like they are when they are filled.
+pub fn path(d: &str, fill_rule: FillRule) -> Result<BezierSpline, Error> {
+    Ok(BezierSpline::with_fill_rule(path_segments(d)?, fill_rule))
+}
+
+/// Reads an SVG document, see [`parse`]
+pub fn read<R: Read>(reader: &mut R) -> Result<Drawing, Error> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+
+    parse(&text)
+}
+
+/// The filled shapes of the SVG document `text`
+///
+/// `<path>`, `<rect>`, `<circle>`, `<ellipse>`, `<polygon>` and `<polyline>` elements are
+/// imported with their `transform` and `fill-rule`, including the ones inherited from groups and
+/// the ones given in `style` attributes. Elements with `fill="none"` or `display="none"` are
+/// skipped, like everything that isn't drawn where it is defined (e.g. within `<defs>`). Strokes,
+/// text and `<use>` elements aren't imported.
+pub fn parse(text: &str) -> Result<Drawing, Error> {
+    let document = Document::parse(text)?;
+    let root = document.root_element();
+    if root.tag_name().name() != "svg" {
+        return Err(Error::Parse(format!(
+            "the root element is `{}` instead of `svg`",
+            root.tag_name().name()
+        )));
+    }
+
+    let mut drawing = Drawing::default();
+    let style = Style {
+        transform: Transform2::IDENTITY,
+        fill_rule: FillRule::NonZero,
+        filled: true,
+    };
+    collect(root, style.inherit(root)?, &mut drawing.shapes)?;
+
+    Ok(drawing)
+}
+
+/// Properties, that elements inherit from their parents
+#[derive(Clone, Copy, Debug)]
+struct Style {
+    /// The transforms of the element and all of its parents
+    transform: Transform2,
+    fill_rule: FillRule,
+    filled: bool,
+}
+impl Style {
+    /// The style of `node`, whose parent has `self`
+    fn inherit(mut self, node: Node) -> Result<Style, Error> {
+        if let Some(transform) = node.attribute("transform") {
+            self.transform = self.transform.compose(&transform_list(transform)?);
+        }
+        match property(node, "fill-rule") {
+            Some("nonzero") => self.fill_rule = FillRule::NonZero,
+            Some("evenodd") => self.fill_rule = FillRule::EvenOdd,
+            _ => {}
+        }
+        match property(node, "fill") {
+            Some("none") => self.filled = false,
+            Some("inherit") | None => {}
+            Some(_) => self.filled = true,
+        }
+
+        Ok(self)
+    }
+}
+
+/// Collects the shapes of all children of `node`, which has the `style`
+fn collect(node: Node, style: Style, shapes: &mut Vec<BezierSpline>) -> Result<(), Error> {
+    for child in node.children().filter(Node::is_element) {
+        let name = child.tag_name().name();
+        let namespace = child.tag_name().namespace();
+        if namespace.is_some_and(|namespace| namespace != NAMESPACE)
+            || NOT_RENDERED.contains(&name)
+            || property(child, "display") == Some("none")
+        {
+            continue;
+        }
+
+        let style = style.inherit(child)?;
+
+        let segments = match name {
+            "svg" | "g" | "a" | "switch" => {
+                collect(child, style, shapes)?;
+                continue;
+            }
+            "path" => path_segments(child.attribute("d").unwrap_or_default())?,
+            "rect" => rect(child)?,
+            "circle" => {
+                let r = length(child, "r")?;
+                ellipse(length(child, "cx")?, length(child, "cy")?, r, r)
+            }
+            "ellipse" => ellipse(
+                length(child, "cx")?,
+                length(child, "cy")?,
+                length(child, "rx")?,
+                length(child, "ry")?,
+            ),
+            "polygon" | "polyline" => polygon(child.attribute("points").unwrap_or_default())?,
+            _ => continue,
+        };
+
+        if style.filled && !segments.is_empty() {
+            let segments = segments
+                .iter()
+                .map(|segment| transformed(segment, &style.transform))
+                .collect();
+            shapes.push(BezierSpline::with_fill_rule(segments, style.fill_rule));
+        }
+    }
+
+    Ok(())
+}
+
+/// The value of a property from the `style` attribute, or else from the attribute with its name
+fn property<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    let declaration = node.attribute("style").and_then(|style| {
+        style
+            .split(';')
+            .filter_map(|declaration| declaration.split_once(':'))
+            .find(|(key, _)| key.trim() == name)
+            .map(|(_, value)| value)
+    });
+
+    declaration.or_else(|| node.attribute(name)).map(str::trim)
+}
+
+/// The length in the attribute `name`, which is 0 if it is missing
+///
+/// Only user units are supported, with or without `px`.
+fn length(node: Node, name: &str) -> Result<f64, Error> {
+    let Some(value) = node.attribute(name) else {
+        return Ok(0.0);
+    };
+    let number = value.trim();
+    let number = number.strip_suffix("px").unwrap_or(number);
+
+    number.parse().map_err(|_| {
+        Error::Parse(format!(
+            "`{value}` isn't a supported length of the attribute `{name}`"
+        ))
+    })
+}
+
+/// The outline of a `<rect>` element, whose corners are rounded by `rx` and `ry`
+fn rect(node: Node) -> Result<Vec<BezierSegment>, Error> {
+    let (x, y) = (length(node, "x")?, length(node, "y")?);
+    let (w, h) = (length(node, "width")?, length(node, "height")?);
+    if w <= 0.0 || h <= 0.0 {
+        return Ok(Vec::new());
+    }
+
+    // A missing radius is the same as the other one
+    let (rx, ry) = (length(node, "rx")?, length(node, "ry")?);
+    let (rx, ry) = match (node.has_attribute("rx"), node.has_attribute("ry")) {
+        (true, false) => (rx, rx),
+        (false, true) => (ry, ry),
+        _ => (rx, ry),
+    };
+    let radii = Vec2::new(rx.clamp(0.0, 0.5 * w), ry.clamp(0.0, 0.5 * h));
+
+    let mut outline = Outline::default();
+    outline.move_to(Vec2::new(x + radii.x, y));
+    outline.line_to(Vec2::new(x + w - radii.x, y));
+    outline.arc_to(radii, 0.0, false, true, Vec2::new(x + w, y + radii.y));
+    outline.line_to(Vec2::new(x + w, y + h - radii.y));
+    outline.arc_to(radii, 0.0, false, true, Vec2::new(x + w - radii.x, y + h));
+    outline.line_to(Vec2::new(x + radii.x, y + h));
+    outline.arc_to(radii, 0.0, false, true, Vec2::new(x, y + h - radii.y));
+    outline.line_to(Vec2::new(x, y + radii.y));
+    outline.arc_to(radii, 0.0, false, true, Vec2::new(x + radii.x, y));
+
+    Ok(outline.finish())
+}
+
+/// The outline of an ellipse with the center `(cx, cy)` and the radii `rx` and `ry`
+fn ellipse(cx: f64, cy: f64, rx: f64, ry: f64) -> Vec<BezierSegment> {
+    if rx <= 0.0 || ry <= 0.0 {
+        return Vec::new();
+    }
+
+    let radii = Vec2::new(rx, ry);
+    let mut outline = Outline::default();
+    outline.move_to(Vec2::new(cx + rx, cy));
+    for end in [
+        Vec2::new(cx, cy + ry),
+        Vec2::new(cx - rx, cy),
+        Vec2::new(cx, cy - ry),
+        Vec2::new(cx + rx, cy),
+    ] {
+        outline.arc_to(radii, 0.0, false, true, end);
+    }
+
+    outline.finish()
+}
+
+/// The outline through the `points` of a `<polygon>` or `<polyline>` element
+fn polygon(points: &str) -> Result<Vec<BezierSegment>, Error> {
+    let mut tokens = Tokens::new(points);
+    let mut outline = Outline::default();
+    if tokens.has_number() {
+        outline.move_to(tokens.point()?);
+    }
+    while tokens.has_number() {
+        outline.line_to(tokens.point()?);
+    }
+    if !tokens.at_end() {
+        return Err(tokens.error("expected a number"));
+    }
+
+    Ok(outline.finish())
+}
+
+/// The segments of the path data `d`
+fn path_segments(d: &str) -> Result<Vec<BezierSegment>, Error> {
+    let mut tokens = Tokens::new(d);
+    let mut outline = Outline::default();
+    let mut command = None;
+    // The second control point of the last cubic and the control point of the last quadratic
+    // curve, which `S` and `T` mirror
+    let mut cubic_control = None;
+    let mut quadratic_control = None;
+
+    while !tokens.at_end() {
+        // Without a new command the last one is repeated, except after closing a subpath
+        let c = match (tokens.command(), command) {
+            (Some(c), _) => c,
+            (None, Some(c)) if !matches!(c, b'Z' | b'z') => c,
+            _ => return Err(tokens.error("expected a command")),
+        };
+        command = Some(c);
+
+        let current = outline.current;
+        let relative = c.is_ascii_lowercase();
+        let origin = if relative { current } else { Vec2::ZERO };
+        let mirrored = |control: Option<Vec2>| control.map_or(current, |c| current * 2.0 - c);
+        let (mut next_cubic, mut next_quadratic) = (None, None);
+
+        match c.to_ascii_uppercase() {
+            b'M' => {
+                outline.move_to(origin + tokens.point()?);
+                // Further points are lines
+                command = Some(if relative { b'l' } else { b'L' });
+            }
+            b'L' => outline.line_to(origin + tokens.point()?),
+            b'H' => {
+                let x = tokens.number()? + origin.x;
+                outline.line_to(Vec2::new(x, current.y));
+            }
+            b'V' => {
+                let y = tokens.number()? + origin.y;
+                outline.line_to(Vec2::new(current.x, y));
+            }
+            b'C' => {
+                let p1 = origin + tokens.point()?;
+                let p2 = origin + tokens.point()?;
+                let p3 = origin + tokens.point()?;
+                outline.cubic_to(p1, p2, p3);
+                next_cubic = Some(p2);
+            }
+            b'S' => {
+                let p1 = mirrored(cubic_control);
+                let p2 = origin + tokens.point()?;
+                let p3 = origin + tokens.point()?;
+                outline.cubic_to(p1, p2, p3);
+                next_cubic = Some(p2);
+            }
+            b'Q' => {
+                let p1 = origin + tokens.point()?;
+                let p2 = origin + tokens.point()?;
+                outline.quadratic_to(p1, p2);
+                next_quadratic = Some(p1);
+            }
+            b'T' => {
+                let p1 = mirrored(quadratic_control);
+                let p2 = origin + tokens.point()?;
+                outline.quadratic_to(p1, p2);
+                next_quadratic = Some(p1);
+            }
+            b'A' => {
+                let radii = tokens.point()?;
+                let rotation = tokens.number()?;
+                let large_arc = tokens.flag()?;
+                let sweep = tokens.flag()?;
+                let end = origin + tokens.point()?;
+                outline.arc_to(radii, rotation, large_arc, sweep, end);
+            }
+            b'Z' => outline.close(),
+            _ => return Err(tokens.error(&format!("`{}` isn't a command", char::from(c)))),
+        }
+
+        cubic_control = next_cubic;
+        quadratic_control = next_quadratic;
+    }
+
+    Ok(outline.finish())
+}
+
+/// The transform of a `transform` attribute
+fn transform_list(text: &str) -> Result<Transform2, Error> {
+    let mut tokens = Tokens::new(text);
+    let mut transform = Transform2::IDENTITY;
+
+    while !tokens.at_end() {
+        let name = tokens.name();
+        tokens.expect(b'(')?;
+        let mut arguments = Vec::new();
+        while tokens.has_number() {
+            arguments.push(tokens.number()?);
+        }
+        tokens.expect(b')')?;
+
+        let next = match (name, arguments.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => Transform2 {
+                matrix: [[a, c], [b, d]],
+                translation: Vec2::new(e, f),
+            },
+            ("translate", &[x]) => Transform2::from_translation(Vec2::new(x, 0.0)),
+            ("translate", &[x, y]) => Transform2::from_translation(Vec2::new(x, y)),
+            ("scale", &[s]) => Transform2::from_scale(s),
+            ("scale", &[x, y]) => Transform2::from_matrix([[x, 0.0], [0.0, y]]),
+            ("rotate", &[alpha]) => Transform2::from_rotation(alpha.to_radians()),
+            ("rotate", &[alpha, x, y]) => Transform2::from_translation(Vec2::new(x, y))
+                .compose(&Transform2::from_rotation(alpha.to_radians()))
+                .compose(&Transform2::from_translation(Vec2::new(-x, -y))),
+            ("skewX", &[alpha]) => Transform2::from_shear(alpha.to_radians().tan(), 0.0),
+            ("skewY", &[alpha]) => Transform2::from_shear(0.0, alpha.to_radians().tan()),
+            _ => {
+                return Err(Error::Parse(format!(
+                    "`{name}` with {} arguments isn't a transform in `{text}`",
+                    arguments.len()
+                )))
+            }
+        };
+        transform = transform.compose(&next);
+    }
+
+    Ok(transform)
+}
+
+/// The segment with all of its control points transformed, which transforms the whole curve
+fn transformed(segment: &BezierSegment, transform: &Transform2) -> BezierSegment {
+    let apply = |p: Vec2| transform.apply_point(p);
+
+    match *segment {
+        BezierSegment::Linear(points) => BezierSegment::Linear(points.map(apply)),
+        BezierSegment::Quadratic(curve) => BezierSegment::Quadratic(QuadraticBezier {
+            p0: apply(curve.p0),
+            p1: apply(curve.p1),
+            p2: apply(curve.p2),
+        }),
+        BezierSegment::Cubic(curve) => BezierSegment::Cubic(CubicBezier {
+            p0: apply(curve.p0),
+            p1: apply(curve.p1),
+            p2: apply(curve.p2),
+            p3: apply(curve.p3),
+        }),
+    }
+}
+
+/// Collects the segments of subpaths, which are all closed
+#[derive(Debug, Default)]
+struct Outline {
+    segments: Vec<BezierSegment>,
+    /// Where the current subpath started
+    start: Vec2,
+    current: Vec2,
+}
+impl Outline {
+    fn move_to(&mut self, p: Vec2) {
+        self.close();
+        self.start = p;
+        self.current = p;
+    }
+
+    fn line_to(&mut self, p: Vec2) {
+        if p != self.current {
+            self.segments.push(BezierSegment::Linear([self.current, p]));
+        }
+        self.current = p;
+    }
+
+    fn quadratic_to(&mut self, p1: Vec2, p2: Vec2) {
+        self.segments
+            .push(BezierSegment::Quadratic(QuadraticBezier {
+                p0: self.current,
+                p1,
+                p2,
+            }));
+        self.current = p2;
+    }
+
+    fn cubic_to(&mut self, p1: Vec2, p2: Vec2, p3: Vec2) {
+        self.segments.push(BezierSegment::Cubic(CubicBezier {
+            p0: self.current,
+            p1,
+            p2,
+            p3,
+        }));
+        self.current = p3;
+    }
+
+    /// An elliptical arc to `end`, as in the `A` command, made of cubic curves of at most a
+    /// quarter of the ellipse each
+    ///
+    /// The center of the ellipse is found as in the implementation notes of the SVG
+    /// specification, radii that are too small to reach `end` are scaled up.
+    fn arc_to(&mut self, radii: Vec2, rotation: f64, large_arc: bool, sweep: bool, end: Vec2) {
+        let start = self.current;
+        if start == end {
+            return;
+        }
+        let (mut rx, mut ry) = (radii.x.abs(), radii.y.abs());
+        if rx == 0.0 || ry == 0.0 {
+            self.line_to(end);
+            return;
+        }
+
+        // The start relative to the middle between both ends, in the axes of the ellipse
+        let (sin, cos) = rotation.to_radians().sin_cos();
+        let half = (start - end) * 0.5;
+        let p = Vec2::new(cos * half.x + sin * half.y, cos * half.y - sin * half.x);
+
+        let scale = (p.x * p.x) / (rx * rx) + (p.y * p.y) / (ry * ry);
+        if scale > 1.0 {
+            rx *= scale.sqrt();
+            ry *= scale.sqrt();
+        }
+        let numerator = (rx * rx * ry * ry - rx * rx * p.y * p.y - ry * ry * p.x * p.x).max(0.0);
+        let denominator = rx * rx * p.y * p.y + ry * ry * p.x * p.x;
+        let mut factor = (numerator / denominator).sqrt();
+        if large_arc == sweep {
+            factor = -factor;
+        }
+        let center = Vec2::new(factor * rx * p.y / ry, -factor * ry * p.x / rx);
+
+        let angle = |v: Vec2| v.y.atan2(v.x);
+        let theta = angle(Vec2::new((p.x - center.x) / rx, (p.y - center.y) / ry));
+        let mut delta = angle(Vec2::new((-p.x - center.x) / rx, (-p.y - center.y) / ry)) - theta;
+        if sweep && delta < 0.0 {
+            delta += TAU;
+        } else if !sweep && delta > 0.0 {
+            delta -= TAU;
+        }
+
+        // Points and tangents of the ellipse in the coordinates of the document
+        let middle = (start + end) * 0.5;
+        let rotate = |v: Vec2| Vec2::new(cos * v.x - sin * v.y, sin * v.x + cos * v.y);
+        let point = |a: f64| middle + rotate(center + Vec2::new(rx * a.cos(), ry * a.sin()));
+        let tangent = |a: f64| rotate(Vec2::new(-rx * a.sin(), ry * a.cos()));
+
+        let pieces = (delta.abs() / FRAC_PI_2 - 1e-9).ceil().max(1.0) as usize;
+        let step = delta / pieces as f64;
+        let k = 4.0 / 3.0 * (0.25 * step).tan();
+        for i in 0..pieces {
+            let (a0, a1) = (theta + step * i as f64, theta + step * (i + 1) as f64);
+            let p3 = if i + 1 == pieces { end } else { point(a1) };
+            self.cubic_to(self.current + tangent(a0) * k, p3 - tangent(a1) * k, p3);
+        }
+    }
+
+    /// Closes the current subpath with a straight segment back to its start
+    fn close(&mut self) {
+        self.line_to(self.start);
+    }
+
+    fn finish(mut self) -> Vec<BezierSegment> {
+        self.close();
+
+        self.segments
+    }
+}
+
+/// Reads numbers, flags, commands and names from path data and attributes
+struct Tokens<'a> {
+    text: &'a str,
+    position: usize,
+}
+impl<'a> Tokens<'a> {
+    fn new(text: &'a str) -> Self {
+        Tokens { text, position: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.position).copied()
+    }
+
+    /// Skips whitespace and commas
+    fn skip_separators(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_ascii_whitespace() || c == b',') {
+            self.position += 1;
+        }
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_separators();
+
+        self.peek().is_none()
+    }
+
+    fn has_number(&mut self) -> bool {
+        self.skip_separators();
+
+        matches!(self.peek(), Some(c) if c.is_ascii_digit() || matches!(c, b'+' | b'-' | b'.'))
+    }
+
+    /// The next letter, if there is one
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        let c = self.peek().filter(u8::is_ascii_alphabetic)?;
+        self.position += 1;
+
+        Some(c)
+    }
+
+    /// The next run of letters
+    fn name(&mut self) -> &'a str {
+        self.skip_separators();
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_alphabetic()) {
+            self.position += 1;
+        }
+
+        &self.text[start..self.position]
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), Error> {
+        self.skip_separators();
+        if self.peek() != Some(expected) {
+            return Err(self.error(&format!("expected `{}`", char::from(expected))));
+        }
+        self.position += 1;
+
+        Ok(())
+    }
+
+    fn number(&mut self) -> Result<f64, Error> {
+        self.skip_separators();
+        let bytes = self.text.as_bytes();
+        let digits = |end: &mut usize| {
+            while bytes.get(*end).is_some_and(u8::is_ascii_digit) {
+                *end += 1;
+            }
+        };
+
+        let mut end = self.position;
+        if matches!(bytes.get(end), Some(b'+' | b'-')) {
+            end += 1;
+        }
+        digits(&mut end);
+        if bytes.get(end) == Some(&b'.') {
+            end += 1;
+            digits(&mut end);
+        }
+        // An `e` only belongs to the number, if an exponent follows it
+        if matches!(bytes.get(end), Some(b'e' | b'E')) {
+            let mut exponent = end + 1;
+            if matches!(bytes.get(exponent), Some(b'+' | b'-')) {
+                exponent += 1;
+            }
+            if bytes.get(exponent).is_some_and(u8::is_ascii_digit) {
+                end = exponent;
+                digits(&mut end);
+            }
+        }
+
+        let number = self.text[self.position..end]
+            .parse()
+            .map_err(|_| self.error("expected a number"))?;
+        self.position = end;
+
+        Ok(number)
+    }
+
+    fn point(&mut self) -> Result<Vec2, Error> {
+        Ok(Vec2::new(self.number()?, self.number()?))
+    }
+
+    /// A flag of an arc, which doesn't need to be separated from what follows it
+    fn flag(&mut self) -> Result<bool, Error> {
+        self.skip_separators();
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(self.error("expected a flag")),
+        };
+        self.position += 1;
+
+        Ok(flag)
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::Parse(format!(
+            "{message} at byte {} of `{}`",
+            self.position, self.text
+        ))
+    }
+}
@@ -0,0 +1,251 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::{scalar::Scalar, Vec2};
+
+/// A closed range of numbers `[min, max]`, used to bound the distances within a whole region
+///
+/// The operations don't round outwards, so bounds computed with them can be off by the usual
+/// floating point errors. Like SDFs, intervals are generic over the [`Scalar`] they bound.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Interval<S = f64> {
+    /// Lower bound
+    pub min: S,
+    /// Upper bound
+    pub max: S,
+}
+
+/// Where a region lies relative to the shape of a SDF, see [`Interval::classify`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Classification {
+    /// Every point is inside of the shape
+    Inside,
+    /// Every point is outside of the shape
+    Outside,
+    /// The region might contain a part of the surface
+    Ambiguous,
+}
+
+impl<S: Scalar> Interval<S> {
+    /// The interval between `a` and `b`, which can be given in any order
+    pub fn new(a: S, b: S) -> Self {
+        Interval {
+            min: a.min(b),
+            max: a.max(b),
+        }
+    }
+
+    /// The interval only containing `v`
+    pub fn point(v: S) -> Self {
+        Interval { min: v, max: v }
+    }
+
+    /// `max - min`
+    pub fn width(self) -> S {
+        self.max - self.min
+    }
+
+    /// The middle of the interval
+    pub fn center(self) -> S {
+        (self.min + self.max) * 0.5
+    }
+
+    /// Whether `v` lies within the interval
+    pub fn contains(self, v: S) -> bool {
+        self.min <= v && v <= self.max
+    }
+
+    /// The smallest interval containing both intervals
+    pub fn hull(self, other: Interval<S>) -> Interval<S> {
+        Interval {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// The absolute values of the interval
+    pub fn abs(self) -> Interval<S> {
+        if self.min >= 0.0 {
+            self
+        } else if self.max <= 0.0 {
+            -self
+        } else {
+            Interval {
+                min: S::ZERO,
+                max: self.max.max(-self.min),
+            }
+        }
+    }
+
+    /// The squares of the interval, which unlike `self * self` are never negative
+    pub fn square(self) -> Interval<S> {
+        let abs = self.abs();
+
+        Interval {
+            min: abs.min * abs.min,
+            max: abs.max * abs.max,
+        }
+    }
+
+    /// The square roots of the interval, negative parts are ignored
+    pub fn sqrt(self) -> Interval<S> {
+        Interval {
+            min: self.min.max(S::ZERO).sqrt(),
+            max: self.max.max(S::ZERO).sqrt(),
+        }
+    }
+
+    /// The minima of the numbers in both intervals
+    pub fn min(self, other: Interval<S>) -> Interval<S> {
+        Interval {
+            min: self.min.min(other.min),
+            max: self.max.min(other.max),
+        }
+    }
+
+    /// The maxima of the numbers in both intervals
+    pub fn max(self, other: Interval<S>) -> Interval<S> {
+        Interval {
+            min: self.min.max(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    /// Whether the distances in the interval are all inside, all outside or might change sign
+    pub fn classify(self) -> Classification {
+        if self.max < 0.0 {
+            Classification::Inside
+        } else if self.min > 0.0 {
+            Classification::Outside
+        } else {
+            Classification::Ambiguous
+        }
+    }
+}
+
+impl<S: Scalar> Add for Interval<S> {
+    type Output = Interval<S>;
+
+    fn add(self, rhs: Interval<S>) -> Interval<S> {
+        Interval {
+            min: self.min + rhs.min,
+            max: self.max + rhs.max,
+        }
+    }
+}
+
+impl<S: Scalar> Sub for Interval<S> {
+    type Output = Interval<S>;
+
+    fn sub(self, rhs: Interval<S>) -> Interval<S> {
+        Interval {
+            min: self.min - rhs.max,
+            max: self.max - rhs.min,
+        }
+    }
+}
+
+impl<S: Scalar> Mul for Interval<S> {
+    type Output = Interval<S>;
+
+    fn mul(self, rhs: Interval<S>) -> Interval<S> {
+        let [first, products @ ..] = [
+            self.min * rhs.min,
+            self.min * rhs.max,
+            self.max * rhs.min,
+            self.max * rhs.max,
+        ];
+
+        Interval {
+            min: products.into_iter().fold(first, S::min),
+            max: products.into_iter().fold(first, S::max),
+        }
+    }
+}
+
+impl<S: Scalar> Neg for Interval<S> {
+    type Output = Interval<S>;
+
+    fn neg(self) -> Interval<S> {
+        Interval {
+            min: -self.max,
+            max: -self.min,
+        }
+    }
+}
+
+impl<S: Scalar> Add<f64> for Interval<S> {
+    type Output = Interval<S>;
+
+    fn add(self, rhs: f64) -> Interval<S> {
+        Interval {
+            min: self.min + rhs,
+            max: self.max + rhs,
+        }
+    }
+}
+
+impl<S: Scalar> Sub<f64> for Interval<S> {
+    type Output = Interval<S>;
+
+    fn sub(self, rhs: f64) -> Interval<S> {
+        Interval {
+            min: self.min - rhs,
+            max: self.max - rhs,
+        }
+    }
+}
+
+impl<S: Scalar> Mul<f64> for Interval<S> {
+    type Output = Interval<S>;
+
+    fn mul(self, rhs: f64) -> Interval<S> {
+        Interval::new(self.min * rhs, self.max * rhs)
+    }
+}
+
+impl<S: Scalar> Div<f64> for Interval<S> {
+    type Output = Interval<S>;
+
+    fn div(self, rhs: f64) -> Interval<S> {
+        Interval::new(self.min / rhs, self.max / rhs)
+    }
+}
+
+/// Axis aligned rectangles are vectors of intervals
+impl<S: Scalar> Vec2<Interval<S>> {
+    /// The rectangle spanned by two opposite corners
+    pub fn from_corners(a: Vec2<S>, b: Vec2<S>) -> Self {
+        Vec2::new(Interval::new(a.x, b.x), Interval::new(a.y, b.y))
+    }
+
+    /// The corner with the smallest coordinates
+    pub fn min_corner(self) -> Vec2<S> {
+        Vec2::new(self.x.min, self.y.min)
+    }
+
+    /// The corner with the largest coordinates
+    pub fn max_corner(self) -> Vec2<S> {
+        Vec2::new(self.x.max, self.y.max)
+    }
+
+    /// The middle of the rectangle
+    pub fn center(self) -> Vec2<S> {
+        Vec2::new(self.x.center(), self.y.center())
+    }
+
+    /// Half of the length of the diagonal, which is the largest distance of a point in the
+    /// rectangle from its center
+    pub fn radius(self) -> S {
+        self.x.width().hypot(self.y.width()) * 0.5
+    }
+
+    /// Whether `p` lies within the rectangle
+    pub fn contains(self, p: Vec2<S>) -> bool {
+        self.x.contains(p.x) && self.y.contains(p.y)
+    }
+
+    /// The euclidean lengths of the vectors in the rectangle
+    pub fn length(self) -> Interval<S> {
+        (self.x.square() + self.y.square()).sqrt()
+    }
+}
@@ -4,7 +4,19 @@
 //! rendered with one of this libraries multiple renders.
 #![warn(missing_docs)]
 
-use math_vector::Vector;
+/// Extracts isolines of SDFs as polylines
+pub mod contour;
+
+/// Samples of SDFs on the grid of a domain
+pub mod field;
+
+/// Turns artwork from other formats into SDFs
+pub mod import;
+/// Intervals to bound the distances within whole regions
+pub mod interval;
+
+/// Extracts surfaces of 3D SDFs as triangle meshes
+pub mod mesh;
 
 /// Objects and tools that are useful for working with objects
 pub mod objects;
@@ -12,12 +24,31 @@ pub mod objects;
 /// Renderers and tools that are useful for working with renderers
 pub mod render;
 
+/// Scalar types SDFs can be evaluated with, including dual numbers for automatic differentiation
+pub mod scalar;
+
+/// Vector types used for points and directions
+pub mod vector;
+pub use vector::{Vec2, Vec3};
+
 /// A rectangular space from which points can be samples
+#[derive(Clone, Debug, PartialEq)]
 pub struct Domain {
     /// Upper left corner of the domain
-    pub p0: Vector<f64>,
+    pub p0: Vec2,
     /// Lower right corner of the domain
-    pub p1: Vector<f64>,
+    pub p1: Vec2,
+    /// Resolution of the domain
+    pub steps: Vec2<usize>,
+}
+
+/// A box shaped space from which points can be sampled
+#[derive(Clone, Debug, PartialEq)]
+pub struct Domain3 {
+    /// Corner of the domain with the smallest coordinates
+    pub p0: Vec3,
+    /// Corner of the domain with the largest coordinates
+    pub p1: Vec3,
     /// Resolution of the domain
-    pub steps: Vector<usize>,
+    pub steps: Vec3<usize>,
 }
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use super::Mesh;
+use crate::{
+    contour,
+    objects::{central_difference, SignedDistanceField},
+    render, Domain, Vec2, Vec3,
+};
+
+/// A point of the sampling grid or a crossing on one of its edges
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Point {
+    /// The sample `(i, j)`
+    Sample(usize, usize),
+    /// On the edge from sample `(i, j)` to `(i + 1, j)`
+    Horizontal(usize, usize),
+    /// On the edge from sample `(i, j)` to `(i, j + 1)`
+    Vertical(usize, usize),
+}
+
+/// A corner of the polygon that the inside covers in one cell
+#[derive(Clone, Copy, Debug)]
+struct Corner {
+    point: Point,
+    /// The sides of the cell the corner lies on, side `k` runs from corner `k` to `k + 1` of the
+    /// cell
+    sides: [Option<usize>; 2],
+}
+impl Corner {
+    fn on_side(&self, side: usize) -> bool {
+        self.sides.contains(&Some(side))
+    }
+}
+
+/// Extrudes the inside of a 2D SDF (clipped to `domain`) from `z = 0` to `z = height` into a
+/// closed solid
+///
+/// The outline is found with marching squares over the samples of `domain` and every crossing is
+/// refined with bisection, so the walls stand on the zero isoline up to floating point precision.
+/// The caps and walls don't share vertices, so each of them gets its own normals: the caps point
+/// straight up and down, while the walls use the gradient of the SDF and look smooth. The gradient
+/// is approximated with central differences, whose step is a ten thousandth of a cell, so it fits
+/// the scale of the domain. Where it vanishes (like on plateaus of the SDF), the walls use the
+/// normal of the outline instead.
+///
+/// # Panics
+///
+/// Panics, if `height` isn't positive, since the caps would coincide then.
+pub fn extrude(sdf: &dyn SignedDistanceField, domain: &Domain, height: f64) -> Mesh {
+    assert!(
+        height > 0.0,
+        "the height of an extrusion has to be positive"
+    );
+
+    if domain.steps.x < 2 || domain.steps.y < 2 {
+        return Mesh::default();
+    }
+
+    let (nx, ny) = (domain.steps.x, domain.steps.y);
+    let field = render::matrix(sdf, domain);
+    let inside = |i: usize, j: usize| field[(i, j)] < 0.0;
+
+    // The cells are walked counter clockwise, unless one of the axes of the domain is flipped
+    let size = domain.p1 - domain.p0;
+    let sign = Vec2::new(size.x.signum(), size.y.signum());
+    let counter_clockwise = sign.x * sign.y > 0.0;
+    // Outwards pointing normals of the sides of the domain
+    let border_normals = [
+        Vec2::new(0.0, -sign.y),
+        Vec2::new(sign.x, 0.0),
+        Vec2::new(0.0, sign.y),
+        Vec2::new(-sign.x, 0.0),
+    ];
+    let epsilon = 1e-4 * size.abs().min_element() / (nx.max(ny) - 1) as f64;
+
+    let mut positions: HashMap<Point, Vec2> = HashMap::new();
+    let mut position = |point: Point| -> Vec2 {
+        *positions.entry(point).or_insert_with(|| match point {
+            Point::Sample(i, j) => field.to_world(i, j),
+            Point::Horizontal(i, j) => {
+                contour::bisect(sdf, 0.0, field.to_world(i, j), field.to_world(i + 1, j))
+            }
+            Point::Vertical(i, j) => {
+                contour::bisect(sdf, 0.0, field.to_world(i, j), field.to_world(i, j + 1))
+            }
+        })
+    };
+
+    let (bottom, top) = (0.0, height);
+    let mut mesh = Mesh::default();
+    // Vertices of the caps are keyed by their point and whether they are on top, vertices of the
+    // walls also by the side of the domain they belong to (`None` for the outline)
+    let mut cap_vertices: HashMap<(Point, bool), usize> = HashMap::new();
+    let mut wall_vertices: HashMap<(Point, bool, Option<usize>), usize> = HashMap::new();
+
+    for i in 0..nx - 1 {
+        for j in 0..ny - 1 {
+            let corners = [
+                inside(i, j),
+                inside(i + 1, j),
+                inside(i + 1, j + 1),
+                inside(i, j + 1),
+            ];
+            let samples = [
+                Point::Sample(i, j),
+                Point::Sample(i + 1, j),
+                Point::Sample(i + 1, j + 1),
+                Point::Sample(i, j + 1),
+            ];
+            let crossings = [
+                Point::Horizontal(i, j),
+                Point::Vertical(i + 1, j),
+                Point::Horizontal(i, j + 1),
+                Point::Vertical(i, j),
+            ];
+            // Sides of the cell that lie on the border of the domain
+            let border = [j == 0, i + 2 == nx, j + 2 == ny, i == 0];
+
+            let mut polygon = Vec::new();
+            for k in 0..4 {
+                if corners[k] {
+                    polygon.push(Corner {
+                        point: samples[k],
+                        sides: [Some((k + 3) % 4), Some(k)],
+                    });
+                }
+                if corners[k] != corners[(k + 1) % 4] {
+                    polygon.push(Corner {
+                        point: crossings[k],
+                        sides: [Some(k), None],
+                    });
+                }
+            }
+
+            // Saddle: if the center is outside, the inside corners are cut off separately
+            let saddle = polygon.len() == 6;
+            let center = (field.to_world(i, j) + field.to_world(i + 1, j + 1)) * 0.5;
+            let polygons = if saddle && sdf.call(center) >= 0.0 {
+                // Start at the crossing in front of an inside corner
+                let start = if corners[0] { 5 } else { 0 };
+                let polygon: Vec<Corner> = polygon[start..]
+                    .iter()
+                    .chain(&polygon[..start])
+                    .copied()
+                    .collect();
+                vec![polygon[..3].to_vec(), polygon[3..].to_vec()]
+            } else if polygon.len() >= 3 {
+                vec![polygon]
+            } else {
+                Vec::new()
+            };
+
+            for mut polygon in polygons {
+                if !counter_clockwise {
+                    polygon.reverse();
+                }
+
+                for z in [bottom, top] {
+                    let is_top = z == top;
+                    let indices: Vec<usize> = polygon
+                        .iter()
+                        .map(|corner| {
+                            let p = position(corner.point);
+                            *cap_vertices
+                                .entry((corner.point, is_top))
+                                .or_insert_with(|| {
+                                    let n = if is_top { 1.0 } else { -1.0 };
+                                    mesh.vertices.push(p.extend(z));
+                                    mesh.normals.push(Vec3::new(0.0, 0.0, n));
+                                    mesh.vertices.len() - 1
+                                })
+                        })
+                        .collect();
+
+                    for n in 1..indices.len() - 1 {
+                        if is_top {
+                            mesh.triangles
+                                .push([indices[0], indices[n], indices[n + 1]]);
+                        } else {
+                            mesh.triangles
+                                .push([indices[0], indices[n + 1], indices[n]]);
+                        }
+                    }
+                }
+
+                for n in 0..polygon.len() {
+                    let (a, b) = (polygon[n], polygon[(n + 1) % polygon.len()]);
+                    let wall = if a.sides[1].is_none() && b.sides[1].is_none() {
+                        // Between two crossings, so on the outline
+                        Some(None)
+                    } else {
+                        (0..4)
+                            .find(|&side| border[side] && a.on_side(side) && b.on_side(side))
+                            .map(Some)
+                    };
+                    let Some(wall) = wall else {
+                        continue;
+                    };
+
+                    // The inside is on the left of the polygon, when it is walked counter clockwise
+                    let direction = position(b.point) - position(a.point);
+                    let outline_normal = if counter_clockwise {
+                        -direction.perp()
+                    } else {
+                        direction.perp()
+                    };
+                    let mut vertex = |point: Point, is_top: bool| -> usize {
+                        let p = position(point);
+                        *wall_vertices
+                            .entry((point, is_top, wall))
+                            .or_insert_with(|| {
+                                let n = match wall {
+                                    None => {
+                                        let gradient = central_difference(sdf, p, epsilon);
+                                        if gradient.length() > 0.0 && gradient.length().is_finite()
+                                        {
+                                            gradient.normalize()
+                                        } else {
+                                            outline_normal.normalize()
+                                        }
+                                    }
+                                    Some(side) => border_normals[side],
+                                };
+                                mesh.vertices
+                                    .push(p.extend(if is_top { top } else { bottom }));
+                                mesh.normals.push(n.extend(0.0));
+                                mesh.vertices.len() - 1
+                            })
+                    };
+                    let quad = [
+                        vertex(a.point, false),
+                        vertex(b.point, false),
+                        vertex(b.point, true),
+                        vertex(a.point, true),
+                    ];
+
+                    mesh.triangles.push([quad[0], quad[1], quad[2]]);
+                    mesh.triangles.push([quad[0], quad[2], quad[3]]);
+                }
+            }
+        }
+    }
+
+    mesh
+}
@@ -0,0 +1,451 @@
+use std::{collections::HashMap, fmt, io, str::FromStr, sync::OnceLock};
+
+use itertools_num::linspace;
+
+use crate::{
+    objects::SignedDistanceField3,
+    render::{self, raymarch},
+    Domain3, Vec3,
+};
+
+mod extrude;
+pub use extrude::extrude;
+
+/// Reads and writes Wavefront OBJ files
+pub mod obj;
+
+/// Reads and writes PLY files
+pub mod ply;
+
+/// Reads and writes ASCII and binary STL files
+pub mod stl;
+
+/// Number of bisection steps used to move a crossing onto the surface
+const BISECTION_STEPS: usize = 48;
+
+/// Number of Newton steps used to move a surface nets vertex onto the surface
+const PROJECTION_STEPS: usize = 8;
+
+/// Corner `k` of a cell lies at the offset `(k & 1, k >> 1 & 1, k >> 2 & 1)` from its first sample
+const CORNERS: [[usize; 3]; 8] = [
+    [0, 0, 0],
+    [1, 0, 0],
+    [0, 1, 0],
+    [1, 1, 0],
+    [0, 0, 1],
+    [1, 0, 1],
+    [0, 1, 1],
+    [1, 1, 1],
+];
+
+/// The edges of a cell as pairs of corners, the first corner is the one with smaller coordinates
+const EDGES: [[usize; 2]; 12] = [
+    [0, 1],
+    [2, 3],
+    [4, 5],
+    [6, 7],
+    [0, 2],
+    [1, 3],
+    [4, 6],
+    [5, 7],
+    [0, 4],
+    [1, 5],
+    [2, 6],
+    [3, 7],
+];
+
+/// The faces of a cell as corners in cyclic order
+const FACES: [[usize; 4]; 6] = [
+    [0, 1, 3, 2],
+    [4, 5, 7, 6],
+    [0, 1, 5, 4],
+    [2, 3, 7, 6],
+    [0, 2, 6, 4],
+    [1, 3, 7, 5],
+];
+
+/// An indexed triangle mesh
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Mesh {
+    /// Positions of the vertices
+    pub vertices: Vec<Vec3>,
+    /// Normals of the vertices, they point outwards and have a length of 1
+    pub normals: Vec<Vec3>,
+    /// Indices of the corners of every triangle, counter clockwise when seen from outside
+    pub triangles: Vec<[usize; 3]>,
+}
+impl Mesh {
+    /// Checks that there is a normal for every vertex and that the triangles only refer to
+    /// existing vertices
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.normals.len() != self.vertices.len() {
+            return Err(Error::InvalidMesh(format!(
+                "{} normals for {} vertices",
+                self.normals.len(),
+                self.vertices.len()
+            )));
+        }
+
+        for (t, triangle) in self.triangles.iter().enumerate() {
+            if let Some(&index) = triangle.iter().find(|&&i| i >= self.vertices.len()) {
+                return Err(Error::InvalidMesh(format!(
+                    "triangle {t} refers to vertex {index}, but there are only {}",
+                    self.vertices.len()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the normal of every vertex to the mean of the normals of the triangles around it,
+    /// weighted by their area
+    pub fn compute_normals(&mut self) {
+        self.normals = vec![Vec3::ZERO; self.vertices.len()];
+
+        for &[a, b, c] in &self.triangles {
+            let n =
+                (self.vertices[b] - self.vertices[a]).cross(self.vertices[c] - self.vertices[a]);
+            for i in [a, b, c] {
+                self.normals[i] += n;
+            }
+        }
+
+        for n in &mut self.normals {
+            if n.length() > 0.0 {
+                *n = n.normalize();
+            }
+        }
+    }
+
+    /// Adds a vertex and returns its index
+    fn push_vertex(&mut self, sdf: &dyn SignedDistanceField3, p: Vec3, epsilon: f64) -> usize {
+        self.vertices.push(p);
+        self.normals.push(raymarch::normal(sdf, p, epsilon));
+        self.vertices.len() - 1
+    }
+
+    /// Adds a triangle, turned so that it faces the same way as the normals of its corners
+    fn push_triangle(&mut self, [a, b, c]: [usize; 3]) {
+        let face_normal =
+            (self.vertices[b] - self.vertices[a]).cross(self.vertices[c] - self.vertices[a]);
+        let vertex_normal = self.normals[a] + self.normals[b] + self.normals[c];
+
+        if face_normal.dot(vertex_normal) < 0.0 {
+            self.triangles.push([a, c, b]);
+        } else {
+            self.triangles.push([a, b, c]);
+        }
+    }
+}
+
+/// Errors of reading and writing mesh files
+#[derive(Debug)]
+pub enum Error {
+    /// The reader or writer failed
+    Io(io::Error),
+    /// The file doesn't follow the format
+    Parse {
+        /// Line (starting at 1) of text files, where the problem is
+        line: Option<usize>,
+        /// What is wrong
+        message: String,
+    },
+    /// The mesh can't be written, e.g. because a triangle refers to a vertex that doesn't exist
+    InvalidMesh(String),
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "{error}"),
+            Error::Parse {
+                line: Some(line),
+                message,
+            } => write!(f, "line {line}: {message}"),
+            Error::Parse {
+                line: None,
+                message,
+            } => write!(f, "{message}"),
+            Error::InvalidMesh(message) => write!(f, "invalid mesh: {message}"),
+        }
+    }
+}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+
+/// Parses the next token on a line of a text file
+fn parse<T: FromStr>(token: Option<&str>, line: usize) -> Result<T, Error> {
+    let token = token.ok_or_else(|| Error::Parse {
+        line: Some(line),
+        message: "unexpected end of line".to_string(),
+    })?;
+
+    token.parse().map_err(|_| Error::Parse {
+        line: Some(line),
+        message: format!("`{token}` isn't a valid number"),
+    })
+}
+
+/// Extracts the surface `sdf(p) = iso` with marching cubes over the samples of `domain`
+///
+/// Every vertex is refined with bisection on the SDF itself, so it lies on the surface up to
+/// floating point precision. Vertices are shared between neighbouring triangles, so a surface that
+/// lies completely inside of the domain gives a closed mesh.
+pub fn marching_cubes(sdf: &dyn SignedDistanceField3, domain: &Domain3, iso: f64) -> Mesh {
+    let grid = Grid::new(domain);
+    if !grid.has_cells() {
+        return Mesh::default();
+    }
+
+    let volume = render::volume(sdf, domain);
+    let inside = |[i, j, k]: [usize; 3]| volume[i][j][k] < iso;
+
+    let mut mesh = Mesh::default();
+    let mut indices: HashMap<([usize; 3], usize), usize> = HashMap::new();
+
+    for i in 0..domain.steps.x - 1 {
+        for j in 0..domain.steps.y - 1 {
+            for k in 0..domain.steps.z - 1 {
+                let corner = |c: usize| [i + CORNERS[c][0], j + CORNERS[c][1], k + CORNERS[c][2]];
+                let case = (0..8)
+                    .filter(|&c| inside(corner(c)))
+                    .fold(0, |case, c| case | 1 << c);
+
+                for triangle in &cases()[case] {
+                    let triangle = triangle.map(|edge| {
+                        let [a, b] = EDGES[edge].map(corner);
+                        // The axis along which the edge runs
+                        let axis = (0..3).find(|&n| a[n] != b[n]).unwrap();
+
+                        *indices.entry((a, axis)).or_insert_with(|| {
+                            let p = bisect(sdf, iso, grid.point(a), grid.point(b));
+                            mesh.push_vertex(sdf, p, grid.epsilon)
+                        })
+                    });
+                    mesh.push_triangle(triangle);
+                }
+            }
+        }
+    }
+
+    mesh
+}
+
+/// Extracts the surface `sdf(p) = iso` with surface nets over the samples of `domain`
+///
+/// Every cell that the surface passes through gets one vertex, which starts at the mean of the
+/// crossings on the edges of the cell and is then moved onto the surface along the gradient. This
+/// gives fewer and more evenly shaped triangles than [`marching_cubes`], but the surface has to
+/// stay one cell away from the border of the domain to be closed.
+pub fn surface_nets(sdf: &dyn SignedDistanceField3, domain: &Domain3, iso: f64) -> Mesh {
+    let grid = Grid::new(domain);
+    if !grid.has_cells() {
+        return Mesh::default();
+    }
+
+    let volume = render::volume(sdf, domain);
+    let inside = |[i, j, k]: [usize; 3]| volume[i][j][k] < iso;
+
+    let mut mesh = Mesh::default();
+    let mut cells: HashMap<[usize; 3], usize> = HashMap::new();
+
+    for i in 0..domain.steps.x - 1 {
+        for j in 0..domain.steps.y - 1 {
+            for k in 0..domain.steps.z - 1 {
+                let corner = |c: usize| [i + CORNERS[c][0], j + CORNERS[c][1], k + CORNERS[c][2]];
+                let crossings: Vec<Vec3> = EDGES
+                    .iter()
+                    .map(|edge| edge.map(corner))
+                    .filter(|&[a, b]| inside(a) != inside(b))
+                    .map(|[a, b]| bisect(sdf, iso, grid.point(a), grid.point(b)))
+                    .collect();
+
+                if crossings.is_empty() {
+                    continue;
+                }
+
+                let mut p =
+                    crossings.iter().fold(Vec3::ZERO, |sum, &c| sum + c) / crossings.len() as f64;
+                for _ in 0..PROJECTION_STEPS {
+                    let n = raymarch::normal(sdf, p, grid.epsilon);
+                    p -= n * (sdf.call(p) - iso);
+                }
+
+                cells.insert([i, j, k], mesh.push_vertex(sdf, p, grid.epsilon));
+            }
+        }
+    }
+
+    // Every crossed edge of the grid gets a quad between the four cells around it
+    let steps = [domain.steps.x, domain.steps.y, domain.steps.z];
+    for i in 0..steps[0] {
+        for j in 0..steps[1] {
+            for k in 0..steps[2] {
+                let a = [i, j, k];
+
+                for axis in 0..3 {
+                    let (u, v) = ((axis + 1) % 3, (axis + 2) % 3);
+                    if a[axis] + 1 >= steps[axis] || a[u] == 0 || a[v] == 0 {
+                        continue;
+                    }
+                    if a[u] + 1 >= steps[u] || a[v] + 1 >= steps[v] {
+                        continue;
+                    }
+
+                    let mut b = a;
+                    b[axis] += 1;
+                    if inside(a) == inside(b) {
+                        continue;
+                    }
+
+                    let cell = |du: usize, dv: usize| {
+                        let mut c = a;
+                        c[u] = c[u] + du - 1;
+                        c[v] = c[v] + dv - 1;
+                        cells[&c]
+                    };
+                    let quad = [cell(0, 0), cell(1, 0), cell(1, 1), cell(0, 1)];
+
+                    mesh.push_triangle([quad[0], quad[1], quad[2]]);
+                    mesh.push_triangle([quad[0], quad[2], quad[3]]);
+                }
+            }
+        }
+    }
+
+    mesh
+}
+
+/// The sample positions of a domain
+struct Grid {
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    zs: Vec<f64>,
+    /// Step size used for the normals
+    epsilon: f64,
+}
+impl Grid {
+    fn new(domain: &Domain3) -> Self {
+        let size = (domain.p1 - domain.p0).abs();
+        let steps = domain.steps;
+        let cell = Vec3::new(
+            size.x / (steps.x.max(2) - 1) as f64,
+            size.y / (steps.y.max(2) - 1) as f64,
+            size.z / (steps.z.max(2) - 1) as f64,
+        );
+
+        Grid {
+            xs: linspace(domain.p0.x, domain.p1.x, steps.x).collect(),
+            ys: linspace(domain.p0.y, domain.p1.y, steps.y).collect(),
+            zs: linspace(domain.p0.z, domain.p1.z, steps.z).collect(),
+            epsilon: 1e-4 * cell.min_element(),
+        }
+    }
+
+    fn has_cells(&self) -> bool {
+        self.xs.len() > 1 && self.ys.len() > 1 && self.zs.len() > 1
+    }
+
+    fn point(&self, [i, j, k]: [usize; 3]) -> Vec3 {
+        Vec3::new(self.xs[i], self.ys[j], self.zs[k])
+    }
+}
+
+/// Finds the point between `a` and `b` where the SDF crosses `iso`
+fn bisect(sdf: &dyn SignedDistanceField3, iso: f64, mut a: Vec3, mut b: Vec3) -> Vec3 {
+    let a_inside = sdf.call(a) < iso;
+
+    for _ in 0..BISECTION_STEPS {
+        let middle = (a + b) * 0.5;
+        if (sdf.call(middle) < iso) == a_inside {
+            a = middle;
+        } else {
+            b = middle;
+        }
+    }
+
+    (a + b) * 0.5
+}
+
+/// The triangles (as edges of a cell) for every combination of corners inside of the surface,
+/// where corner `c` is inside if bit `c` of the index is set
+///
+/// Instead of a hand written table, the cases are derived by running marching squares on the six
+/// faces and closing the resulting loops with triangle fans. Ambiguous faces always separate the
+/// inside corners, neighbouring cells agree on that, so the mesh has no holes.
+fn cases() -> &'static [Vec<[usize; 3]>] {
+    static CASES: OnceLock<Vec<Vec<[usize; 3]>>> = OnceLock::new();
+
+    CASES.get_or_init(|| (0..256).map(case).collect())
+}
+
+fn case(case: usize) -> Vec<[usize; 3]> {
+    let inside = |c: usize| case >> c & 1 == 1;
+    let edge = |a: usize, b: usize| {
+        EDGES
+            .iter()
+            .position(|&e| e == [a.min(b), a.max(b)])
+            .unwrap()
+    };
+
+    // Each crossed edge of the cell is shared by two faces, so it ends up with two neighbours
+    let mut neighbours: [Vec<usize>; 12] = Default::default();
+    for face in FACES {
+        // Edge n of the face lies between the corners n and n + 1
+        let edges = [0, 1, 2, 3].map(|n| edge(face[n], face[(n + 1) % 4]));
+        let crossed: Vec<usize> = (0..4)
+            .filter(|&n| inside(face[n]) != inside(face[(n + 1) % 4]))
+            .collect();
+
+        let mut segments = Vec::new();
+        match crossed.len() {
+            2 => segments.push((edges[crossed[0]], edges[crossed[1]])),
+            4 => {
+                for n in (0..4).filter(|&n| inside(face[n])) {
+                    segments.push((edges[(n + 3) % 4], edges[n]));
+                }
+            }
+            _ => {}
+        }
+
+        for (a, b) in segments {
+            neighbours[a].push(b);
+            neighbours[b].push(a);
+        }
+    }
+
+    let mut visited = [false; 12];
+    let mut triangles = Vec::new();
+
+    for start in 0..12 {
+        if visited[start] || neighbours[start].is_empty() {
+            continue;
+        }
+
+        let mut polygon = vec![start];
+        visited[start] = true;
+        let mut current = start;
+        while let Some(&next) = neighbours[current].iter().find(|&&n| !visited[n]) {
+            visited[next] = true;
+            polygon.push(next);
+            current = next;
+        }
+
+        for n in 1..polygon.len() - 1 {
+            triangles.push([polygon[0], polygon[n], polygon[n + 1]]);
+        }
+    }
+
+    triangles
+}
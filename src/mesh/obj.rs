@@ -0,0 +1,138 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, BufReader, Read, Write},
+};
+
+use super::{parse, Error, Mesh};
+use crate::Vec3;
+
+/// Writes the mesh as an OBJ file with vertex normals
+pub fn write<W: Write>(writer: &mut W, mesh: &Mesh) -> Result<(), Error> {
+    mesh.validate()?;
+
+    for p in &mesh.vertices {
+        writeln!(writer, "v {} {} {}", p.x, p.y, p.z)?;
+    }
+    for n in &mesh.normals {
+        writeln!(writer, "vn {} {} {}", n.x, n.y, n.z)?;
+    }
+    // Vertices and their normals share the same index, which starts at 1
+    for triangle in &mesh.triangles {
+        let [a, b, c] = triangle.map(|i| i + 1);
+        writeln!(writer, "f {a}//{a} {b}//{b} {c}//{c}")?;
+    }
+
+    Ok(())
+}
+
+/// Reads the geometry of an OBJ file
+///
+/// Faces with more than three corners are split into triangle fans. A position that is used with
+/// different normals becomes one vertex per normal. Vertices without a normal in the file get one
+/// computed from the triangles around them. Everything except for positions, normals and faces
+/// (like texture coordinates, groups and materials) is ignored.
+pub fn read<R: Read>(reader: &mut R) -> Result<Mesh, Error> {
+    let mut mesh = Mesh::default();
+    let mut normals: Vec<Vec3> = Vec::new();
+    // Faces as corners made of a position and maybe a normal
+    let mut faces: Vec<Vec<(usize, Option<usize>)>> = Vec::new();
+
+    for (line, content) in BufReader::new(reader).lines().enumerate() {
+        let line = line + 1;
+        let content = content?;
+        let content = content.split('#').next().unwrap();
+        let mut tokens = content.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => mesh.vertices.push(Vec3::new(
+                parse(tokens.next(), line)?,
+                parse(tokens.next(), line)?,
+                parse(tokens.next(), line)?,
+            )),
+            Some("vn") => normals.push(Vec3::new(
+                parse(tokens.next(), line)?,
+                parse(tokens.next(), line)?,
+                parse(tokens.next(), line)?,
+            )),
+            Some("f") => {
+                let mut corners = Vec::new();
+                for token in tokens {
+                    let mut parts = token.split('/');
+                    let position = resolve(parts.next(), mesh.vertices.len(), line)?;
+                    let normal = match parts.nth(1) {
+                        Some(part) if !part.is_empty() => {
+                            Some(resolve(Some(part), normals.len(), line)?)
+                        }
+                        _ => None,
+                    };
+                    corners.push((position, normal));
+                }
+
+                if corners.len() < 3 {
+                    return Err(Error::Parse {
+                        line: Some(line),
+                        message: "a face needs at least three corners".to_string(),
+                    });
+                }
+                faces.push(corners);
+            }
+            _ => {}
+        }
+    }
+
+    // The normal of every vertex, vertices keep their index unless they need to be split
+    let mut vertex_normals: Vec<Option<Option<usize>>> = vec![None; mesh.vertices.len()];
+    let mut splits: HashMap<(usize, Option<usize>), usize> = HashMap::new();
+
+    for corners in faces {
+        let corners: Vec<usize> = corners
+            .into_iter()
+            .map(|(position, normal)| match vertex_normals[position] {
+                None => {
+                    vertex_normals[position] = Some(normal);
+                    position
+                }
+                Some(n) if n == normal => position,
+                Some(_) => *splits.entry((position, normal)).or_insert_with(|| {
+                    mesh.vertices.push(mesh.vertices[position]);
+                    vertex_normals.push(Some(normal));
+                    mesh.vertices.len() - 1
+                }),
+            })
+            .collect();
+
+        for n in 1..corners.len() - 1 {
+            mesh.triangles
+                .push([corners[0], corners[n], corners[n + 1]]);
+        }
+    }
+
+    mesh.compute_normals();
+    for (n, normal) in mesh.normals.iter_mut().zip(vertex_normals) {
+        if let Some(Some(normal)) = normal {
+            *n = normals[normal];
+        }
+    }
+
+    Ok(mesh)
+}
+
+/// Turns an index of the file (starting at 1, or counting backwards from the end if it is
+/// negative) into an index of the `count` elements read so far
+fn resolve(token: Option<&str>, count: usize, line: usize) -> Result<usize, Error> {
+    let index: i64 = parse(token, line)?;
+    let resolved = match index {
+        1.. => index - 1,
+        ..=-1 => count as i64 + index,
+        0 => -1,
+    };
+
+    if resolved < 0 || resolved >= count as i64 {
+        return Err(Error::Parse {
+            line: Some(line),
+            message: format!("index {index} is out of range"),
+        });
+    }
+
+    Ok(resolved as usize)
+}
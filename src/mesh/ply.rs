@@ -0,0 +1,156 @@
+use std::io::{BufRead, BufReader, Read, Write};
+
+use super::{parse, Error, Mesh};
+use crate::Vec3;
+
+/// Writes the mesh as an ASCII PLY file with vertex normals
+pub fn write<W: Write>(writer: &mut W, mesh: &Mesh) -> Result<(), Error> {
+    mesh.validate()?;
+
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format ascii 1.0")?;
+    writeln!(writer, "element vertex {}", mesh.vertices.len())?;
+    for property in ["x", "y", "z", "nx", "ny", "nz"] {
+        writeln!(writer, "property double {property}")?;
+    }
+    writeln!(writer, "element face {}", mesh.triangles.len())?;
+    writeln!(writer, "property list uchar uint vertex_indices")?;
+    writeln!(writer, "end_header")?;
+
+    for (p, n) in mesh.vertices.iter().zip(&mesh.normals) {
+        writeln!(writer, "{} {} {} {} {} {}", p.x, p.y, p.z, n.x, n.y, n.z)?;
+    }
+    for [a, b, c] in &mesh.triangles {
+        writeln!(writer, "3 {a} {b} {c}")?;
+    }
+
+    Ok(())
+}
+
+/// An element declared in the header
+struct Element {
+    name: String,
+    count: usize,
+    /// Names of the properties, lists are only supported as the last property
+    properties: Vec<String>,
+}
+
+/// Reads an ASCII PLY file
+///
+/// Only the positions and normals of the vertices and the `vertex_indices` of the faces are read,
+/// faces with more than three corners are split into triangle fans. If the vertices have no
+/// normals, they are computed from the triangles.
+pub fn read<R: Read>(reader: &mut R) -> Result<Mesh, Error> {
+    let mut lines = BufReader::new(reader)
+        .lines()
+        .enumerate()
+        .map(|(line, content)| (line + 1, content));
+    let mut next_line = |what: &str| -> Result<(usize, String), Error> {
+        match lines.next() {
+            Some((line, content)) => Ok((line, content?)),
+            None => Err(Error::Parse {
+                line: None,
+                message: format!("the file ends before {what}"),
+            }),
+        }
+    };
+    let error = |line: usize, message: &str| Error::Parse {
+        line: Some(line),
+        message: message.to_string(),
+    };
+
+    let (line, magic) = next_line("the header")?;
+    if magic.trim() != "ply" {
+        return Err(error(line, "PLY files have to start with `ply`"));
+    }
+
+    let mut elements: Vec<Element> = Vec::new();
+    loop {
+        let (line, content) = next_line("the end of the header")?;
+        let tokens: Vec<&str> = content.split_whitespace().collect();
+
+        match tokens[..] {
+            ["format", "ascii", "1.0"] => {}
+            ["format", ..] => return Err(error(line, "only ASCII PLY files are supported")),
+            ["comment" | "obj_info", ..] | [] => {}
+            ["element", name, count] => elements.push(Element {
+                name: name.to_string(),
+                count: parse(Some(count), line)?,
+                properties: Vec::new(),
+            }),
+            ["property", .., name] => match elements.last_mut() {
+                Some(element) => element.properties.push(name.to_string()),
+                None => return Err(error(line, "property without an element")),
+            },
+            ["end_header"] => break,
+            _ => return Err(error(line, &format!("unexpected header line `{content}`"))),
+        }
+    }
+
+    let mut mesh = Mesh::default();
+    let mut has_normals = false;
+
+    for element in &elements {
+        let position = |name: &str| element.properties.iter().position(|p| p == name);
+
+        for _ in 0..element.count {
+            let (line, content) = next_line(&format!("all {} {}s", element.count, element.name))?;
+            let values: Vec<&str> = content.split_whitespace().collect();
+            let value = |index: Option<usize>| -> Result<f64, Error> {
+                parse(index.and_then(|i| values.get(i).copied()), line)
+            };
+
+            match element.name.as_str() {
+                "vertex" => {
+                    let [x, y, z, nx, ny, nz] = ["x", "y", "z", "nx", "ny", "nz"].map(position);
+                    mesh.vertices
+                        .push(Vec3::new(value(x)?, value(y)?, value(z)?));
+
+                    has_normals = nx.is_some() && ny.is_some() && nz.is_some();
+                    if has_normals {
+                        mesh.normals
+                            .push(Vec3::new(value(nx)?, value(ny)?, value(nz)?));
+                    }
+                }
+                "face" => {
+                    let list = position("vertex_indices")
+                        .or_else(|| position("vertex_index"))
+                        .ok_or_else(|| error(line, "faces need `vertex_indices`"))?;
+                    // Lists are the last property, so everything after the length belongs to it
+                    let count: usize = parse(values.get(list).copied(), line)?;
+                    let end = (list + 1).checked_add(count);
+                    let corners = end
+                        .and_then(|end| values.get(list + 1..end))
+                        .ok_or_else(|| error(line, "the face has too few indices"))?
+                        .iter()
+                        .map(|&index| parse::<usize>(Some(index), line))
+                        .collect::<Result<Vec<_>, _>>()?;
+
+                    if count < 3 {
+                        return Err(error(line, "a face needs at least three corners"));
+                    }
+                    for n in 1..count - 1 {
+                        mesh.triangles
+                            .push([corners[0], corners[n], corners[n + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if !has_normals {
+        mesh.normals = vec![Vec3::ZERO; mesh.vertices.len()];
+    }
+    if let Err(Error::InvalidMesh(message)) = mesh.validate() {
+        return Err(Error::Parse {
+            line: None,
+            message,
+        });
+    }
+    if !has_normals {
+        mesh.compute_normals();
+    }
+
+    Ok(mesh)
+}
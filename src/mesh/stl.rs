@@ -0,0 +1,195 @@
+use std::io::{Read, Write};
+
+use super::{parse, Error, Mesh};
+use crate::Vec3;
+
+/// Header of binary files, it must not start with `solid`, so they aren't mistaken for ASCII files
+const HEADER: &[u8] = b"binary STL written by signed_distance_fields";
+
+/// Writes the mesh as an ASCII STL file
+///
+/// STL has no vertex normals, so the normals of the facets are computed from the triangles.
+pub fn write_ascii<W: Write>(writer: &mut W, mesh: &Mesh) -> Result<(), Error> {
+    mesh.validate()?;
+
+    writeln!(writer, "solid")?;
+    for triangle in &mesh.triangles {
+        let [a, b, c] = triangle.map(|i| mesh.vertices[i]);
+        let n = facet_normal(a, b, c);
+
+        writeln!(writer, "  facet normal {} {} {}", n.x, n.y, n.z)?;
+        writeln!(writer, "    outer loop")?;
+        for p in [a, b, c] {
+            writeln!(writer, "      vertex {} {} {}", p.x, p.y, p.z)?;
+        }
+        writeln!(writer, "    endloop")?;
+        writeln!(writer, "  endfacet")?;
+    }
+    writeln!(writer, "endsolid")?;
+
+    Ok(())
+}
+
+/// Writes the mesh as a binary STL file
+///
+/// The coordinates are stored as `f32`, so they lose precision. STL has no vertex normals, so the
+/// normals of the facets are computed from the triangles.
+pub fn write_binary<W: Write>(writer: &mut W, mesh: &Mesh) -> Result<(), Error> {
+    mesh.validate()?;
+    let count = u32::try_from(mesh.triangles.len()).map_err(|_| {
+        Error::InvalidMesh(format!(
+            "binary STL files can't hold {} triangles",
+            mesh.triangles.len()
+        ))
+    })?;
+
+    let mut header = [0; 80];
+    header[..HEADER.len()].copy_from_slice(HEADER);
+    writer.write_all(&header)?;
+    writer.write_all(&count.to_le_bytes())?;
+
+    for triangle in &mesh.triangles {
+        let [a, b, c] = triangle.map(|i| mesh.vertices[i]);
+
+        for p in [facet_normal(a, b, c), a, b, c] {
+            for v in [p.x, p.y, p.z] {
+                writer.write_all(&(v as f32).to_le_bytes())?;
+            }
+        }
+        // Attribute byte count, which is unused
+        writer.write_all(&[0, 0])?;
+    }
+
+    Ok(())
+}
+
+/// Reads an ASCII or binary STL file
+///
+/// STL stores every triangle on its own, so every triangle gets its own three vertices, which all
+/// have the normal of the facet.
+pub fn read<R: Read>(reader: &mut R) -> Result<Mesh, Error> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    // Some binary files start with `solid` as well, but their size gives them away
+    let binary_size = bytes
+        .get(80..84)
+        .map(|count| 84 + 50 * u32::from_le_bytes(count.try_into().unwrap()) as usize);
+    if !bytes.starts_with(b"solid") || binary_size == Some(bytes.len()) {
+        read_binary(&bytes)
+    } else {
+        read_ascii(&bytes)
+    }
+}
+
+fn read_binary(bytes: &[u8]) -> Result<Mesh, Error> {
+    let error = |message: String| Error::Parse {
+        line: None,
+        message,
+    };
+
+    let count = bytes
+        .get(80..84)
+        .ok_or_else(|| error("the file is too short for a binary STL header".to_string()))?;
+    let count = u32::from_le_bytes(count.try_into().unwrap()) as usize;
+    if bytes.len() != 84 + 50 * count {
+        return Err(error(format!(
+            "a binary STL file with {count} triangles should have {} bytes, but it has {}",
+            84 + 50 * count,
+            bytes.len()
+        )));
+    }
+
+    let mut mesh = Mesh::default();
+    for facet in bytes[84..].chunks_exact(50) {
+        let [n, a, b, c] = [0, 1, 2, 3].map(|v| {
+            let component = |c: usize| {
+                let offset = 12 * v + 4 * c;
+                f32::from_le_bytes(facet[offset..offset + 4].try_into().unwrap()) as f64
+            };
+            Vec3::new(component(0), component(1), component(2))
+        });
+
+        push_facet(&mut mesh, n, [a, b, c]);
+    }
+
+    Ok(mesh)
+}
+
+fn read_ascii(bytes: &[u8]) -> Result<Mesh, Error> {
+    let text = std::str::from_utf8(bytes).map_err(|error| Error::Parse {
+        line: None,
+        message: format!("ASCII STL files have to be valid UTF-8: {error}"),
+    })?;
+
+    let mut mesh = Mesh::default();
+    let mut normal = None;
+    let mut corners = Vec::new();
+
+    for (line, content) in text.lines().enumerate() {
+        let line = line + 1;
+        let error = |message: &str| Error::Parse {
+            line: Some(line),
+            message: message.to_string(),
+        };
+        let mut tokens = content.split_whitespace();
+        let point = |tokens: &mut std::str::SplitWhitespace| -> Result<Vec3, Error> {
+            Ok(Vec3::new(
+                parse(tokens.next(), line)?,
+                parse(tokens.next(), line)?,
+                parse(tokens.next(), line)?,
+            ))
+        };
+
+        match tokens.next() {
+            Some("facet") => {
+                if tokens.next() != Some("normal") {
+                    return Err(error("expected `facet normal`"));
+                }
+                normal = Some(point(&mut tokens)?);
+                corners.clear();
+            }
+            Some("vertex") => {
+                if normal.is_none() || corners.len() == 3 {
+                    return Err(error("unexpected vertex"));
+                }
+                corners.push(point(&mut tokens)?);
+            }
+            Some("endfacet") => {
+                let (Some(n), &[a, b, c]) = (normal.take(), &corners[..]) else {
+                    return Err(error("a facet needs a normal and three vertices"));
+                };
+                push_facet(&mut mesh, n, [a, b, c]);
+            }
+            Some("solid" | "outer" | "endloop" | "endsolid") | None => {}
+            Some(keyword) => return Err(error(&format!("unknown keyword `{keyword}`"))),
+        }
+    }
+
+    Ok(mesh)
+}
+
+/// Adds a triangle with its own vertices, the normal is recomputed if the file doesn't have one
+fn push_facet(mesh: &mut Mesh, n: Vec3, [a, b, c]: [Vec3; 3]) {
+    let n = if n.length() > 0.0 {
+        n.normalize()
+    } else {
+        facet_normal(a, b, c)
+    };
+
+    let first = mesh.vertices.len();
+    mesh.vertices.extend([a, b, c]);
+    mesh.normals.extend([n; 3]);
+    mesh.triangles.push([first, first + 1, first + 2]);
+}
+
+/// Normal of a counter clockwise triangle, or zero if it has no area
+fn facet_normal(a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+    let n = (b - a).cross(c - a);
+
+    if n.length() > 0.0 {
+        n.normalize()
+    } else {
+        Vec3::ZERO
+    }
+}
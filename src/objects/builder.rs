@@ -0,0 +1,323 @@
+use std::ops::{BitAnd, BitOr, BitXor, Not, Sub};
+
+#[cfg(feature = "svg")]
+use crate::import::svg::Drawing;
+use crate::{Vec2, Vec3};
+
+use super::{
+    operators::{
+        boolean::{
+            Blend, Complement, Difference, Intersection, SmoothDifference, SmoothIntersection,
+            SmoothUnion, Union, Xor,
+        },
+        transforms::{Affine2, Matrix, Rotate, Rotate3, Scale, Transform2, Transformed, Translate},
+        CentralDifferences, Smooth,
+    },
+    primitives::{
+        BezierSpline, Circle, CubicBezier, Line, MultiPolygon, Plane, Polygon, QuadraticBezier,
+        Rectangle, Straight,
+    },
+    primitives3::{
+        Capsule, Cone, Cuboid, Cylinder, Ellipsoid, Plane as Plane3, RoundedCuboid, Sphere, Torus,
+    },
+    SignedDistanceField, SignedDistanceField3, F, F3,
+};
+
+/// Fluent methods to wrap any SDF into an operator
+///
+/// ```
+/// use signed_distance_fields::{
+///     objects::{
+///         primitives::{Circle, Rectangle},
+///         SignedDistanceFieldExt,
+///     },
+///     Vec2,
+/// };
+///
+/// let scene = Circle { r: 5.0 }
+///     .smooth_union(Rectangle { w: 15.0, h: 5.0 }, 1.0)
+///     .translate(Vec2::new(2.0, 0.0));
+/// ```
+pub trait SignedDistanceFieldExt: SignedDistanceField + Sized {
+    /// Translate the SDF by `p`
+    fn translate(self, p: Vec2) -> Translate<Self> {
+        Translate { p, sdf: self }
+    }
+
+    /// Rotate the SDF clockwise by `alpha` (in radians), see [`Rotate`]
+    fn rotate(self, alpha: f64) -> Rotate<Self> {
+        Rotate { alpha, sdf: self }
+    }
+
+    /// Scale the SDF uniformly by `s`
+    fn scale(self, s: f64) -> Scale<Self> {
+        Scale {
+            scale: Vec2::splat(s),
+            sdf: self,
+        }
+    }
+
+    /// Multiply the SDF with a matrix
+    fn matrix(self, matrix: [[f64; 2]; 2]) -> Matrix<Self> {
+        Matrix::new(matrix, self)
+    }
+
+    /// Apply the affine transform `matrix * p + translation` to the SDF
+    fn affine(self, matrix: [[f64; 2]; 2], translation: Vec2) -> Affine2<Self> {
+        Affine2::new(matrix, translation, self)
+    }
+
+    /// Apply a [`Transform2`] to the SDF, further transforms are composed into it
+    fn transformed(self, transform: Transform2) -> Transformed<Self> {
+        Transformed::new(transform, self)
+    }
+
+    /// Round the edges of the SDF by `k`
+    fn round(self, k: f64) -> Smooth<Self> {
+        Smooth { sdf: self, k }
+    }
+
+    /// Approximate the gradient with central differences of the step size `epsilon`, see
+    /// [`CentralDifferences`]
+    fn gradient_epsilon(self, epsilon: f64) -> CentralDifferences<Self> {
+        CentralDifferences { sdf: self, epsilon }
+    }
+
+    /// Create a union with `other` (`self ∪ other`)
+    fn union<B: SignedDistanceField>(self, other: B) -> Union<Self, B> {
+        Union { a: self, b: other }
+    }
+
+    /// Create an intersection with `other` (`self ∩ other`)
+    fn intersection<B: SignedDistanceField>(self, other: B) -> Intersection<Self, B> {
+        Intersection { a: self, b: other }
+    }
+
+    /// Take `other` away from the SDF (`self\other`)
+    fn difference<B: SignedDistanceField>(self, other: B) -> Difference<Self, B> {
+        Difference { a: self, b: other }
+    }
+
+    /// Create the symmetric difference with `other` (`self ⊕ other`)
+    fn xor<B: SignedDistanceField>(self, other: B) -> Xor<Self, B> {
+        Xor { a: self, b: other }
+    }
+
+    /// Swap the inside and the outside of the SDF (`¬self`)
+    fn complement(self) -> Complement<Self> {
+        Complement { sdf: self }
+    }
+
+    /// Create a smooth union with `other` using a polynomial blend of radius `k`
+    fn smooth_union<B: SignedDistanceField>(self, other: B, k: f64) -> SmoothUnion<Self, B> {
+        SmoothUnion {
+            a: self,
+            b: other,
+            k,
+            blend: Blend::Polynomial,
+        }
+    }
+
+    /// Create a smooth intersection with `other` using a polynomial blend of radius `k`
+    fn smooth_intersection<B: SignedDistanceField>(
+        self,
+        other: B,
+        k: f64,
+    ) -> SmoothIntersection<Self, B> {
+        SmoothIntersection {
+            a: self,
+            b: other,
+            k,
+            blend: Blend::Polynomial,
+        }
+    }
+
+    /// Smoothly take `other` away from the SDF using a polynomial blend of radius `k`
+    fn smooth_difference<B: SignedDistanceField>(
+        self,
+        other: B,
+        k: f64,
+    ) -> SmoothDifference<Self, B> {
+        SmoothDifference {
+            a: self,
+            b: other,
+            k,
+            blend: Blend::Polynomial,
+        }
+    }
+}
+impl<T: SignedDistanceField> SignedDistanceFieldExt for T {}
+
+/// Fluent methods to wrap any 3D SDF into an operator
+pub trait SignedDistanceField3Ext: SignedDistanceField3 + Sized {
+    /// Translate the SDF by `p`
+    fn translate(self, p: Vec3) -> Translate<Self, Vec3> {
+        Translate { p, sdf: self }
+    }
+
+    /// Rotate the SDF counter clockwise by `alpha` (in radians) around `axis`, which has to have a
+    /// length of 1
+    fn rotate(self, alpha: f64, axis: Vec3) -> Rotate3<Self> {
+        Rotate3 {
+            alpha,
+            axis,
+            sdf: self,
+        }
+    }
+
+    /// Scale the SDF uniformly by `s`
+    fn scale(self, s: f64) -> Scale<Self, Vec3> {
+        Scale {
+            scale: Vec3::splat(s),
+            sdf: self,
+        }
+    }
+
+    /// Round the edges of the SDF by `k`
+    fn round(self, k: f64) -> Smooth<Self> {
+        Smooth { sdf: self, k }
+    }
+
+    /// Create a union with `other` (`self ∪ other`)
+    fn union<B: SignedDistanceField3>(self, other: B) -> Union<Self, B> {
+        Union { a: self, b: other }
+    }
+
+    /// Create an intersection with `other` (`self ∩ other`)
+    fn intersection<B: SignedDistanceField3>(self, other: B) -> Intersection<Self, B> {
+        Intersection { a: self, b: other }
+    }
+
+    /// Take `other` away from the SDF (`self\other`)
+    fn difference<B: SignedDistanceField3>(self, other: B) -> Difference<Self, B> {
+        Difference { a: self, b: other }
+    }
+
+    /// Create a smooth union with `other` using a polynomial blend of radius `k`
+    fn smooth_union<B: SignedDistanceField3>(self, other: B, k: f64) -> SmoothUnion<Self, B> {
+        SmoothUnion {
+            a: self,
+            b: other,
+            k,
+            blend: Blend::Polynomial,
+        }
+    }
+
+    /// Create a smooth intersection with `other` using a polynomial blend of radius `k`
+    fn smooth_intersection<B: SignedDistanceField3>(
+        self,
+        other: B,
+        k: f64,
+    ) -> SmoothIntersection<Self, B> {
+        SmoothIntersection {
+            a: self,
+            b: other,
+            k,
+            blend: Blend::Polynomial,
+        }
+    }
+
+    /// Smoothly take `other` away from the SDF using a polynomial blend of radius `k`
+    fn smooth_difference<B: SignedDistanceField3>(
+        self,
+        other: B,
+        k: f64,
+    ) -> SmoothDifference<Self, B> {
+        SmoothDifference {
+            a: self,
+            b: other,
+            k,
+            blend: Blend::Polynomial,
+        }
+    }
+}
+impl<T: SignedDistanceField3> SignedDistanceField3Ext for T {}
+
+/// Implements `|` (union), `&` (intersection), `-` (difference), `^` (symmetric difference) and `!`
+/// (complement) for the 2D and 3D SDF types of this crate
+macro_rules! impl_boolean_ops {
+    ($($ty:ident $(<$($g:ident),+>)?),* $(,)?) => {$(
+        impl<$($($g,)+)? Rhs> BitOr<Rhs> for $ty $(<$($g),+>)? {
+            type Output = Union<Self, Rhs>;
+
+            fn bitor(self, rhs: Rhs) -> Self::Output {
+                Union { a: self, b: rhs }
+            }
+        }
+
+        impl<$($($g,)+)? Rhs> BitAnd<Rhs> for $ty $(<$($g),+>)? {
+            type Output = Intersection<Self, Rhs>;
+
+            fn bitand(self, rhs: Rhs) -> Self::Output {
+                Intersection { a: self, b: rhs }
+            }
+        }
+
+        impl<$($($g,)+)? Rhs> Sub<Rhs> for $ty $(<$($g),+>)? {
+            type Output = Difference<Self, Rhs>;
+
+            fn sub(self, rhs: Rhs) -> Self::Output {
+                Difference { a: self, b: rhs }
+            }
+        }
+
+        impl<$($($g,)+)? Rhs> BitXor<Rhs> for $ty $(<$($g),+>)? {
+            type Output = Xor<Self, Rhs>;
+
+            fn bitxor(self, rhs: Rhs) -> Self::Output {
+                Xor { a: self, b: rhs }
+            }
+        }
+
+        impl$(<$($g),+>)? Not for $ty $(<$($g),+>)? {
+            type Output = Complement<Self>;
+
+            fn not(self) -> Self::Output {
+                Complement { sdf: self }
+            }
+        }
+    )*};
+}
+
+#[cfg(feature = "svg")]
+impl_boolean_ops!(Drawing);
+impl_boolean_ops!(
+    F<S>,
+    F3<S>,
+    Rectangle,
+    Circle,
+    Straight,
+    Line,
+    Plane,
+    Polygon,
+    MultiPolygon,
+    QuadraticBezier,
+    CubicBezier,
+    BezierSpline,
+    Sphere,
+    Cuboid,
+    RoundedCuboid,
+    Torus,
+    Capsule,
+    Cylinder,
+    Cone,
+    Plane3,
+    Ellipsoid,
+    Smooth<T>,
+    CentralDifferences<T>,
+    Translate<T, V>,
+    Rotate<T>,
+    Rotate3<T>,
+    Scale<T, V>,
+    Matrix<T>,
+    Affine2<T>,
+    Transformed<T>,
+    Union<A, B>,
+    Intersection<A, B>,
+    Difference<A, B>,
+    Xor<A, B>,
+    Complement<T>,
+    SmoothUnion<A, B>,
+    SmoothIntersection<A, B>,
+    SmoothDifference<A, B>,
+);
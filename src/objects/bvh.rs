@@ -0,0 +1,199 @@
+use crate::Vec2;
+
+/// Number of segments up to which a node isn't split any further
+const LEAF_SIZE: usize = 4;
+
+/// A bounding volume hierarchy over line segments
+///
+/// It finds the closest segment and the winding number of closed outlines, while only looking at
+/// the segments near the point (or near the ray from it) instead of all of them.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SegmentBvh {
+    segments: Vec<[Vec2; 2]>,
+    nodes: Vec<Node>,
+}
+
+/// A node of the hierarchy with the bounding box of all of its segments
+#[derive(Clone, Copy, Debug)]
+struct Node {
+    min: Vec2,
+    max: Vec2,
+    /// Leaves hold the segments `first..first + count`, inner nodes (with a `count` of 0) have
+    /// their children at `first` and `first + 1`
+    first: usize,
+    count: usize,
+}
+
+impl SegmentBvh {
+    /// The hierarchy over the segments of all closed outlines, every outline ends with a segment
+    /// from its last back to its first vertex
+    pub(crate) fn from_outlines<'a>(outlines: impl IntoIterator<Item = &'a [Vec2]>) -> Self {
+        let segments = outlines
+            .into_iter()
+            .flat_map(|outline| {
+                let next = outline.iter().cycle().skip(1);
+                outline.iter().zip(next).map(|(&a, &b)| [a, b])
+            })
+            .collect();
+
+        SegmentBvh::new(segments)
+    }
+
+    /// The hierarchy over `segments`, which are reordered
+    pub(crate) fn new(mut segments: Vec<[Vec2; 2]>) -> Self {
+        let mut nodes = Vec::new();
+        if !segments.is_empty() {
+            nodes.push(Node::leaf(&segments, 0, segments.len()));
+            split(&mut nodes, &mut segments, 0);
+        }
+
+        SegmentBvh { segments, nodes }
+    }
+
+    /// The segment at the index returned by [`closest`](SegmentBvh::closest)
+    pub(crate) fn segment(&self, index: usize) -> [Vec2; 2] {
+        self.segments[index]
+    }
+
+    /// The index of the segment closest to `p`, `None` if there are no segments
+    pub(crate) fn closest(&self, p: Vec2) -> Option<usize> {
+        let mut best = None;
+        let mut best_distance = f64::INFINITY;
+        let mut stack = Vec::with_capacity(32);
+        if !self.nodes.is_empty() {
+            stack.push((0, self.nodes[0].distance_squared(p)));
+        }
+
+        while let Some((index, distance)) = stack.pop() {
+            if distance >= best_distance {
+                continue;
+            }
+
+            let node = self.nodes[index];
+            if node.count > 0 {
+                for i in node.first..node.first + node.count {
+                    let [a, b] = self.segments[i];
+                    let distance = segment_distance_squared(p, a, b);
+                    if distance < best_distance {
+                        best = Some(i);
+                        best_distance = distance;
+                    }
+                }
+            } else {
+                // The closer child is popped first, which prunes more of the other one
+                let children = [node.first, node.first + 1]
+                    .map(|child| (child, self.nodes[child].distance_squared(p)));
+                let [near, far] = if children[0].1 <= children[1].1 {
+                    children
+                } else {
+                    [children[1], children[0]]
+                };
+                stack.push(far);
+                stack.push(near);
+            }
+        }
+
+        best
+    }
+
+    /// How often the segments wind counter clockwise around `p`, if they form closed outlines
+    ///
+    /// Only segments that cross the ray from `p` towards positive x are counted.
+    pub(crate) fn winding_number(&self, p: Vec2) -> i32 {
+        let mut winding_number = 0;
+        let mut stack = Vec::with_capacity(32);
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+
+        while let Some(index) = stack.pop() {
+            let node = self.nodes[index];
+            if node.max.x < p.x || p.y < node.min.y || node.max.y < p.y {
+                continue;
+            }
+
+            if node.count > 0 {
+                for &[a, b] in &self.segments[node.first..node.first + node.count] {
+                    let side = (b - a).cross(p - a);
+                    if a.y <= p.y && p.y < b.y && side > 0.0 {
+                        winding_number += 1;
+                    } else if b.y <= p.y && p.y < a.y && side < 0.0 {
+                        winding_number -= 1;
+                    }
+                }
+            } else {
+                stack.push(node.first);
+                stack.push(node.first + 1);
+            }
+        }
+
+        winding_number
+    }
+}
+
+impl Node {
+    /// A leaf with the segments `first..first + count`
+    fn leaf(segments: &[[Vec2; 2]], first: usize, count: usize) -> Node {
+        let mut min = Vec2::splat(f64::INFINITY);
+        let mut max = Vec2::splat(f64::NEG_INFINITY);
+        for &[a, b] in &segments[first..first + count] {
+            min = min.min(a).min(b);
+            max = max.max(a).max(b);
+        }
+
+        Node {
+            min,
+            max,
+            first,
+            count,
+        }
+    }
+
+    /// Squared distance from `p` to the bounding box, which is 0 inside of it
+    fn distance_squared(&self, p: Vec2) -> f64 {
+        let outside = (self.min - p).max(p - self.max).max(Vec2::splat(0.0));
+
+        outside.length_squared()
+    }
+}
+
+/// Splits the leaf `index` at the median of the centers of its segments along the longer side of
+/// its bounding box, until the leaves are small enough
+fn split(nodes: &mut Vec<Node>, segments: &mut [[Vec2; 2]], index: usize) {
+    let node = nodes[index];
+    if node.count <= LEAF_SIZE {
+        return;
+    }
+
+    let size = node.max - node.min;
+    let axis = |p: Vec2| if size.x >= size.y { p.x } else { p.y };
+    let half = node.count / 2;
+    segments[node.first..node.first + node.count]
+        .select_nth_unstable_by(half, |s, t| axis(s[0] + s[1]).total_cmp(&axis(t[0] + t[1])));
+
+    let children = nodes.len();
+    nodes.push(Node::leaf(segments, node.first, half));
+    nodes.push(Node::leaf(segments, node.first + half, node.count - half));
+    nodes[index] = Node {
+        first: children,
+        count: 0,
+        ..node
+    };
+
+    split(nodes, segments, children);
+    split(nodes, segments, children + 1);
+}
+
+/// Squared distance from `p` to the segment from `a` to `b`
+fn segment_distance_squared(p: Vec2, a: Vec2, b: Vec2) -> f64 {
+    let pa = p - a;
+    let ba = b - a;
+    let length_squared = ba.length_squared();
+    let h = if length_squared == 0.0 {
+        0.0
+    } else {
+        (pa.dot(ba) / length_squared).clamp(0.0, 1.0)
+    };
+
+    (pa - ba * h).length_squared()
+}
@@ -0,0 +1,257 @@
+use crate::Vec2;
+
+/// Highest degree of the polynomials, which is the degree of the closest point polynomial of a
+/// cubic curve
+const MAX_DEGREE: usize = 5;
+
+/// A polynomial curve `sum(coefficients[i] * t^i)` with `t` in `[0, 1]`, made from the control
+/// points of a Bézier curve of up to degree 3
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Curve {
+    coefficients: [Vec2; 4],
+    degree: usize,
+    start: Vec2,
+    end: Vec2,
+    /// Corners of the bounding box of the control points, which contains the whole curve
+    min: Vec2,
+    max: Vec2,
+}
+
+impl Curve {
+    /// The curve with the Bézier control points `points`, of which there are 2 to 4
+    pub(crate) fn new(points: &[Vec2]) -> Curve {
+        let zero = Vec2::splat(0.0);
+        let coefficients = match *points {
+            [p0, p1] => [p0, p1 - p0, zero, zero],
+            [p0, p1, p2] => [p0, (p1 - p0) * 2.0, p0 - p1 * 2.0 + p2, zero],
+            [p0, p1, p2, p3] => [
+                p0,
+                (p1 - p0) * 3.0,
+                (p0 - p1 * 2.0 + p2) * 3.0,
+                p3 - p0 + (p1 - p2) * 3.0,
+            ],
+            _ => panic!("Bézier curves have 2 to 4 control points"),
+        };
+        let (min, max) = points.iter().fold(
+            (Vec2::splat(f64::INFINITY), Vec2::splat(f64::NEG_INFINITY)),
+            |(min, max), &p| (min.min(p), max.max(p)),
+        );
+
+        Curve {
+            coefficients,
+            degree: points.len() - 1,
+            start: points[0],
+            end: points[points.len() - 1],
+            min,
+            max,
+        }
+    }
+
+    /// The point of the curve at `t`, which is exactly the first and last control point at the ends
+    pub(crate) fn point(&self, t: f64) -> Vec2 {
+        if t == 0.0 {
+            self.start
+        } else if t == 1.0 {
+            self.end
+        } else {
+            self.coefficients[..=self.degree]
+                .iter()
+                .rev()
+                .fold(Vec2::splat(0.0), |sum, &c| sum * t + c)
+        }
+    }
+
+    /// The derivative of the curve at `t`
+    pub(crate) fn derivative(&self, t: f64) -> Vec2 {
+        (1..=self.degree)
+            .rev()
+            .fold(Vec2::splat(0.0), |sum, i| sum * t + self.coefficients[i] * i as f64)
+    }
+
+    /// Corners of the smallest box that contains the whole curve
+    ///
+    /// Besides the ends only the points where the curve turns around in x or y can lie on it.
+    pub(crate) fn bounds(&self) -> (Vec2, Vec2) {
+        let mut min = self.start.min(self.end);
+        let mut max = self.start.max(self.end);
+        for axis in [|p: Vec2| p.x, |p: Vec2| p.y] {
+            let mut derivative = [0.0; 3];
+            for i in 1..=self.degree {
+                derivative[i - 1] = axis(self.coefficients[i]) * i as f64;
+            }
+
+            let mut extrema = [0.0; MAX_DEGREE];
+            let count = unit_roots(&derivative[..self.degree], &mut extrema);
+            for &t in &extrema[..count] {
+                let p = self.point(t);
+                min = min.min(p);
+                max = max.max(p);
+            }
+        }
+
+        (min, max)
+    }
+
+    /// Squared distance from `p` to the bounding box of the curve, which is a lower bound of the
+    /// squared distance to the curve
+    pub(crate) fn box_distance_squared(&self, p: Vec2) -> f64 {
+        (self.min - p)
+            .max(p - self.max)
+            .max(Vec2::splat(0.0))
+            .length_squared()
+    }
+
+    /// The parameter of the point of the curve closest to `p` and the squared distance to it
+    ///
+    /// The closest point is either an end of the curve or a root of `(B(t) - p) · B'(t)`.
+    pub(crate) fn closest(&self, p: Vec2) -> (f64, f64) {
+        let mut offset = self.coefficients;
+        offset[0] -= p;
+
+        let mut polynomial = [0.0; MAX_DEGREE + 1];
+        for (i, &c) in offset[..=self.degree].iter().enumerate() {
+            for j in 1..=self.degree {
+                polynomial[i + j - 1] += c.dot(self.coefficients[j]) * j as f64;
+            }
+        }
+
+        let mut roots = [0.0; MAX_DEGREE];
+        let count = unit_roots(&polynomial[..2 * self.degree], &mut roots);
+
+        [0.0, 1.0]
+            .into_iter()
+            .chain(roots[..count].iter().copied())
+            .map(|t| (t, (self.point(t) - p).length_squared()))
+            .fold((0.0, f64::INFINITY), |best, candidate| {
+                if candidate.1 < best.1 {
+                    candidate
+                } else {
+                    best
+                }
+            })
+    }
+
+    /// How often the curve winds counter clockwise around `p`, as part of closed outlines
+    ///
+    /// The crossings with the ray from `p` towards positive x are counted on the pieces of the curve
+    /// that only go up or down. Like for straight edges a piece that goes up includes its start
+    /// and one that goes down includes its end, so the ends of neighbouring curves are counted once.
+    pub(crate) fn winding_number(&self, p: Vec2) -> i32 {
+        if self.max.x < p.x || p.y < self.min.y || self.max.y < p.y {
+            return 0;
+        }
+
+        let y: [f64; 4] = self.coefficients.map(|c| c.y);
+        let derivative: [f64; 3] = [y[1], 2.0 * y[2], 3.0 * y[3]];
+        let mut extrema = [0.0; MAX_DEGREE];
+        let count = unit_roots(&derivative[..self.degree], &mut extrema);
+
+        let mut y_minus_p = y;
+        y_minus_p[0] -= p.y;
+        let y_minus_p = &y_minus_p[..=self.degree];
+
+        let mut winding_number = 0;
+        let mut start = (0.0, self.start.y);
+        for t in extrema[..count].iter().copied().chain([1.0]) {
+            let end = (t, self.point(t).y);
+            let direction = if start.1 <= p.y && p.y < end.1 {
+                1
+            } else if end.1 <= p.y && p.y < start.1 {
+                -1
+            } else {
+                0
+            };
+            if direction != 0 {
+                let crossing = bisect(y_minus_p, start.0, end.0).unwrap_or(start.0);
+                if self.point(crossing).x > p.x {
+                    winding_number += direction;
+                }
+            }
+            start = end;
+        }
+
+        winding_number
+    }
+}
+
+/// The value of the polynomial with the `coefficients` (lowest degree first) at `t`
+fn evaluate(coefficients: &[f64], t: f64) -> f64 {
+    coefficients.iter().rev().fold(0.0, |sum, &c| sum * t + c)
+}
+
+/// Writes the real roots within `[0, 1]` of the polynomial with the `coefficients` (lowest degree
+/// first) into `roots` in increasing order and returns how many there are
+///
+/// The roots of the derivative split `[0, 1]` into intervals on which the polynomial is monotonic,
+/// so each of them contains at most one root, which is found with bisection.
+fn unit_roots(coefficients: &[f64], roots: &mut [f64; MAX_DEGREE]) -> usize {
+    let degree = match coefficients.iter().rposition(|&c| c != 0.0) {
+        Some(degree) => degree,
+        None => return 0,
+    };
+    let coefficients = &coefficients[..=degree];
+
+    if degree == 0 {
+        return 0;
+    }
+    if degree == 1 {
+        let t = -coefficients[0] / coefficients[1];
+        roots[0] = t;
+        return usize::from((0.0..=1.0).contains(&t));
+    }
+
+    let mut derivative = [0.0; MAX_DEGREE];
+    for i in 1..=degree {
+        derivative[i - 1] = coefficients[i] * i as f64;
+    }
+    let mut extrema = [0.0; MAX_DEGREE];
+    let extrema_count = unit_roots(&derivative[..degree], &mut extrema);
+
+    let mut count = 0;
+    let mut start = 0.0;
+    for end in extrema[..extrema_count].iter().copied().chain([1.0]) {
+        if let Some(t) = bisect(coefficients, start, end) {
+            // A root on the border of two intervals is found in both of them
+            if count == 0 || t > roots[count - 1] {
+                roots[count] = t;
+                count += 1;
+            }
+        }
+        start = end;
+    }
+
+    count
+}
+
+/// The root of the polynomial between `a` and `b`, if it changes its sign in between
+fn bisect(coefficients: &[f64], mut a: f64, mut b: f64) -> Option<f64> {
+    let mut fa = evaluate(coefficients, a);
+    let fb = evaluate(coefficients, b);
+    if fa == 0.0 {
+        return Some(a);
+    }
+    if fb == 0.0 {
+        return Some(b);
+    }
+    if (fa < 0.0) == (fb < 0.0) {
+        return None;
+    }
+
+    loop {
+        let middle = 0.5 * (a + b);
+        if middle <= a || middle >= b {
+            return Some(middle);
+        }
+
+        let f = evaluate(coefficients, middle);
+        if f == 0.0 {
+            return Some(middle);
+        }
+        if (f < 0.0) == (fa < 0.0) {
+            a = middle;
+            fa = f;
+        } else {
+            b = middle;
+        }
+    }
+}
@@ -1,24 +1,295 @@
-use math_vector::Vector;
+use std::sync::Arc;
+
+use crate::{
+    interval::Interval,
+    scalar::{self, Dual, Scalar},
+    Vec2, Vec3,
+};
 
 /// Trait that every SDF must implement
-pub trait SignedDistanceField {
+///
+/// SDFs own their children, so a whole scene is a single value that can be cloned, returned from
+/// functions and shared between threads.
+///
+/// SDFs are evaluated with a [`Scalar`], which is `f64` by default. Implementing the trait for
+/// every scalar lets the SDF be evaluated with [`Dual`] numbers, which gives exact gradients, see
+/// [`scalar::gradient`].
+pub trait SignedDistanceField<S: Scalar = f64>: Send + Sync {
+    /// This is the function that returns the distance for a point
+    fn call(&self, p: Vec2<S>) -> S;
+
+    /// The gradient of the SDF at `p`, which points away from the shape
+    ///
+    /// By default it is approximated with [`central_difference`] and a step size of
+    /// [`GRADIENT_EPSILON`], SDFs that know their gradient override it. Wrap a SDF into
+    /// [`CentralDifferences`](operators::CentralDifferences) to choose another step size.
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        central_difference(self, p, GRADIENT_EPSILON)
+    }
+
+    /// The distance and the gradient at `p`, which is cheaper than calling both on their own for
+    /// operators that would evaluate their children twice otherwise
+    fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+        (self.call(p), self.gradient(p))
+    }
+
+    /// Bounds of the distances of all points in the rectangle `region`
+    ///
+    /// By default the distance at the center is widened by the radius of the region, which holds
+    /// for every SDF that doesn't change faster than the distance to its shape (it is 1-Lipschitz).
+    /// [`Rectangle`](primitives::Rectangle), [`Circle`](primitives::Circle),
+    /// [`Straight`](primitives::Straight), [`Line`](primitives::Line),
+    /// [`Plane`](primitives::Plane) and the operators of this crate compute tighter bounds with
+    /// interval arithmetic. The outline primitives ([`Polygon`](primitives::Polygon),
+    /// [`MultiPolygon`](primitives::MultiPolygon), the Bézier curves and
+    /// [`BezierSpline`](primitives::BezierSpline)), imported SVG drawings and closures use this
+    /// fallback.
+    fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+        let distance = self.call(region.center());
+        let radius = region.radius();
+
+        Interval::new(distance - radius, distance + radius)
+    }
+
+    /// Writes the distances of all `points` into `out`, which has to be just as long
+    ///
+    /// Behind a `dyn` pointer this costs a single virtual call instead of one per point. By
+    /// default it calls [`call`](SignedDistanceField::call) for chunks of [`BATCH_SIZE`] points,
+    /// where the compiler can inline and vectorize it. The operators of this crate pass the whole
+    /// batch on to the SDFs they wrap. The distances are exactly the ones `call` returns.
+    fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+        for (points, out) in batches(points, out) {
+            for (p, out) in points.iter().zip(out) {
+                *out = self.call(*p);
+            }
+        }
+    }
+}
+
+/// Number of points evaluated at once in [`SignedDistanceField::call_batch`]
+pub const BATCH_SIZE: usize = 64;
+
+/// Splits the points and the distances of [`SignedDistanceField::call_batch`] into matching
+/// chunks of at most [`BATCH_SIZE`] elements
+fn batches<'a, P, S>(
+    points: &'a [P],
+    out: &'a mut [S],
+) -> impl Iterator<Item = (&'a [P], &'a mut [S])> {
+    assert_eq!(
+        points.len(),
+        out.len(),
+        "there has to be exactly one distance for every point"
+    );
+
+    points.chunks(BATCH_SIZE).zip(out.chunks_mut(BATCH_SIZE))
+}
+
+/// Evaluates `sdf` in batches at the points moved by `f`, for operators that transform the points
+pub(crate) fn call_batch_mapped<S: Scalar, T: SignedDistanceField<S> + ?Sized>(
+    sdf: &T,
+    points: &[Vec2<S>],
+    out: &mut [S],
+    f: impl Fn(Vec2<S>) -> Vec2<S>,
+) {
+    let mut mapped = [Vec2::splat(S::ZERO); BATCH_SIZE];
+
+    for (points, out) in batches(points, out) {
+        let mapped = &mut mapped[..points.len()];
+        for (mapped, p) in mapped.iter_mut().zip(points) {
+            *mapped = f(*p);
+        }
+        sdf.call_batch(mapped, out);
+    }
+}
+
+/// Evaluates `a` and `b` in batches and combines their distances with `f`, for operators with two
+/// SDFs
+pub(crate) fn call_batch_combined<S: Scalar, A, B>(
+    a: &A,
+    b: &B,
+    points: &[Vec2<S>],
+    out: &mut [S],
+    f: impl Fn(S, S) -> S,
+) where
+    A: SignedDistanceField<S> + ?Sized,
+    B: SignedDistanceField<S> + ?Sized,
+{
+    let mut b_distances = [S::ZERO; BATCH_SIZE];
+
+    for (points, out) in batches(points, out) {
+        let b_distances = &mut b_distances[..points.len()];
+        a.call_batch(points, out);
+        b.call_batch(points, b_distances);
+        for (out, b) in out.iter_mut().zip(b_distances.iter()) {
+            *out = f(*out, *b);
+        }
+    }
+}
+
+/// Step size of the central differences used by the default [`SignedDistanceField::gradient`]
+///
+/// It fits SDFs in units around 1. For other scales wrap the SDF into
+/// [`CentralDifferences`](operators::CentralDifferences) (with
+/// [`gradient_epsilon`](SignedDistanceFieldExt::gradient_epsilon)), which sets the step size for
+/// every user of its gradient.
+pub const GRADIENT_EPSILON: f64 = 1e-6;
+
+/// Approximates the gradient of `sdf` at `p` with central differences of step size `epsilon`
+pub fn central_difference<S: Scalar, T: SignedDistanceField<S> + ?Sized>(
+    sdf: &T,
+    p: Vec2<S>,
+    epsilon: f64,
+) -> Vec2<S> {
+    let dx = Vec2::new(S::from_f64(epsilon), S::ZERO);
+    let dy = Vec2::new(S::ZERO, S::from_f64(epsilon));
+
+    Vec2::new(
+        sdf.call(p + dx) - sdf.call(p - dx),
+        sdf.call(p + dy) - sdf.call(p - dy),
+    ) / S::from_f64(2.0 * epsilon)
+}
+
+/// Forwards every method of [`SignedDistanceField`] to the SDF behind a pointer
+macro_rules! impl_pointer {
+    ($($pointer:ty),+) => {
+        $(
+            impl<S: Scalar, T: SignedDistanceField<S> + ?Sized> SignedDistanceField<S> for $pointer {
+                fn call(&self, p: Vec2<S>) -> S {
+                    (**self).call(p)
+                }
+
+                fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+                    (**self).gradient(p)
+                }
+
+                fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+                    (**self).call_with_gradient(p)
+                }
+
+                fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+                    (**self).eval_interval(region)
+                }
+
+                fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+                    (**self).call_batch(points, out)
+                }
+            }
+        )+
+    };
+}
+impl_pointer!(&T, Box<T>, Arc<T>);
+
+/// Trait that every 3D SDF must implement
+///
+/// The boolean operators and [`Smooth`](operators::Smooth) work for 3D SDFs just like for 2D ones.
+/// Like [`SignedDistanceField`] it is generic over the [`Scalar`] it is evaluated with.
+pub trait SignedDistanceField3<S: Scalar = f64>: Send + Sync {
     /// This is the function that returns the distance for a point
-    fn call(&self, p: Vector<f64>) -> f64;
+    fn call(&self, p: Vec3<S>) -> S;
+}
+
+impl<S: Scalar, T: SignedDistanceField3<S> + ?Sized> SignedDistanceField3<S> for &T {
+    fn call(&self, p: Vec3<S>) -> S {
+        (**self).call(p)
+    }
+}
+impl<S: Scalar, T: SignedDistanceField3<S> + ?Sized> SignedDistanceField3<S> for Box<T> {
+    fn call(&self, p: Vec3<S>) -> S {
+        (**self).call(p)
+    }
+}
+impl<S: Scalar, T: SignedDistanceField3<S> + ?Sized> SignedDistanceField3<S> for Arc<T> {
+    fn call(&self, p: Vec3<S>) -> S {
+        (**self).call(p)
+    }
 }
 
+/// A type erased SDF, that is cheap to clone
+///
+/// Useful to store SDFs of different types in the same collection or struct field.
+pub type SharedSignedDistanceField = Arc<dyn SignedDistanceField>;
+
+/// A type erased 3D SDF, that is cheap to clone
+pub type SharedSignedDistanceField3 = Arc<dyn SignedDistanceField3>;
+
 /// A general purpose SDF with just a distance function
-pub struct F {
+///
+/// The function is evaluated with the scalar `S`. With [`Dual`] numbers the SDF can be used like
+/// any other one and its gradient is exact instead of approximated:
+/// ```
+/// use std::sync::Arc;
+///
+/// use signed_distance_fields::{
+///     objects::{SignedDistanceField, F},
+///     scalar::{Dual, Scalar},
+///     Vec2,
+/// };
+///
+/// let f: F<Dual> = F {
+///     f: Arc::new(|p: Vec2<Dual>| p.x * p.x + p.y.sin()),
+/// };
+///
+/// assert_eq!(f.call(Vec2::new(2.0, 0.0)), 4.0);
+/// assert_eq!(f.gradient(Vec2::new(2.0, 0.0)), Vec2::new(4.0, 1.0));
+/// ```
+#[derive(Clone)]
+pub struct F<S = f64> {
     /// The distance function
-    pub f: Box<dyn Fn(Vector<f64>) -> f64>,
+    pub f: Arc<dyn Fn(Vec2<S>) -> S + Send + Sync>,
 }
-impl SignedDistanceField for F {
-    fn call(&self, p: Vector<f64>) -> f64 {
+impl<S: Scalar> SignedDistanceField<S> for F<S> {
+    fn call(&self, p: Vec2<S>) -> S {
         (self.f)(p)
     }
 }
+impl SignedDistanceField for F<Dual> {
+    fn call(&self, p: Vec2) -> f64 {
+        (self.f)(p.cast()).value
+    }
+
+    fn gradient(&self, p: Vec2) -> Vec2 {
+        scalar::gradient(self, p).1
+    }
+
+    fn call_with_gradient(&self, p: Vec2) -> (f64, Vec2) {
+        scalar::gradient(self, p)
+    }
+}
+
+/// A general purpose 3D SDF with just a distance function
+///
+/// Like [`F`] it can be evaluated with a [`Dual`] function, see [`scalar::gradient3`].
+#[derive(Clone)]
+pub struct F3<S = f64> {
+    /// The distance function
+    pub f: Arc<dyn Fn(Vec3<S>) -> S + Send + Sync>,
+}
+impl<S: Scalar> SignedDistanceField3<S> for F3<S> {
+    fn call(&self, p: Vec3<S>) -> S {
+        (self.f)(p)
+    }
+}
+impl SignedDistanceField3 for F3<Dual> {
+    fn call(&self, p: Vec3) -> f64 {
+        (self.f)(p.cast()).value
+    }
+}
+
+/// Bounding volume hierarchy over the edges of polygons
+mod bvh;
+
+/// Polynomial curves and their closest points, for Bézier curves
+pub(crate) mod curve;
+
+/// Fluent methods and operator overloads (`|`, `&`, `-`) to compose SDFs
+pub mod builder;
+pub use builder::{SignedDistanceField3Ext, SignedDistanceFieldExt};
 
 /// Combine, change, develop SDFs
 pub mod operators;
 
 /// Primitive shapes
 pub mod primitives;
+
+/// Primitive 3D shapes
+pub mod primitives3;
@@ -1,122 +1,1192 @@
-use math_vector::Vector;
+use crate::{interval::Interval, scalar::Scalar, Vec2, Vec3};
+
+use super::{central_difference, SignedDistanceField, SignedDistanceField3};
 
 /// This operator rounds the edges of functions
-pub struct Smooth {
+#[derive(Clone, Debug)]
+pub struct Smooth<T> {
     /// The SDF, that will be smoothed
-    pub sdf: Box<dyn super::SignedDistanceField>,
+    pub sdf: T,
     /// Smoothing factor by which the SDF will be smoothed
     pub k: f64,
 }
-impl super::SignedDistanceField for Smooth {
-    fn call(&self, p: Vector<f64>) -> f64 {
+impl<S: Scalar, T: SignedDistanceField<S>> SignedDistanceField<S> for Smooth<T> {
+    fn call(&self, p: Vec2<S>) -> S {
+        self.sdf.call(p) - self.k
+    }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        self.sdf.gradient(p)
+    }
+
+    fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+        let (distance, gradient) = self.sdf.call_with_gradient(p);
+
+        (distance - self.k, gradient)
+    }
+
+    fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+        self.sdf.eval_interval(region) - self.k
+    }
+
+    fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+        self.sdf.call_batch(points, out);
+        for distance in out {
+            *distance = *distance - self.k;
+        }
+    }
+}
+impl<S: Scalar, T: SignedDistanceField3<S>> SignedDistanceField3<S> for Smooth<T> {
+    fn call(&self, p: Vec3<S>) -> S {
         self.sdf.call(p) - self.k
     }
 }
 
+/// Approximates the gradient of a SDF with central differences of the step size `epsilon`
+///
+/// The default [`SignedDistanceField::gradient`] steps by [`GRADIENT_EPSILON`](super::GRADIENT_EPSILON),
+/// which is too small or too large for SDFs in much smaller or larger units. This replaces the
+/// gradient of any SDF (also analytic ones and ones behind a `dyn` pointer) with one that fits
+/// its scale, while the distances stay the same.
+#[derive(Clone, Debug)]
+pub struct CentralDifferences<T> {
+    /// The SDF, whose gradient will be approximated
+    pub sdf: T,
+    /// Step size of the central differences
+    pub epsilon: f64,
+}
+impl<S: Scalar, T: SignedDistanceField<S>> SignedDistanceField<S> for CentralDifferences<T> {
+    fn call(&self, p: Vec2<S>) -> S {
+        self.sdf.call(p)
+    }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        central_difference(&self.sdf, p, self.epsilon)
+    }
+
+    fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+        self.sdf.eval_interval(region)
+    }
+
+    fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+        self.sdf.call_batch(points, out)
+    }
+}
+
 /// The operators in this module apply linear transforms to SDFs
 pub mod transforms {
-    use math_vector::Vector;
+    use crate::{interval::Interval, objects::call_batch_mapped, scalar::Scalar, Vec2, Vec3};
+
+    use super::{SignedDistanceField, SignedDistanceField3};
+
     /// Translate a SDF
-    pub struct Translate<'a> {
+    ///
+    /// `V` is [`Vec2`] for 2D SDFs and [`Vec3`] for 3D SDFs.
+    #[derive(Clone, Debug)]
+    pub struct Translate<T, V = Vec2> {
         /// The SDF will be translated by p
-        pub p: Vector<f64>,
+        pub p: V,
         /// The SDF, that will be translated
-        pub sdf: Box<&'a dyn super::super::SignedDistanceField>,
+        pub sdf: T,
     }
-    impl super::super::SignedDistanceField for Translate<'_> {
-        fn call(&self, p: Vector<f64>) -> f64 {
-            self.sdf.call(p - self.p)
+    impl<S: Scalar, T: SignedDistanceField<S>> SignedDistanceField<S> for Translate<T> {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.sdf.call(p - self.p.cast())
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.sdf.gradient(p - self.p.cast())
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            self.sdf.call_with_gradient(p - self.p.cast())
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            let region = Vec2::new(region.x - self.p.x, region.y - self.p.y);
+
+            self.sdf.eval_interval(region)
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            let offset = self.p.cast();
+
+            call_batch_mapped(&self.sdf, points, out, |p| p - offset)
+        }
+    }
+    impl<S: Scalar, T: SignedDistanceField3<S>> SignedDistanceField3<S> for Translate<T, Vec3> {
+        fn call(&self, p: Vec3<S>) -> S {
+            self.sdf.call(p - self.p.cast())
         }
     }
 
     /// Rotate a SDF
-    pub struct Rotate<'a> {
-        /// The angle (in radians) by which the SDF will be rotated
+    ///
+    /// The point is rotated counter clockwise by `alpha` before the SDF is evaluated, so the shape
+    /// turns clockwise (with y pointing up).
+    #[derive(Clone, Debug)]
+    pub struct Rotate<T> {
+        /// The angle (in radians) by which the SDF will be rotated clockwise
+        pub alpha: f64,
+        /// The SDF, that will be rotated
+        pub sdf: T,
+    }
+    impl<T> Rotate<T> {
+        /// Rotate by `alpha` around `axis`, like the `axis` of earlier versions
+        ///
+        /// A 2D SDF can only be rotated around the z axis, so `axis` has to point along it. It is
+        /// rotated by `alpha` for `(0, 0, 1)` and by `-alpha` for `(0, 0, -1)`.
+        ///
+        /// # Panics
+        ///
+        /// Panics, if `axis` doesn't point along the z axis.
+        pub fn around(alpha: f64, axis: Vec3, sdf: T) -> Self {
+            assert!(
+                axis.x == 0.0 && axis.y == 0.0 && axis.z != 0.0,
+                "2D SDFs can only be rotated around the z axis"
+            );
+
+            Rotate {
+                alpha: alpha * axis.z.signum(),
+                sdf,
+            }
+        }
+    }
+    impl<S: Scalar, T: SignedDistanceField<S>> SignedDistanceField<S> for Rotate<T> {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.sdf.call(p.rotate(self.alpha))
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.sdf.gradient(p.rotate(self.alpha)).rotate(-self.alpha)
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let (distance, gradient) = self.sdf.call_with_gradient(p.rotate(self.alpha));
+
+            (distance, gradient.rotate(-self.alpha))
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            let region = Transform2::from_rotation(self.alpha).apply_region(region);
+
+            self.sdf.eval_interval(region)
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            // Same as `p.rotate(self.alpha)`, without the sine and cosine for every point
+            let (sin, cos) = self.alpha.sin_cos();
+
+            call_batch_mapped(&self.sdf, points, out, |p| {
+                Vec2::new(p.x * cos - p.y * sin, p.x * sin + p.y * cos)
+            })
+        }
+    }
+
+    /// Rotate a 3D SDF around an axis
+    #[derive(Clone, Debug)]
+    pub struct Rotate3<T> {
+        /// The angle (in radians) by which the SDF will be rotated counter clockwise, when looking
+        /// against the axis
         pub alpha: f64,
-        /// The axis around which the SDF will be rotated
-        pub axis: Vector<f64>,
+        /// The axis around which the SDF will be rotated, it has to have a length of 1
+        pub axis: Vec3,
         /// The SDF, that will be rotated
-        pub sdf: Box<&'a dyn super::super::SignedDistanceField>,
+        pub sdf: T,
     }
-    impl super::super::SignedDistanceField for Rotate<'_> {
-        fn call(&self, p: Vector<f64>) -> f64 {
-            self.sdf.call(p.rotate(self.alpha, self.axis))
+    impl<S: Scalar, T: SignedDistanceField3<S>> SignedDistanceField3<S> for Rotate3<T> {
+        fn call(&self, p: Vec3<S>) -> S {
+            self.sdf.call(p.rotate(-self.alpha, self.axis))
         }
     }
 
     /// Scale a SDF
-    pub struct Scale<'a> {
+    ///
+    /// The shape grows by the components of `scale` and the distances are scaled along with it, so
+    /// uniform scales keep an exact SDF. For non-uniform scales the distance is multiplied by the
+    /// smallest component, which gives a conservative bound instead of the exact distance.
+    ///
+    /// # Panics
+    ///
+    /// Evaluating the SDF panics, if a component of `scale` is zero.
+    #[derive(Clone, Debug)]
+    pub struct Scale<T, V = Vec2> {
         /// The SDF will be scaled by the components of scale
-        pub scale: Vector<f64>,
+        pub scale: V,
         /// The SDF, that will be scaled
-        pub sdf: Box<&'a dyn super::super::SignedDistanceField>,
-    }
-    impl super::super::SignedDistanceField for Scale<'_> {
-        fn call(&self, p: Vector<f64>) -> f64 {
-            self.sdf.call(Vector {
-                x: p.x * self.scale.x,
-                y: p.y * self.scale.y,
-                z: 0.0,
-            })
+        pub sdf: T,
+    }
+    impl<T> Scale<T> {
+        /// The smallest absolute component of the scale, which the distances are multiplied with
+        fn distance_scale(&self) -> f64 {
+            let factor = self.scale.abs().min_element();
+            assert!(factor > 0.0, "the components of a scale can't be zero");
+
+            factor
+        }
+    }
+    impl<T> Scale<T, Vec3> {
+        /// The smallest absolute component of the scale, which the distances are multiplied with
+        fn distance_scale(&self) -> f64 {
+            let factor = self.scale.abs().min_element();
+            assert!(factor > 0.0, "the components of a scale can't be zero");
+
+            factor
+        }
+    }
+    impl<S: Scalar, T: SignedDistanceField<S>> SignedDistanceField<S> for Scale<T> {
+        fn call(&self, p: Vec2<S>) -> S {
+            let distance = self.sdf.call(p.div_components(self.scale.cast()));
+
+            distance * self.distance_scale()
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            let gradient = self.sdf.gradient(p.div_components(self.scale.cast()));
+
+            gradient.div_components(self.scale.cast()) * S::from_f64(self.distance_scale())
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let (distance, gradient) = self
+                .sdf
+                .call_with_gradient(p.div_components(self.scale.cast()));
+            let factor = self.distance_scale();
+
+            (
+                distance * factor,
+                gradient.div_components(self.scale.cast()) * S::from_f64(factor),
+            )
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            let region = Vec2::new(region.x / self.scale.x, region.y / self.scale.y);
+
+            self.sdf.eval_interval(region) * self.distance_scale()
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            let scale = self.scale.cast();
+            let factor = self.distance_scale();
+
+            call_batch_mapped(&self.sdf, points, out, |p| p.div_components(scale));
+            for distance in out {
+                *distance = *distance * factor;
+            }
+        }
+    }
+    impl<S: Scalar, T: SignedDistanceField3<S>> SignedDistanceField3<S> for Scale<T, Vec3> {
+        fn call(&self, p: Vec3<S>) -> S {
+            let distance = self.sdf.call(p.div_components(self.scale.cast()));
+
+            distance * self.distance_scale()
         }
     }
 
     /// Multiplie a SDF
-    pub struct Matrix<'a> {
+    ///
+    /// The shape is transformed by `matrix`, hence the SDF is evaluated at the inverse transformed
+    /// point. Like [`Affine2`] the distance is corrected by the smallest singular value of the
+    /// matrix, which is exact for rotations and uniform scales and a conservative bound otherwise.
+    /// The inverse and the correction are computed once when it is created.
+    #[derive(Clone, Debug)]
+    pub struct Matrix<T> {
+        transformed: Transformed<T>,
+    }
+    impl<T> Matrix<T> {
+        /// Multiply `sdf` with `matrix`
+        ///
+        /// # Panics
+        ///
+        /// Panics, if the matrix isn't invertible.
+        pub fn new(matrix: [[f64; 2]; 2], sdf: T) -> Self {
+            Matrix {
+                transformed: Transformed::new(Transform2::from_matrix(matrix), sdf),
+            }
+        }
+
         /// The SDF will be multiplied with this matrix
-        pub matrix: [[f64; 2]; 2],
+        pub fn matrix(&self) -> [[f64; 2]; 2] {
+            self.transformed.transform().matrix
+        }
+
         /// The SDF, that will be multiplied
-        pub sdf: Box<&'a dyn super::super::SignedDistanceField>,
-    }
-    impl super::super::SignedDistanceField for Matrix<'_> {
-        fn call(&self, p: Vector<f64>) -> f64 {
-            self.sdf.call(Vector {
-                x: self.matrix[0][0] * p.x + self.matrix[0][1] * p.y,
-                y: self.matrix[1][0] * p.x + self.matrix[1][1] * p.y,
-                z: 0.0,
-            })
+        pub fn sdf(&self) -> &T {
+            self.transformed.sdf()
+        }
+    }
+    impl<S: Scalar, T: SignedDistanceField<S>> SignedDistanceField<S> for Matrix<T> {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.transformed.call(p)
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.transformed.gradient(p)
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            self.transformed.call_with_gradient(p)
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            self.transformed.eval_interval(region)
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            self.transformed.call_batch(points, out)
+        }
+    }
+
+    /// Apply an affine transform (`matrix * p + translation`) to a SDF
+    ///
+    /// The inverse and the distance correction are computed once when the transform is created.
+    /// The distance is multiplied by the smallest singular value of the matrix, which is exact for
+    /// rotations and uniform scales and a conservative bound for shears and non-uniform scales.
+    #[derive(Clone, Debug)]
+    pub struct Affine2<T> {
+        transformed: Transformed<T>,
+    }
+    impl<T> Affine2<T> {
+        /// Create a new affine transform
+        ///
+        /// # Panics
+        ///
+        /// Panics, if the matrix isn't invertible.
+        pub fn new(matrix: [[f64; 2]; 2], translation: Vec2, sdf: T) -> Self {
+            Affine2 {
+                transformed: Transformed::new(
+                    Transform2 {
+                        matrix,
+                        translation,
+                    },
+                    sdf,
+                ),
+            }
+        }
+
+        /// The linear part of the transform
+        pub fn matrix(&self) -> [[f64; 2]; 2] {
+            self.transformed.transform().matrix
+        }
+
+        /// The translation of the transform
+        pub fn translation(&self) -> Vec2 {
+            self.transformed.transform().translation
+        }
+
+        /// The SDF, that will be transformed
+        pub fn sdf(&self) -> &T {
+            self.transformed.sdf()
+        }
+    }
+    impl<S: Scalar, T: SignedDistanceField<S>> SignedDistanceField<S> for Affine2<T> {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.transformed.call(p)
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.transformed.gradient(p)
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            self.transformed.call_with_gradient(p)
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            self.transformed.eval_interval(region)
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            self.transformed.call_batch(points, out)
+        }
+    }
+
+    /// A 2D transform made up of translations, rotations, uniform scales and shears
+    ///
+    /// It is stored as a matrix followed by a translation (`matrix * p + translation`), so any
+    /// number of transforms can be composed into a single one.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub struct Transform2 {
+        /// The linear part of the transform
+        pub matrix: [[f64; 2]; 2],
+        /// The translation, that is applied after the linear part
+        pub translation: Vec2,
+    }
+    impl Default for Transform2 {
+        fn default() -> Self {
+            Self::IDENTITY
+        }
+    }
+    impl Transform2 {
+        /// The transform that doesn't change anything
+        pub const IDENTITY: Transform2 = Transform2 {
+            matrix: [[1.0, 0.0], [0.0, 1.0]],
+            translation: Vec2::ZERO,
+        };
+
+        /// Create a transform from a translation, a rotation (in radians, counter clockwise) and
+        /// a uniform scale, which are applied in the order scale, rotation, translation
+        pub fn new(translation: Vec2, alpha: f64, scale: f64) -> Self {
+            Self::from_translation(translation)
+                .compose(&Self::from_rotation(alpha))
+                .compose(&Self::from_scale(scale))
+        }
+
+        /// Translate by `p`
+        pub fn from_translation(p: Vec2) -> Self {
+            Transform2 {
+                translation: p,
+                ..Self::IDENTITY
+            }
+        }
+
+        /// Rotate counter clockwise by `alpha` (in radians)
+        pub fn from_rotation(alpha: f64) -> Self {
+            let (sin, cos) = alpha.sin_cos();
+            Self::from_matrix([[cos, -sin], [sin, cos]])
+        }
+
+        /// Scale uniformly by `s`
+        pub fn from_scale(s: f64) -> Self {
+            Self::from_matrix([[s, 0.0], [0.0, s]])
+        }
+
+        /// Shear x by `x * p.y` and y by `y * p.x`
+        pub fn from_shear(x: f64, y: f64) -> Self {
+            Self::from_matrix([[1.0, x], [y, 1.0]])
+        }
+
+        /// Multiply by a matrix
+        pub fn from_matrix(matrix: [[f64; 2]; 2]) -> Self {
+            Transform2 {
+                matrix,
+                ..Self::IDENTITY
+            }
+        }
+
+        /// The transform that first applies `other` and then `self`
+        pub fn compose(&self, other: &Transform2) -> Transform2 {
+            let [[a, b], [c, d]] = self.matrix;
+            let [[e, f], [g, h]] = other.matrix;
+
+            Transform2 {
+                matrix: [
+                    [a * e + b * g, a * f + b * h],
+                    [c * e + d * g, c * f + d * h],
+                ],
+                translation: self.apply_point(other.translation),
+            }
+        }
+
+        /// The transform that undoes `self`
+        ///
+        /// # Panics
+        ///
+        /// Panics, if the matrix isn't invertible.
+        pub fn inverse(&self) -> Transform2 {
+            let [[a, b], [c, d]] = self.matrix;
+            let det = a * d - b * c;
+            assert!(
+                det != 0.0 && det.is_finite(),
+                "the matrix of a transform has to be invertible"
+            );
+            let linear = Self::from_matrix([[d / det, -b / det], [-c / det, a / det]]);
+
+            Transform2 {
+                translation: -linear.apply_vector(self.translation),
+                ..linear
+            }
+        }
+
+        /// Transform a point
+        pub fn apply_point<S: Scalar>(&self, p: Vec2<S>) -> Vec2<S> {
+            self.apply_vector(p) + self.translation.cast()
+        }
+
+        /// Transform a direction, which ignores the translation
+        pub fn apply_vector<S: Scalar>(&self, v: Vec2<S>) -> Vec2<S> {
+            Vec2::new(
+                v.x * self.matrix[0][0] + v.y * self.matrix[0][1],
+                v.x * self.matrix[1][0] + v.y * self.matrix[1][1],
+            )
+        }
+
+        /// Bounds of the transformed rectangle `region`
+        pub fn apply_region<S: Scalar>(&self, region: Vec2<Interval<S>>) -> Vec2<Interval<S>> {
+            Vec2::new(
+                region.x * self.matrix[0][0] + region.y * self.matrix[0][1] + self.translation.x,
+                region.x * self.matrix[1][0] + region.y * self.matrix[1][1] + self.translation.y,
+            )
+        }
+
+        /// Multiply a vector with the transposed matrix
+        ///
+        /// A function evaluated at `self.apply_point(p)` has its gradient (with respect to `p`)
+        /// turned by this, which is how gradients pass through transforms.
+        pub fn apply_transposed<S: Scalar>(&self, v: Vec2<S>) -> Vec2<S> {
+            Vec2::new(
+                v.x * self.matrix[0][0] + v.y * self.matrix[1][0],
+                v.x * self.matrix[0][1] + v.y * self.matrix[1][1],
+            )
+        }
+
+        /// The smallest factor by which the transform stretches distances (its smallest singular
+        /// value), which is the factor a distance has to be multiplied with to stay a lower bound
+        pub fn distance_scale(&self) -> f64 {
+            let [[a, b], [c, d]] = self.matrix;
+            let det = a * d - b * c;
+            let sum = a * a + b * b + c * c + d * d;
+
+            (0.5 * (sum - (sum * sum - 4.0 * det * det).max(0.0).sqrt()))
+                .max(0.0)
+                .sqrt()
+        }
+    }
+    impl std::ops::Mul for Transform2 {
+        type Output = Transform2;
+
+        fn mul(self, rhs: Transform2) -> Transform2 {
+            self.compose(&rhs)
+        }
+    }
+
+    /// Apply a [`Transform2`] to a SDF
+    ///
+    /// Transforming it again composes the transforms instead of wrapping the SDF a second time, so
+    /// a whole chain of transforms costs a single point transform per call.
+    #[derive(Clone, Debug)]
+    pub struct Transformed<T> {
+        transform: Transform2,
+        inverse: Transform2,
+        distance_scale: f64,
+        sdf: T,
+    }
+    impl<T> Transformed<T> {
+        /// Apply `transform` to `sdf`
+        ///
+        /// # Panics
+        ///
+        /// Panics, if the matrix of `transform` isn't invertible.
+        pub fn new(transform: Transform2, sdf: T) -> Self {
+            Transformed {
+                transform,
+                inverse: transform.inverse(),
+                distance_scale: transform.distance_scale(),
+                sdf,
+            }
+        }
+
+        /// The transform that is applied to the SDF
+        pub fn transform(&self) -> Transform2 {
+            self.transform
+        }
+
+        /// The SDF, that will be transformed
+        pub fn sdf(&self) -> &T {
+            &self.sdf
+        }
+
+        /// Apply `transform` after the current transform
+        pub fn then(self, transform: Transform2) -> Self {
+            Self::new(transform.compose(&self.transform), self.sdf)
+        }
+
+        /// Translate by `p` after the current transform
+        pub fn translate(self, p: Vec2) -> Self {
+            self.then(Transform2::from_translation(p))
+        }
+
+        /// Rotate counter clockwise by `alpha` (in radians) after the current transform
+        pub fn rotate(self, alpha: f64) -> Self {
+            self.then(Transform2::from_rotation(alpha))
+        }
+
+        /// Scale uniformly by `s` after the current transform
+        pub fn scale(self, s: f64) -> Self {
+            self.then(Transform2::from_scale(s))
+        }
+    }
+    impl<S: Scalar, T: SignedDistanceField<S>> SignedDistanceField<S> for Transformed<T> {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.sdf.call(self.inverse.apply_point(p)) * self.distance_scale
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            let gradient = self.sdf.gradient(self.inverse.apply_point(p));
+
+            self.inverse.apply_transposed(gradient) * S::from_f64(self.distance_scale)
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let (distance, gradient) = self.sdf.call_with_gradient(self.inverse.apply_point(p));
+
+            (
+                distance * self.distance_scale,
+                self.inverse.apply_transposed(gradient) * S::from_f64(self.distance_scale),
+            )
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            self.sdf.eval_interval(self.inverse.apply_region(region)) * self.distance_scale
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            call_batch_mapped(&self.sdf, points, out, |p| self.inverse.apply_point(p));
+            for distance in out {
+                *distance = *distance * self.distance_scale;
+            }
+        }
+    }
+
+    impl<T> From<Translate<T>> for Transformed<T> {
+        fn from(translate: Translate<T>) -> Self {
+            Transformed::new(Transform2::from_translation(translate.p), translate.sdf)
+        }
+    }
+    impl<T> From<Rotate<T>> for Transformed<T> {
+        fn from(rotate: Rotate<T>) -> Self {
+            Transformed::new(Transform2::from_rotation(-rotate.alpha), rotate.sdf)
+        }
+    }
+    impl<T> From<Scale<T>> for Transformed<T> {
+        fn from(scale: Scale<T>) -> Self {
+            let matrix = [[scale.scale.x, 0.0], [0.0, scale.scale.y]];
+            Transformed::new(Transform2::from_matrix(matrix), scale.sdf)
+        }
+    }
+    impl<T> From<Matrix<T>> for Transformed<T> {
+        fn from(matrix: Matrix<T>) -> Self {
+            matrix.transformed
+        }
+    }
+    impl<T> From<Affine2<T>> for Transformed<T> {
+        fn from(affine: Affine2<T>) -> Self {
+            affine.transformed
         }
     }
 }
 
 /// Use boolean logic operators on SDFs
 pub mod boolean {
+    use crate::{interval::Interval, objects::call_batch_combined, scalar::Scalar, Vec2, Vec3};
+
+    use super::{SignedDistanceField, SignedDistanceField3};
+
     /// Create a union of the SDFs (`a ∪ b`)
-    pub struct Union<'a> {
+    #[derive(Clone, Debug)]
+    pub struct Union<A, B> {
         /// The one part of the union
-        pub a: Box<&'a dyn super::super::SignedDistanceField>,
+        pub a: A,
         /// The other part of the union
-        pub b: Box<&'a dyn super::super::SignedDistanceField>,
+        pub b: B,
     }
-    impl super::super::SignedDistanceField for Union<'_> {
-        fn call(&self, p: math_vector::Vector<f64>) -> f64 {
+    impl<S: Scalar, A: SignedDistanceField<S>, B: SignedDistanceField<S>> SignedDistanceField<S>
+        for Union<A, B>
+    {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.a.call(p).min(self.b.call(p))
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.call_with_gradient(p).1
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let a = self.a.call_with_gradient(p);
+            let b = self.b.call_with_gradient(p);
+
+            if a.0 <= b.0 {
+                a
+            } else {
+                b
+            }
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            self.a
+                .eval_interval(region)
+                .min(self.b.eval_interval(region))
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            call_batch_combined(&self.a, &self.b, points, out, |a, b| a.min(b))
+        }
+    }
+    impl<S: Scalar, A: SignedDistanceField3<S>, B: SignedDistanceField3<S>> SignedDistanceField3<S>
+        for Union<A, B>
+    {
+        fn call(&self, p: Vec3<S>) -> S {
             self.a.call(p).min(self.b.call(p))
         }
     }
 
     /// Create a intersection of the SDFs (`a ∩ b`)
-    pub struct Intersection<'a> {
+    #[derive(Clone, Debug)]
+    pub struct Intersection<A, B> {
         /// The one part of the intersection
-        pub a: Box<&'a dyn super::super::SignedDistanceField>,
+        pub a: A,
         /// The other part of the intersection
-        pub b: Box<&'a dyn super::super::SignedDistanceField>,
+        pub b: B,
     }
-    impl super::super::SignedDistanceField for Intersection<'_> {
-        fn call(&self, p: math_vector::Vector<f64>) -> f64 {
+    impl<S: Scalar, A: SignedDistanceField<S>, B: SignedDistanceField<S>> SignedDistanceField<S>
+        for Intersection<A, B>
+    {
+        fn call(&self, p: Vec2<S>) -> S {
             self.a.call(p).max(self.b.call(p))
         }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.call_with_gradient(p).1
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let a = self.a.call_with_gradient(p);
+            let b = self.b.call_with_gradient(p);
+
+            if a.0 >= b.0 {
+                a
+            } else {
+                b
+            }
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            self.a
+                .eval_interval(region)
+                .max(self.b.eval_interval(region))
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            call_batch_combined(&self.a, &self.b, points, out, |a, b| a.max(b))
+        }
+    }
+    impl<S: Scalar, A: SignedDistanceField3<S>, B: SignedDistanceField3<S>> SignedDistanceField3<S>
+        for Intersection<A, B>
+    {
+        fn call(&self, p: Vec3<S>) -> S {
+            self.a.call(p).max(self.b.call(p))
+        }
+    }
+
+    /// Take one SDF away from another (`a\b`), so everything that is inside of `a` but not inside
+    /// of `b`
+    #[derive(Clone, Debug)]
+    pub struct Difference<A, B> {
+        /// The SDF to take away from
+        pub a: A,
+        /// The SDF that will be taken away
+        pub b: B,
+    }
+    impl<S: Scalar, A: SignedDistanceField<S>, B: SignedDistanceField<S>> SignedDistanceField<S>
+        for Difference<A, B>
+    {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.a.call(p).max(-self.b.call(p))
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.call_with_gradient(p).1
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let a = self.a.call_with_gradient(p);
+            let (b, b_gradient) = self.b.call_with_gradient(p);
+
+            if a.0 >= -b {
+                a
+            } else {
+                (-b, -b_gradient)
+            }
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            self.a
+                .eval_interval(region)
+                .max(-self.b.eval_interval(region))
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            call_batch_combined(&self.a, &self.b, points, out, |a, b| a.max(-b))
+        }
+    }
+    impl<S: Scalar, A: SignedDistanceField3<S>, B: SignedDistanceField3<S>> SignedDistanceField3<S>
+        for Difference<A, B>
+    {
+        fn call(&self, p: Vec3<S>) -> S {
+            self.a.call(p).max(-self.b.call(p))
+        }
+    }
+
+    /// Create the symmetric difference of the SDFs (`a ⊕ b`), so everything that is inside of
+    /// exactly one of them
+    #[derive(Clone, Debug)]
+    pub struct Xor<A, B> {
+        /// The one part of the symmetric difference
+        pub a: A,
+        /// The other part of the symmetric difference
+        pub b: B,
+    }
+    impl<S: Scalar, A: SignedDistanceField<S>, B: SignedDistanceField<S>> SignedDistanceField<S>
+        for Xor<A, B>
+    {
+        fn call(&self, p: Vec2<S>) -> S {
+            let a = self.a.call(p);
+            let b = self.b.call(p);
+
+            a.min(b).max(-a.max(b))
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.call_with_gradient(p).1
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let a = self.a.call_with_gradient(p);
+            let b = self.b.call_with_gradient(p);
+            let (min, max) = if a.0 <= b.0 { (a, b) } else { (b, a) };
+
+            if min.0 >= -max.0 {
+                min
+            } else {
+                (-max.0, -max.1)
+            }
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            let a = self.a.eval_interval(region);
+            let b = self.b.eval_interval(region);
+
+            a.min(b).max(-a.max(b))
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            call_batch_combined(&self.a, &self.b, points, out, |a, b| {
+                a.min(b).max(-a.max(b))
+            })
+        }
     }
+    impl<S: Scalar, A: SignedDistanceField3<S>, B: SignedDistanceField3<S>> SignedDistanceField3<S>
+        for Xor<A, B>
+    {
+        fn call(&self, p: Vec3<S>) -> S {
+            let a = self.a.call(p);
+            let b = self.b.call(p);
 
-    /// Create the negative of a SDFs (`a\b`)
-    pub struct Difference<'a> {
+            a.min(b).max(-a.max(b))
+        }
+    }
+
+    /// Create the complement of a SDF (`¬a`), which swaps inside and outside
+    #[derive(Clone, Debug)]
+    pub struct Complement<T> {
+        /// The SDF, that will be negated
+        pub sdf: T,
+    }
+    impl<S: Scalar, T: SignedDistanceField<S>> SignedDistanceField<S> for Complement<T> {
+        fn call(&self, p: Vec2<S>) -> S {
+            -self.sdf.call(p)
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            -self.sdf.gradient(p)
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let (distance, gradient) = self.sdf.call_with_gradient(p);
+
+            (-distance, -gradient)
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            -self.sdf.eval_interval(region)
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            self.sdf.call_batch(points, out);
+            for distance in out {
+                *distance = -*distance;
+            }
+        }
+    }
+    impl<S: Scalar, T: SignedDistanceField3<S>> SignedDistanceField3<S> for Complement<T> {
+        fn call(&self, p: Vec3<S>) -> S {
+            -self.sdf.call(p)
+        }
+    }
+
+    /// The blending function used by the smooth boolean operators
+    ///
+    /// The polynomial variants only differ from the hard `min`/`max` within the blend radius `k`,
+    /// while the exponential and root variants differ everywhere and only approach it far from
+    /// the blend. No variant reports a distance larger than the hard operator would, so the
+    /// result stays usable as a distance bound.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum Blend {
+        /// Quadratic polynomial blend, `min(a, b) - h²k/4`
+        Polynomial,
+        /// Cubic polynomial blend, `min(a, b) - h³k/6`, which is C2 continuous
+        Cubic,
+        /// Exponential blend, `-k log2(2^(-a/k) + 2^(-b/k))`
+        Exponential,
+        /// Root based blend, `(a + b - sqrt((a - b)² + 4k²)) / 2`
+        Root,
+    }
+    impl Blend {
+        /// Smooth minimum of `a` and `b` with a blend radius of `k`
+        pub fn min<S: Scalar>(self, a: S, b: S, k: f64) -> S {
+            if k <= 0.0 {
+                return a.min(b);
+            }
+
+            match self {
+                Blend::Polynomial => {
+                    let h = (S::from_f64(k) - (a - b).abs()).max(S::ZERO) / k;
+                    a.min(b) - h * h * k * 0.25
+                }
+                Blend::Cubic => {
+                    let h = (S::from_f64(k) - (a - b).abs()).max(S::ZERO) / k;
+                    a.min(b) - h * h * h * k / 6.0
+                }
+                Blend::Exponential => {
+                    let m = a.min(b);
+                    m - ((-(a - m) / k).exp2() + (-(b - m) / k).exp2()).log2() * k
+                }
+                Blend::Root => {
+                    let x = a - b;
+                    (a + b - (x * x + 4.0 * k * k).sqrt()) * 0.5
+                }
+            }
+        }
+
+        /// Smooth maximum of `a` and `b` with a blend radius of `k`
+        pub fn max<S: Scalar>(self, a: S, b: S, k: f64) -> S {
+            -self.min(-a, -b, k)
+        }
+
+        /// Bounds of [`Blend::min`] for `a` and `b` within the given intervals
+        ///
+        /// Every blend grows with both of its arguments, so the bounds are the blends of the
+        /// bounds.
+        pub fn min_interval<S: Scalar>(
+            self,
+            a: Interval<S>,
+            b: Interval<S>,
+            k: f64,
+        ) -> Interval<S> {
+            Interval {
+                min: self.min(a.min, b.min, k),
+                max: self.min(a.max, b.max, k),
+            }
+        }
+
+        /// Bounds of [`Blend::max`] for `a` and `b` within the given intervals
+        pub fn max_interval<S: Scalar>(
+            self,
+            a: Interval<S>,
+            b: Interval<S>,
+            k: f64,
+        ) -> Interval<S> {
+            Interval {
+                min: self.max(a.min, b.min, k),
+                max: self.max(a.max, b.max, k),
+            }
+        }
+
+        /// The derivative of [`Blend::min`] with respect to `a`
+        ///
+        /// Every blend only depends on `a - b` apart from an added `min(a, b)`, so the derivative
+        /// with respect to `b` is one minus this and the gradient of the blend is the weighted
+        /// mean `w * ∇a + (1 - w) * ∇b`.
+        pub fn min_weight<S: Scalar>(self, a: S, b: S, k: f64) -> S {
+            let hard = if a <= b { S::ONE } else { S::ZERO };
+            if k <= 0.0 {
+                return hard;
+            }
+
+            // The polynomial weights are 1/2 on the seam and move to the hard weight towards `k`
+            let x = a - b;
+            match self {
+                Blend::Polynomial => {
+                    let h = (S::from_f64(k) - x.abs()).max(S::ZERO) / k;
+                    (S::ONE - x.signum() * (S::ONE - h)) * 0.5
+                }
+                Blend::Cubic => {
+                    let h = (S::from_f64(k) - x.abs()).max(S::ZERO) / k;
+                    (S::ONE - x.signum() * (S::ONE - h * h)) * 0.5
+                }
+                Blend::Exponential => S::ONE / ((x / k).exp2() + 1.0),
+                Blend::Root => (S::ONE - x / (x * x + 4.0 * k * k).sqrt()) * 0.5,
+            }
+        }
+
+        /// The derivative of [`Blend::max`] with respect to `a`, see [`Blend::min_weight`]
+        pub fn max_weight<S: Scalar>(self, a: S, b: S, k: f64) -> S {
+            self.min_weight(-a, -b, k)
+        }
+    }
+
+    /// Create a smooth union of the SDFs (`a ∪ b`) without a crease at the seam
+    #[derive(Clone, Debug)]
+    pub struct SmoothUnion<A, B> {
+        /// The one part of the union
+        pub a: A,
+        /// The other part of the union
+        pub b: B,
+        /// Blend radius
+        pub k: f64,
+        /// The blending function
+        pub blend: Blend,
+    }
+    impl<S: Scalar, A: SignedDistanceField<S>, B: SignedDistanceField<S>> SignedDistanceField<S>
+        for SmoothUnion<A, B>
+    {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.blend.min(self.a.call(p), self.b.call(p), self.k)
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.call_with_gradient(p).1
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let (a, a_gradient) = self.a.call_with_gradient(p);
+            let (b, b_gradient) = self.b.call_with_gradient(p);
+            let w = self.blend.min_weight(a, b, self.k);
+
+            (
+                self.blend.min(a, b, self.k),
+                a_gradient * w + b_gradient * (S::ONE - w),
+            )
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            let a = self.a.eval_interval(region);
+            let b = self.b.eval_interval(region);
+
+            self.blend.min_interval(a, b, self.k)
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            call_batch_combined(&self.a, &self.b, points, out, |a, b| {
+                self.blend.min(a, b, self.k)
+            })
+        }
+    }
+    impl<S: Scalar, A: SignedDistanceField3<S>, B: SignedDistanceField3<S>> SignedDistanceField3<S>
+        for SmoothUnion<A, B>
+    {
+        fn call(&self, p: Vec3<S>) -> S {
+            self.blend.min(self.a.call(p), self.b.call(p), self.k)
+        }
+    }
+
+    /// Create a smooth intersection of the SDFs (`a ∩ b`) without a crease at the seam
+    #[derive(Clone, Debug)]
+    pub struct SmoothIntersection<A, B> {
+        /// The one part of the intersection
+        pub a: A,
+        /// The other part of the intersection
+        pub b: B,
+        /// Blend radius
+        pub k: f64,
+        /// The blending function
+        pub blend: Blend,
+    }
+    impl<S: Scalar, A: SignedDistanceField<S>, B: SignedDistanceField<S>> SignedDistanceField<S>
+        for SmoothIntersection<A, B>
+    {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.blend.max(self.a.call(p), self.b.call(p), self.k)
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.call_with_gradient(p).1
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let (a, a_gradient) = self.a.call_with_gradient(p);
+            let (b, b_gradient) = self.b.call_with_gradient(p);
+            let w = self.blend.max_weight(a, b, self.k);
+
+            (
+                self.blend.max(a, b, self.k),
+                a_gradient * w + b_gradient * (S::ONE - w),
+            )
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            let a = self.a.eval_interval(region);
+            let b = self.b.eval_interval(region);
+
+            self.blend.max_interval(a, b, self.k)
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            call_batch_combined(&self.a, &self.b, points, out, |a, b| {
+                self.blend.max(a, b, self.k)
+            })
+        }
+    }
+    impl<S: Scalar, A: SignedDistanceField3<S>, B: SignedDistanceField3<S>> SignedDistanceField3<S>
+        for SmoothIntersection<A, B>
+    {
+        fn call(&self, p: Vec3<S>) -> S {
+            self.blend.max(self.a.call(p), self.b.call(p), self.k)
+        }
+    }
+
+    /// Smoothly take one SDF away from another (`a\b`) without a crease at the seam
+    #[derive(Clone, Debug)]
+    pub struct SmoothDifference<A, B> {
         /// The SDF to take away from
-        pub a: Box<&'a dyn super::super::SignedDistanceField>,
+        pub a: A,
         /// The SDF that will be taken away
-        pub b: Box<&'a dyn super::super::SignedDistanceField>,
+        pub b: B,
+        /// Blend radius
+        pub k: f64,
+        /// The blending function
+        pub blend: Blend,
+    }
+    impl<S: Scalar, A: SignedDistanceField<S>, B: SignedDistanceField<S>> SignedDistanceField<S>
+        for SmoothDifference<A, B>
+    {
+        fn call(&self, p: Vec2<S>) -> S {
+            self.blend.max(self.a.call(p), -self.b.call(p), self.k)
+        }
+
+        fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+            self.call_with_gradient(p).1
+        }
+
+        fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+            let (a, a_gradient) = self.a.call_with_gradient(p);
+            let (b, b_gradient) = self.b.call_with_gradient(p);
+            let w = self.blend.max_weight(a, -b, self.k);
+
+            (
+                self.blend.max(a, -b, self.k),
+                a_gradient * w - b_gradient * (S::ONE - w),
+            )
+        }
+
+        fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+            let a = self.a.eval_interval(region);
+            let b = self.b.eval_interval(region);
+
+            self.blend.max_interval(a, -b, self.k)
+        }
+
+        fn call_batch(&self, points: &[Vec2<S>], out: &mut [S]) {
+            call_batch_combined(&self.a, &self.b, points, out, |a, b| {
+                self.blend.max(a, -b, self.k)
+            })
+        }
     }
-    impl super::super::SignedDistanceField for Difference<'_> {
-        fn call(&self, p: math_vector::Vector<f64>) -> f64 {
-           self.a.call(p).min(-self.b.call(p)) 
+    impl<S: Scalar, A: SignedDistanceField3<S>, B: SignedDistanceField3<S>> SignedDistanceField3<S>
+        for SmoothDifference<A, B>
+    {
+        fn call(&self, p: Vec3<S>) -> S {
+            self.blend.max(self.a.call(p), -self.b.call(p), self.k)
         }
     }
 }
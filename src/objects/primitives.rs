@@ -1,35 +1,52 @@
-use math_vector::Vector;
+use super::{bvh::SegmentBvh, curve::Curve};
+use crate::{interval::Interval, scalar::Scalar, Vec2};
 
 /// SDF of a rectangle
 ///
 /// ![A SDF render of a rectangle with a width of 14.0 and a height of 5.0](https://github.com/MAA28/Signed-Distance-Fields/blob/main/images/signed_distance_field-objects-primitives-Rectangle%20%7B%20w-14.0,%20h-5.0%20%7D.png?raw=true)
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Rectangle {
     /// Width
     pub w: f64,
     /// Height
     pub h: f64,
 }
-impl super::SignedDistanceField for Rectangle {
-    fn call(&self, p: Vector<f64>) -> f64 {
-        let half_size = Vector {
-            x: 0.5 * self.w,
-            y: 0.5 * self.h,
-            z: 0.0,
-        };
-        let component_wise_edge_distance = Vector {
-            x: p.x.abs() - half_size.x,
-            y: p.y.abs() - half_size.y,
-            z: 0.0,
+impl<S: Scalar> super::SignedDistanceField<S> for Rectangle {
+    fn call(&self, p: Vec2<S>) -> S {
+        let half_size = Vec2::new(0.5 * self.w, 0.5 * self.h).cast();
+        let component_wise_edge_distance = p.abs() - half_size;
+        let outside_distance = component_wise_edge_distance
+            .max(Vec2::splat(S::ZERO))
+            .length();
+        let inside_distance = component_wise_edge_distance.max_element().min(S::ZERO);
+
+        outside_distance + inside_distance
+    }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        let half_size = Vec2::new(0.5 * self.w, 0.5 * self.h).cast();
+        let component_wise_edge_distance = p.abs() - half_size;
+        let sign = Vec2::new(p.x.signum(), p.y.signum());
+
+        let direction = if component_wise_edge_distance.max_element() > 0.0 {
+            component_wise_edge_distance
+                .max(Vec2::splat(S::ZERO))
+                .normalize()
+        } else if component_wise_edge_distance.x > component_wise_edge_distance.y {
+            Vec2::new(S::ONE, S::ZERO)
+        } else {
+            Vec2::new(S::ZERO, S::ONE)
         };
-        let outside_distance = Vector {
-            x: component_wise_edge_distance.x.max(0.0),
-            y: component_wise_edge_distance.y.max(0.0),
-            z: 0.0,
-        }
-        .length();
-        let inside_distance = component_wise_edge_distance
-            .x
-            .max(component_wise_edge_distance.y);
+
+        direction.mul_components(sign)
+    }
+
+    fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+        let x = region.x.abs() - 0.5 * self.w;
+        let y = region.y.abs() - 0.5 * self.h;
+        let zero = Interval::point(S::ZERO);
+        let outside_distance = Vec2::new(x.max(zero), y.max(zero)).length();
+        let inside_distance = x.max(y).min(zero);
 
         outside_distance + inside_distance
     }
@@ -38,63 +55,489 @@ impl super::SignedDistanceField for Rectangle {
 /// SDF for a circle
 ///
 /// ![A SDF render of a circle with a radius of 7.5](https://github.com/MAA28/Signed-Distance-Fields/blob/main/images/signed_distance_field-objects-primitives-Circle%20%7B%20r-7.5%20%7D.png?raw=true")
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Circle {
     /// Radius
     pub r: f64,
 }
-impl super::SignedDistanceField for Circle {
-    fn call(&self, p: Vector<f64>) -> f64 {
+impl<S: Scalar> super::SignedDistanceField<S> for Circle {
+    fn call(&self, p: Vec2<S>) -> S {
         p.length() - self.r
     }
+
+    /// The gradient isn't defined at the center, where it is zero
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        let zero = Vec2::splat(S::ZERO);
+        if p == zero {
+            zero
+        } else {
+            p.normalize()
+        }
+    }
+
+    fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+        region.length() - self.r
+    }
 }
 
 /// SDF for a infinite line
 ///
 /// ![A SDF render of a infinite line](https://github.com/MAA28/Signed-Distance-Fields/blob/main/images/signed_distance_field-objects-primitives-Straight%20%7B%7D.png?raw=true)
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Straight {}
-impl super::SignedDistanceField for Straight {
-    fn call(&self, p: Vector<f64>) -> f64 {
+impl<S: Scalar> super::SignedDistanceField<S> for Straight {
+    fn call(&self, p: Vec2<S>) -> S {
         p.y.abs()
     }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        Vec2::new(S::ZERO, p.y.signum())
+    }
+
+    fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+        region.y.abs()
+    }
 }
 
 /// SDF for a line
 ///
 /// ![A SDF render of a line with a length of 7.5](https://github.com/MAA28/Signed-Distance-Fields/blob/main/images/signed_distance_field-objects-primitives-Line%20%7B%20l-20%7D.png?raw=true)
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Line {
     /// Length of the line
     pub l: f64,
 }
-impl super::SignedDistanceField for Line {
-    fn call(&self, p: Vector<f64>) -> f64 {
-        if -0.5 * self.l > p.x {
-            (Vector {
-                x: -0.5 * self.l,
-                y: 0.0,
-                z: 0.0,
-            } - p)
-                .length()
+impl<S: Scalar> super::SignedDistanceField<S> for Line {
+    fn call(&self, p: Vec2<S>) -> S {
+        if p.x < -0.5 * self.l {
+            (Vec2::new(-0.5 * self.l, 0.0).cast() - p).length()
         } else if p.x > 0.5 * self.l {
-            (Vector {
-                x: 0.5 * self.l,
-                y: 0.0,
-                z: 0.0,
-            } - p)
-                .length()
+            (Vec2::new(0.5 * self.l, 0.0).cast() - p).length()
         } else {
             p.y.abs()
         }
     }
-}
 
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        let end = Vec2::new(0.5 * self.l, 0.0);
+
+        if p.x < -end.x {
+            (p + end.cast()).normalize()
+        } else if p.x > end.x {
+            (p - end.cast()).normalize()
+        } else {
+            Vec2::new(S::ZERO, p.y.signum())
+        }
+    }
+
+    /// The distance to the segment is the length of `(max(|x| - l/2, 0), y)`
+    fn eval_interval(&self, region: Vec2<Interval<S>>) -> Interval<S> {
+        let x = (region.x.abs() - 0.5 * self.l).max(Interval::point(S::ZERO));
+
+        Vec2::new(x, region.y).length()
+    }
+}
 
 /// SDF for the whole plane
 ///
 /// ![A SDF render of a line with a length of 7.5](https://github.com/MAA28/Signed-Distance-Fields/blob/main/images/signed_distance_field-objects-primitives-Plane%20%7B%7D.png?raw=true)
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Plane {}
-impl super::SignedDistanceField for Plane {
-    fn call(&self, _p: Vector<f64>) -> f64 {
-      0.0 
+impl<S: Scalar> super::SignedDistanceField<S> for Plane {
+    fn call(&self, _p: Vec2<S>) -> S {
+        S::ZERO
+    }
+
+    fn gradient(&self, _p: Vec2<S>) -> Vec2<S> {
+        Vec2::splat(S::ZERO)
+    }
+
+    fn eval_interval(&self, _region: Vec2<Interval<S>>) -> Interval<S> {
+        Interval::point(S::ZERO)
+    }
+}
+
+/// SDF of a polygon, given by its vertices
+///
+/// The outline closes from the last vertex back to the first one. The distance is exact and the
+/// sign follows the non-zero winding rule, so concave, self-touching and self-intersecting outlines
+/// work in either orientation. The edges are kept in a bounding volume hierarchy, so evaluating
+/// the SDF only looks at the edges near the point instead of all of them.
+#[derive(Clone, Debug)]
+pub struct Polygon {
+    vertices: Vec<Vec2>,
+    bvh: SegmentBvh,
+}
+impl Polygon {
+    /// The polygon with the outline through `vertices`
+    pub fn new(vertices: Vec<Vec2>) -> Self {
+        let bvh = SegmentBvh::from_outlines([vertices.as_slice()]);
+
+        Polygon { vertices, bvh }
+    }
+
+    /// The vertices of the outline
+    pub fn vertices(&self) -> &[Vec2] {
+        &self.vertices
+    }
+}
+impl PartialEq for Polygon {
+    fn eq(&self, other: &Self) -> bool {
+        self.vertices == other.vertices
+    }
+}
+impl<S: Scalar> super::SignedDistanceField<S> for Polygon {
+    fn call(&self, p: Vec2<S>) -> S {
+        outline_call_with_gradient(&self.bvh, p, |winding| winding != 0).0
+    }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        outline_call_with_gradient(&self.bvh, p, |winding| winding != 0).1
+    }
+
+    fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+        outline_call_with_gradient(&self.bvh, p, |winding| winding != 0)
+    }
+}
+
+/// One polygon of a [`MultiPolygon`], whose holes are cut out of it
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PolygonWithHoles {
+    /// The vertices of the outer outline
+    pub exterior: Vec<Vec2>,
+    /// The vertices of the outlines of the holes, which lie within the exterior
+    pub holes: Vec<Vec<Vec2>>,
+}
+
+/// SDF of several polygons with holes, like the shapes of CAD exports
+///
+/// The shape is the union of all polygons, each without its holes. The outlines can have any
+/// orientation, the exteriors are turned counter clockwise and the holes clockwise. Every polygon
+/// has its own bounding volume hierarchy and the distance is the minimum of theirs, just like a
+/// [`Union`](super::operators::boolean::Union). So it is exact outside of the shape, but inside
+/// where polygons overlap it can be smaller than the distance to the outline of the union.
+#[derive(Clone, Debug)]
+pub struct MultiPolygon {
+    polygons: Vec<PolygonWithHoles>,
+    bvhs: Vec<SegmentBvh>,
+}
+impl MultiPolygon {
+    /// The union of `polygons`
+    pub fn new(polygons: Vec<PolygonWithHoles>) -> Self {
+        let oriented = |outline: &[Vec2], counter_clockwise: bool| -> Vec<Vec2> {
+            let mut outline = outline.to_vec();
+            if (signed_area(&outline) > 0.0) != counter_clockwise {
+                outline.reverse();
+            }
+            outline
+        };
+        let bvhs = polygons
+            .iter()
+            .map(|polygon| {
+                let holes = polygon.holes.iter().map(|hole| oriented(hole, false));
+                let outlines: Vec<Vec<Vec2>> = std::iter::once(oriented(&polygon.exterior, true))
+                    .chain(holes)
+                    .collect();
+                SegmentBvh::from_outlines(outlines.iter().map(Vec::as_slice))
+            })
+            .collect();
+
+        MultiPolygon { polygons, bvhs }
+    }
+
+    /// The polygons, as they were given
+    pub fn polygons(&self) -> &[PolygonWithHoles] {
+        &self.polygons
+    }
+}
+impl PartialEq for MultiPolygon {
+    fn eq(&self, other: &Self) -> bool {
+        self.polygons == other.polygons
+    }
+}
+impl<S: Scalar> super::SignedDistanceField<S> for MultiPolygon {
+    fn call(&self, p: Vec2<S>) -> S {
+        self.call_with_gradient(p).0
+    }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        self.call_with_gradient(p).1
+    }
+
+    fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+        let mut closest = (S::from_f64(f64::INFINITY), Vec2::splat(S::ZERO));
+        for bvh in &self.bvhs {
+            let next = outline_call_with_gradient(bvh, p, |winding| winding > 0);
+            if next.0 < closest.0 {
+                closest = next;
+            }
+        }
+
+        closest
+    }
+}
+
+/// SDF of a quadratic Bézier curve from `p0` to `p2`, which is pulled towards `p1`
+///
+/// Like for a [`Line`] the distance is exact and never negative.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QuadraticBezier {
+    /// Start of the curve
+    pub p0: Vec2,
+    /// Control point
+    pub p1: Vec2,
+    /// End of the curve
+    pub p2: Vec2,
+}
+impl QuadraticBezier {
+    fn curve(&self) -> Curve {
+        Curve::new(&[self.p0, self.p1, self.p2])
+    }
+}
+impl<S: Scalar> super::SignedDistanceField<S> for QuadraticBezier {
+    fn call(&self, p: Vec2<S>) -> S {
+        curves_call_with_gradient(&[self.curve()], p, None).0
+    }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        curves_call_with_gradient(&[self.curve()], p, None).1
+    }
+
+    fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+        curves_call_with_gradient(&[self.curve()], p, None)
     }
 }
 
+/// SDF of a cubic Bézier curve from `p0` to `p3`, which is pulled towards `p1` and `p2`
+///
+/// Like for a [`Line`] the distance is exact and never negative. The closest point is found by
+/// solving the polynomial of degree 5 for it numerically.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CubicBezier {
+    /// Start of the curve
+    pub p0: Vec2,
+    /// Control point next to the start
+    pub p1: Vec2,
+    /// Control point next to the end
+    pub p2: Vec2,
+    /// End of the curve
+    pub p3: Vec2,
+}
+impl CubicBezier {
+    fn curve(&self) -> Curve {
+        Curve::new(&[self.p0, self.p1, self.p2, self.p3])
+    }
+}
+impl<S: Scalar> super::SignedDistanceField<S> for CubicBezier {
+    fn call(&self, p: Vec2<S>) -> S {
+        curves_call_with_gradient(&[self.curve()], p, None).0
+    }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        curves_call_with_gradient(&[self.curve()], p, None).1
+    }
+
+    fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+        curves_call_with_gradient(&[self.curve()], p, None)
+    }
+}
+
+/// One segment of a [`BezierSpline`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BezierSegment {
+    /// A straight segment from the first to the second point
+    Linear([Vec2; 2]),
+    /// A quadratic Bézier curve
+    Quadratic(QuadraticBezier),
+    /// A cubic Bézier curve
+    Cubic(CubicBezier),
+}
+impl BezierSegment {
+    /// The first point of the segment
+    pub fn start(&self) -> Vec2 {
+        match self {
+            BezierSegment::Linear([p0, _]) => *p0,
+            BezierSegment::Quadratic(curve) => curve.p0,
+            BezierSegment::Cubic(curve) => curve.p0,
+        }
+    }
+
+    /// The last point of the segment
+    pub fn end(&self) -> Vec2 {
+        match self {
+            BezierSegment::Linear([_, p1]) => *p1,
+            BezierSegment::Quadratic(curve) => curve.p2,
+            BezierSegment::Cubic(curve) => curve.p3,
+        }
+    }
+
+    fn curve(&self) -> Curve {
+        match self {
+            BezierSegment::Linear(points) => Curve::new(points),
+            BezierSegment::Quadratic(curve) => curve.curve(),
+            BezierSegment::Cubic(curve) => curve.curve(),
+        }
+    }
+}
+
+/// How overlapping parts of a filled path are combined
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FillRule {
+    /// A point is filled, if a ray from it crosses the outline an odd number of times
+    EvenOdd,
+    /// A point is filled, if the outline winds around it. Outlines are oriented so that holes wind
+    /// the other way around, so holes stay empty.
+    NonZero,
+}
+
+/// SDF of closed outlines made of Bézier curves and straight segments, like the glyphs of fonts
+///
+/// Every outline has to be closed, so each one ends where it started. The segments of all
+/// outlines can come in any order. The distance is exact and the sign follows the fill rule, which
+/// is the non-zero winding rule like for a [`Polygon`] unless it is given.
+#[derive(Clone, Debug)]
+pub struct BezierSpline {
+    segments: Vec<BezierSegment>,
+    fill_rule: FillRule,
+    curves: Vec<Curve>,
+}
+impl BezierSpline {
+    /// The spline made of `segments`, the outlines of holes have to run the other way around
+    pub fn new(segments: Vec<BezierSegment>) -> Self {
+        Self::with_fill_rule(segments, FillRule::NonZero)
+    }
+
+    /// The spline made of `segments`, whose inside is decided by `fill_rule`
+    pub fn with_fill_rule(segments: Vec<BezierSegment>, fill_rule: FillRule) -> Self {
+        let curves = segments.iter().map(BezierSegment::curve).collect();
+
+        BezierSpline {
+            segments,
+            fill_rule,
+            curves,
+        }
+    }
+
+    /// The segments, as they were given
+    pub fn segments(&self) -> &[BezierSegment] {
+        &self.segments
+    }
+
+    /// The rule that decides which points are inside
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+}
+impl PartialEq for BezierSpline {
+    fn eq(&self, other: &Self) -> bool {
+        self.segments == other.segments && self.fill_rule == other.fill_rule
+    }
+}
+impl<S: Scalar> super::SignedDistanceField<S> for BezierSpline {
+    fn call(&self, p: Vec2<S>) -> S {
+        curves_call_with_gradient(&self.curves, p, Some(self.fill_rule)).0
+    }
+
+    fn gradient(&self, p: Vec2<S>) -> Vec2<S> {
+        curves_call_with_gradient(&self.curves, p, Some(self.fill_rule)).1
+    }
+
+    fn call_with_gradient(&self, p: Vec2<S>) -> (S, Vec2<S>) {
+        curves_call_with_gradient(&self.curves, p, Some(self.fill_rule))
+    }
+}
+
+/// Twice the area enclosed by the outline, which is positive for counter clockwise outlines
+fn signed_area(outline: &[Vec2]) -> f64 {
+    let next = outline.iter().cycle().skip(1);
+
+    outline.iter().zip(next).map(|(a, b)| a.cross(*b)).sum()
+}
+
+/// The distance to the closest edge of closed outlines and its gradient, where the winding number
+/// of the outlines around `p` decides with `inside` whether it is negative
+///
+/// Without any edges the distance is infinite.
+fn outline_call_with_gradient<S: Scalar>(
+    bvh: &SegmentBvh,
+    p: Vec2<S>,
+    inside: impl Fn(i32) -> bool,
+) -> (S, Vec2<S>) {
+    // Searching is done on the values, only the distance to the closest edge is computed with `S`
+    let value = Vec2::new(p.x.value(), p.y.value());
+    let Some(closest) = bvh.closest(value) else {
+        return (S::from_f64(f64::INFINITY), Vec2::splat(S::ZERO));
+    };
+    let [a, b] = bvh.segment(closest);
+
+    let (pa, ba) = (p - a.cast(), (b - a).cast());
+    let length_squared = (b - a).length_squared();
+    let h = if length_squared == 0.0 {
+        S::ZERO
+    } else {
+        (pa.dot(ba) / length_squared).clamp(0.0, 1.0)
+    };
+    let offset = pa - ba * h;
+    let distance = offset.length();
+    let direction = if distance == 0.0 {
+        Vec2::splat(S::ZERO)
+    } else {
+        offset / distance
+    };
+
+    if inside(bvh.winding_number(value)) {
+        (-distance, -direction)
+    } else {
+        (distance, direction)
+    }
+}
+
+/// The distance to the closest of the `curves` and its gradient, which is negative inside of the
+/// curves by the `fill_rule`, if there is one
+///
+/// Without any curves the distance is infinite.
+fn curves_call_with_gradient<S: Scalar>(
+    curves: &[Curve],
+    p: Vec2<S>,
+    fill_rule: Option<FillRule>,
+) -> (S, Vec2<S>) {
+    // Like for polygons the closest point is searched for on the values
+    let value = Vec2::new(p.x.value(), p.y.value());
+    let mut closest = None;
+    let mut best_distance = f64::INFINITY;
+    for curve in curves {
+        if curve.box_distance_squared(value) >= best_distance {
+            continue;
+        }
+
+        let (t, distance) = curve.closest(value);
+        if distance < best_distance {
+            closest = Some(curve.point(t));
+            best_distance = distance;
+        }
+    }
+    let Some(closest) = closest else {
+        return (S::from_f64(f64::INFINITY), Vec2::splat(S::ZERO));
+    };
+
+    let offset = p - closest.cast();
+    let distance = offset.length();
+    let direction = if distance == 0.0 {
+        Vec2::splat(S::ZERO)
+    } else {
+        offset / distance
+    };
+
+    let inside = fill_rule.is_some_and(|fill_rule| {
+        let winding_number: i32 = curves.iter().map(|curve| curve.winding_number(value)).sum();
+        match fill_rule {
+            FillRule::NonZero => winding_number != 0,
+            FillRule::EvenOdd => winding_number % 2 != 0,
+        }
+    });
+    if inside {
+        (-distance, -direction)
+    } else {
+        (distance, direction)
+    }
+}
@@ -0,0 +1,192 @@
+use crate::{scalar::Scalar, Vec2, Vec3};
+
+/// SDF of a sphere
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sphere {
+    /// Radius
+    pub r: f64,
+}
+impl<S: Scalar> super::SignedDistanceField3<S> for Sphere {
+    fn call(&self, p: Vec3<S>) -> S {
+        p.length() - self.r
+    }
+}
+
+/// SDF of an axis aligned box
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cuboid {
+    /// Width (along x)
+    pub w: f64,
+    /// Height (along y)
+    pub h: f64,
+    /// Depth (along z)
+    pub d: f64,
+}
+impl<S: Scalar> super::SignedDistanceField3<S> for Cuboid {
+    fn call(&self, p: Vec3<S>) -> S {
+        let half_size = Vec3::new(0.5 * self.w, 0.5 * self.h, 0.5 * self.d);
+        box_distance(p, half_size.cast())
+    }
+}
+
+/// SDF of an axis aligned box with rounded edges
+///
+/// The rounding happens inside of the box, so it keeps its outer dimensions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RoundedCuboid {
+    /// Width (along x)
+    pub w: f64,
+    /// Height (along y)
+    pub h: f64,
+    /// Depth (along z)
+    pub d: f64,
+    /// Radius of the edges
+    pub r: f64,
+}
+impl<S: Scalar> super::SignedDistanceField3<S> for RoundedCuboid {
+    fn call(&self, p: Vec3<S>) -> S {
+        let half_size = Vec3::new(0.5 * self.w, 0.5 * self.h, 0.5 * self.d);
+        box_distance(p, (half_size - Vec3::splat(self.r)).cast()) - self.r
+    }
+}
+
+/// SDF of a torus lying in the xy-plane
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Torus {
+    /// Distance from the center to the middle of the tube
+    pub major: f64,
+    /// Radius of the tube
+    pub minor: f64,
+}
+impl<S: Scalar> super::SignedDistanceField3<S> for Torus {
+    fn call(&self, p: Vec3<S>) -> S {
+        Vec2::new(p.truncate().length() - self.major, p.z).length() - self.minor
+    }
+}
+
+/// SDF of a capsule, which are all points within `r` of the segment from `a` to `b`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Capsule {
+    /// Start of the segment
+    pub a: Vec3,
+    /// End of the segment
+    pub b: Vec3,
+    /// Radius
+    pub r: f64,
+}
+impl<S: Scalar> super::SignedDistanceField3<S> for Capsule {
+    fn call(&self, p: Vec3<S>) -> S {
+        let pa = p - self.a.cast();
+        let ba = (self.b - self.a).cast();
+        // With `a == b` the capsule is a sphere around `a`
+        let length_squared = (self.b - self.a).length_squared();
+        let h = if length_squared == 0.0 {
+            S::ZERO
+        } else {
+            (pa.dot(ba) / length_squared).clamp(0.0, 1.0)
+        };
+
+        (pa - ba * h).length() - self.r
+    }
+}
+
+/// SDF of a capped cylinder around the z axis, centered at the origin
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cylinder {
+    /// Radius
+    pub r: f64,
+    /// Height (along z)
+    pub h: f64,
+}
+impl<S: Scalar> super::SignedDistanceField3<S> for Cylinder {
+    fn call(&self, p: Vec3<S>) -> S {
+        let d = Vec2::new(p.truncate().length() - self.r, p.z.abs() - 0.5 * self.h);
+
+        d.max_element().min(S::ZERO) + d.max(Vec2::splat(S::ZERO)).length()
+    }
+}
+
+/// SDF of a cone around the z axis, with its base at `z = 0` and its tip at `z = h`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Cone {
+    /// Radius of the base
+    pub r: f64,
+    /// Height (along z)
+    pub h: f64,
+}
+impl<S: Scalar> super::SignedDistanceField3<S> for Cone {
+    fn call(&self, p: Vec3<S>) -> S {
+        // The closest point always lies in the same half plane through the axis as `p`, so this
+        // is the distance to the outline of the triangular cross section (without the axis)
+        let q = Vec2::new(p.truncate().length(), p.z);
+        let base = Vec2::new(self.r, 0.0).cast();
+        let tip = Vec2::new(0.0, self.h).cast();
+
+        let distance =
+            segment_distance(q, Vec2::splat(S::ZERO), base).min(segment_distance(q, base, tip));
+        let inside = q.y >= 0.0 && q.x / self.r + q.y / self.h <= 1.0;
+
+        if inside {
+            -distance
+        } else {
+            distance
+        }
+    }
+}
+
+/// SDF of a plane, everything on the opposite side of the normal is inside
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Plane {
+    /// Normal of the plane, it has to have a length of 1
+    pub n: Vec3,
+    /// Offset of the plane from the origin along the normal
+    pub h: f64,
+}
+impl<S: Scalar> super::SignedDistanceField3<S> for Plane {
+    fn call(&self, p: Vec3<S>) -> S {
+        p.dot(self.n.cast()) - self.h
+    }
+}
+
+/// SDF of an axis aligned ellipsoid
+///
+/// There is no closed form for the distance to an ellipsoid, so this is an approximation which is
+/// exact on the surface and along the axes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Ellipsoid {
+    /// Radii along the axes
+    pub r: Vec3,
+}
+impl<S: Scalar> super::SignedDistanceField3<S> for Ellipsoid {
+    fn call(&self, p: Vec3<S>) -> S {
+        let k0 = p.div_components(self.r.cast()).length();
+        let k1 = p
+            .div_components(self.r.mul_components(self.r).cast())
+            .length();
+
+        if k1 == 0.0 {
+            S::from_f64(-self.r.min_element())
+        } else {
+            k0 * (k0 - 1.0) / k1
+        }
+    }
+}
+
+fn box_distance<S: Scalar>(p: Vec3<S>, half_size: Vec3<S>) -> S {
+    let q = p.abs() - half_size;
+
+    q.max(Vec3::splat(S::ZERO)).length() + q.max_element().min(S::ZERO)
+}
+
+fn segment_distance<S: Scalar>(p: Vec2<S>, a: Vec2<S>, b: Vec2<S>) -> S {
+    let pa = p - a;
+    let ba = b - a;
+    let length_squared = ba.length_squared();
+    let h = if length_squared == 0.0 {
+        S::ZERO
+    } else {
+        (pa.dot(ba) / length_squared).clamp(0.0, 1.0)
+    };
+
+    (pa - ba * h).length()
+}
@@ -0,0 +1,172 @@
+use image::{Rgb, RgbImage};
+use itertools_num::linspace;
+
+use crate::{field::SampledField, objects::SignedDistanceField, Domain, Vec2};
+
+/// How the samples of cells that are skipped are filled in
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Fill {
+    /// Bilinear interpolation of the corners of the cell, which keeps the sign and is close to the
+    /// real distance for smooth SDFs
+    #[default]
+    Interpolate,
+    /// The largest lower bound of the distance that follows from the corners of the cell, so the
+    /// distance is never overestimated
+    Conservative,
+}
+
+/// Renders a SDF into a matrix of samples like [`super::matrix`], but only evaluates it near the
+/// surface
+///
+/// The domain is split into a quadtree. A cell whose corners are all further away from the
+/// surface than the length of its diagonal can't contain a part of the surface, so it isn't split
+/// any further and its samples are filled in according to `fill`. Every other cell is split until
+/// all of its samples are evaluated, hence samples near the surface are exactly the ones
+/// [`super::matrix`] returns and all signs are the same.
+///
+/// This relies on the SDF never changing faster than the distance to its shape, which is true for
+/// every exact SDF and distance bound.
+pub fn matrix(sdf: &dyn SignedDistanceField, domain: &Domain, fill: Fill) -> SampledField {
+    let (width, height) = (domain.steps.x, domain.steps.y);
+    if width == 0 || height == 0 {
+        return SampledField::from_values(domain.clone(), Vec::new());
+    }
+
+    let mut quadtree = Quadtree {
+        sdf,
+        fill,
+        xs: linspace(domain.p0.x, domain.p1.x, width).collect(),
+        ys: linspace(domain.p0.y, domain.p1.y, height).collect(),
+        values: SampledField::from_values(domain.clone(), vec![0.0; width * height]),
+        evaluated: vec![0; (width * height).div_ceil(64)],
+    };
+    for (x, y) in [
+        (0, 0),
+        (width - 1, 0),
+        (0, height - 1),
+        (width - 1, height - 1),
+    ] {
+        quadtree.evaluate(x, y);
+    }
+    quadtree.refine(0, width - 1, 0, height - 1);
+
+    quadtree.values
+}
+
+/// Renders a SDF into an image like [`super::image`], using [`matrix`]
+pub fn image(
+    sdf: &dyn SignedDistanceField,
+    domain: &Domain,
+    fill: Fill,
+    color_mapper: &(dyn Fn(&f64) -> Rgb<u8> + Sync),
+) -> RgbImage {
+    super::image_from_field(&matrix(sdf, domain, fill), color_mapper)
+}
+
+struct Quadtree<'a> {
+    sdf: &'a dyn SignedDistanceField,
+    fill: Fill,
+    xs: Vec<f64>,
+    ys: Vec<f64>,
+    values: SampledField,
+    /// One bit per sample, which is set once the SDF is evaluated there
+    evaluated: Vec<u64>,
+}
+
+impl Quadtree<'_> {
+    fn point(&self, x: usize, y: usize) -> Vec2 {
+        Vec2::new(self.xs[x], self.ys[y])
+    }
+
+    fn is_evaluated(&self, x: usize, y: usize) -> bool {
+        let index = y * self.xs.len() + x;
+        self.evaluated[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    fn evaluate(&mut self, x: usize, y: usize) {
+        if !self.is_evaluated(x, y) {
+            let index = y * self.xs.len() + x;
+            self.values[(x, y)] = self.sdf.call(self.point(x, y));
+            self.evaluated[index / 64] |= 1 << (index % 64);
+        }
+    }
+
+    /// Refines the cell spanned by the samples `x0..=x1` and `y0..=y1`, whose corners are already
+    /// evaluated
+    fn refine(&mut self, x0: usize, x1: usize, y0: usize, y1: usize) {
+        if x1 - x0 <= 1 && y1 - y0 <= 1 {
+            return;
+        }
+
+        let corners = [(x0, y0), (x1, y0), (x0, y1), (x1, y1)];
+        let diagonal = self.point(x0, y0).distance(self.point(x1, y1));
+        if corners
+            .iter()
+            .all(|&(x, y)| self.values[(x, y)].abs() > diagonal)
+        {
+            self.fill(x0, x1, y0, y1);
+            return;
+        }
+
+        // Axes that are only one cell long aren't split any further
+        let halves = |a: usize, b: usize| {
+            let middle = (a + b) / 2;
+            if b - a > 1 {
+                ([(a, middle), (middle, b)], 2)
+            } else {
+                ([(a, b), (a, b)], 1)
+            }
+        };
+        let (xs, x_count) = halves(x0, x1);
+        let (ys, y_count) = halves(y0, y1);
+
+        for &(cx0, cx1) in &xs[..x_count] {
+            for &(cy0, cy1) in &ys[..y_count] {
+                for (x, y) in [(cx0, cy0), (cx1, cy0), (cx0, cy1), (cx1, cy1)] {
+                    self.evaluate(x, y);
+                }
+                self.refine(cx0, cx1, cy0, cy1);
+            }
+        }
+    }
+
+    /// Fills the samples of a cell without a part of the surface, that aren't evaluated
+    fn fill(&mut self, x0: usize, x1: usize, y0: usize, y1: usize) {
+        let corners = [(x0, y0), (x1, y0), (x0, y1), (x1, y1)]
+            .map(|(x, y)| (self.point(x, y), self.values[(x, y)]));
+        let size = corners[3].0 - corners[0].0;
+        // Cells of domains that are a single sample wide are flat along that axis
+        let inverse = |size: f64| if size == 0.0 { 0.0 } else { 1.0 / size };
+        let inverse_size = Vec2::new(inverse(size.x), inverse(size.y));
+
+        for y in y0..=y1 {
+            // Interpolating along y first leaves a linear interpolation along the row
+            let ty = (self.ys[y] - corners[0].0.y) * inverse_size.y;
+            let left = corners[0].1 + (corners[2].1 - corners[0].1) * ty;
+            let right = corners[1].1 + (corners[3].1 - corners[1].1) * ty;
+
+            for x in x0..=x1 {
+                if self.is_evaluated(x, y) {
+                    continue;
+                }
+
+                self.values[(x, y)] = match self.fill {
+                    Fill::Interpolate => {
+                        let tx = (self.xs[x] - corners[0].0.x) * inverse_size.x;
+                        left + (right - left) * tx
+                    }
+                    Fill::Conservative => {
+                        let p = self.point(x, y);
+                        let sign = corners[0].1.signum();
+                        let bound = corners
+                            .iter()
+                            .map(|&(corner, value)| value.abs() - corner.distance(p))
+                            .fold(f64::NEG_INFINITY, f64::max);
+
+                        sign * bound
+                    }
+                };
+            }
+        }
+    }
+}
@@ -1,32 +1,76 @@
-use image::{ImageBuffer, Rgb, RgbImage};
+use image::{Rgb, RgbImage};
 use itertools_num::linspace;
-use math_vector::Vector;
 
-use crate::{objects::SignedDistanceField, Domain};
+use crate::{
+    field::SampledField,
+    objects::{SignedDistanceField, SignedDistanceField3},
+    Domain, Domain3, Vec2, Vec3,
+};
+#[cfg(not(feature = "parallel"))]
+use image::ImageBuffer;
+
+/// Renders SDFs adaptively, only evaluating them near the surface
+pub mod adaptive;
+/// Generates multi-channel SDFs of outlines and packs them into atlases, see [`msdf::image`]
+pub mod msdf;
+/// Renders SDFs on multiple threads with rayon, see [`parallel::Settings`]
+#[cfg(feature = "parallel")]
+pub mod parallel;
+/// Renders 3D SDFs into images by sphere tracing, purely on the CPU
+pub mod raymarch;
+/// Writes SDFs as SVG documents, see [`svg()`]
+pub mod svg;
 
 /// Renders a SDF into a images
+///
+/// With the `parallel` feature this renders on the global thread pool of rayon.
 pub fn image(
     sdf: &dyn SignedDistanceField,
     domain: &Domain,
-    color_mapper: &dyn Fn(&f64) -> Rgb<u8>,
+    color_mapper: &(dyn Fn(&f64) -> Rgb<u8> + Sync),
 ) -> RgbImage {
-    let matrix = matrix(sdf, domain);
+    image_from_field(&matrix(sdf, domain), color_mapper)
+}
 
+/// Maps every sample of a field to a pixel
+#[cfg(feature = "parallel")]
+pub fn image_from_field(
+    field: &SampledField,
+    color_mapper: &(dyn Fn(&f64) -> Rgb<u8> + Sync),
+) -> RgbImage {
+    parallel::image_from_field(field, color_mapper, &parallel::Settings::default())
+}
+
+/// Maps every sample of a field to a pixel
+#[cfg(not(feature = "parallel"))]
+pub fn image_from_field(
+    field: &SampledField,
+    color_mapper: &(dyn Fn(&f64) -> Rgb<u8> + Sync),
+) -> RgbImage {
     ImageBuffer::from_fn(
-        domain.steps.x as u32,
-        domain.steps.y as u32,
-        |x: u32, y: u32| -> Rgb<u8> {
-            color_mapper(matrix.get(x as usize).unwrap().get(y as usize).unwrap())
-        },
+        field.width() as u32,
+        field.height() as u32,
+        |x: u32, y: u32| -> Rgb<u8> { color_mapper(&field[(x as usize, y as usize)]) },
     )
 }
 
+/// Renders the outlines and insides of a SDF into an SVG document
+///
+/// Use [`svg::write`] to write the document into any [`std::io::Write`] instead.
+pub fn svg(sdf: &dyn SignedDistanceField, domain: &Domain, settings: &svg::Settings) -> String {
+    let mut buffer = Vec::new();
+    svg::write(&mut buffer, sdf, domain, settings).expect("writing into a Vec can't fail");
+
+    String::from_utf8(buffer).expect("the document is valid UTF-8")
+}
+
 /// Renders a SDF into a text
 /// * `sdf` - The SDF, that is to be rendered
 /// * `domain` - The domain, that is to be rendered _(I recommend a ratio of 1/2 between `domain.step.y / domain.step.x` to aviod streching)_
 /// * `text_mapper` - The mapper, that will interprete the values
+///
 /// Example output:
-/// ```
+/// ```text
 ///                                                                                                     
 ///                                                                                                    
 ///                                                                                                    
@@ -83,43 +127,72 @@ pub fn image(
 pub fn text(
     sdf: &dyn SignedDistanceField,
     domain: &Domain,
-    text_mapper: &dyn Fn(&Vec<Vec<f64>>, &Domain) -> String,
+    text_mapper: &(dyn Fn(&SampledField) -> String + Sync),
 ) -> String {
-    let matrix = matrix(sdf, domain);
-    
-    text_mapper(&matrix, domain)
+    text_mapper(&matrix(sdf, domain))
 }
 
-/// Renders a SDF into a matrix
-pub fn matrix(sdf: &dyn SignedDistanceField, domain: &Domain) -> Vec<Vec<f64>> {
-    let mut matrix = Vec::new();
+/// Renders a SDF into a matrix of samples
+///
+/// Every row is evaluated with a single [`SignedDistanceField::call_batch`]. With the `parallel`
+/// feature this renders on the global thread pool of rayon.
+#[cfg(feature = "parallel")]
+pub fn matrix(sdf: &dyn SignedDistanceField, domain: &Domain) -> SampledField {
+    parallel::matrix(sdf, domain, &parallel::Settings::default())
+}
+
+/// Renders a SDF into a matrix of samples
+///
+/// Every row is evaluated with a single [`SignedDistanceField::call_batch`].
+#[cfg(not(feature = "parallel"))]
+pub fn matrix(sdf: &dyn SignedDistanceField, domain: &Domain) -> SampledField {
+    let xs: Vec<f64> = linspace(domain.p0.x, domain.p1.x, domain.steps.x).collect();
+    let ys = linspace(domain.p0.y, domain.p1.y, domain.steps.y);
+    let mut field = SampledField::from_values(domain.clone(), vec![0.0; xs.len() * ys.len()]);
+
+    if !xs.is_empty() {
+        for (values, y) in field.values_mut().chunks_mut(xs.len()).zip(ys) {
+            row(sdf, &xs, y, values);
+        }
+    }
+
+    field
+}
+
+/// Evaluates a row of samples with a single [`SignedDistanceField::call_batch`]
+fn row(sdf: &dyn SignedDistanceField, xs: &[f64], y: f64, values: &mut [f64]) {
+    let points: Vec<Vec2> = xs.iter().map(|&x| Vec2::new(x, y)).collect();
+    sdf.call_batch(&points, values);
+}
+
+/// Samples a 3D SDF on the grid of a domain, indexed as `volume[x][y][z]`
+pub fn volume(sdf: &dyn SignedDistanceField3, domain: &Domain3) -> Vec<Vec<Vec<f64>>> {
+    let mut volume = Vec::new();
 
     for x in linspace(domain.p0.x, domain.p1.x, domain.steps.x) {
-        matrix.push(Vec::new());
+        volume.push(Vec::new());
         for y in linspace(domain.p0.y, domain.p1.y, domain.steps.y) {
-            let p = Vector { x, y, z: 0.0 };
-            let value = sdf.call(p);
-            matrix.last_mut().unwrap().push(value);
+            let column = linspace(domain.p0.z, domain.p1.z, domain.steps.z)
+                .map(|z| sdf.call(Vec3::new(x, y, z)))
+                .collect();
+            volume.last_mut().unwrap().push(column);
         }
     }
 
-    matrix
+    volume
 }
 
 /// Change the style of the text render
 pub mod text_mappers {
-    use crate::Domain;
-
+    use crate::field::SampledField;
 
     /// Drawes the edge ('*') and show which parts are inside ('-') and which are outside ('-')
-    pub fn default(matrix: &Vec<Vec<f64>>, domain: &Domain) -> String {
+    pub fn default(field: &SampledField) -> String {
         let mut s = String::new();
 
-        for j in 0..domain.steps.y {
-            for i in 0..domain.steps.x {
+        for row in field.rows() {
+            for x in row {
                 s.push({
-                    let x = matrix.get(i).unwrap().get(j).unwrap();
-
                     if *x == 0.0 {
                         '*'
                     } else if 0.0 > *x && *x > -0.5 {
@@ -136,24 +209,14 @@ pub mod text_mappers {
 
         s
     }
-    
-    
 
     /// Fills the inside with '#'
-    pub fn fill_inside(matrix: &Vec<Vec<f64>>, domain: &Domain) -> String  {
+    pub fn fill_inside(field: &SampledField) -> String {
         let mut s = String::new();
 
-        for j in 0..domain.steps.y {
-            for i in 0..domain.steps.x {
-                s.push({
-                    let x = matrix.get(i).unwrap().get(j).unwrap();
-
-                    if *x <= 0.0 {
-                        '#'
-                    } else {
-                        ' '
-                    }
-                });
+        for row in field.rows() {
+            for x in row {
+                s.push(if *x <= 0.0 { '#' } else { ' ' });
             }
             s.push('\n');
         }
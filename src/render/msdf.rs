@@ -0,0 +1,728 @@
+use std::io::{self, Write};
+
+use image::{Rgb, RgbImage};
+use itertools_num::linspace;
+
+use crate::{
+    objects::{
+        curve::Curve,
+        primitives::{
+            BezierSegment, BezierSpline, CubicBezier, FillRule, Polygon, QuadraticBezier,
+        },
+    },
+    Domain, Vec2,
+};
+
+/// Channels of an edge, one bit each for red, green and blue
+type Color = u8;
+const WHITE: Color = 0b111;
+const YELLOW: Color = 0b011;
+const MAGENTA: Color = 0b101;
+const CYAN: Color = 0b110;
+
+/// Closed outlines to generate MSDFs of
+#[derive(Clone, Debug, PartialEq)]
+pub struct Shape {
+    /// Every contour is a list of segments, each starting where the one before it ends, and the
+    /// last one ends where the first one starts
+    pub contours: Vec<Vec<BezierSegment>>,
+    /// Decides which points are inside of the contours
+    pub fill_rule: FillRule,
+}
+impl Shape {
+    /// Corners of the smallest box around all contours, `None` if there aren't any segments
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        self.contours
+            .iter()
+            .flatten()
+            .map(|segment| curve(segment).bounds())
+            .reduce(|(min, max), (a, b)| (min.min(a), max.max(b)))
+    }
+
+    /// Whether `p` is inside of the shape
+    fn contains(&self, curves: &[Curve], p: Vec2) -> bool {
+        let winding_number: i32 = curves.iter().map(|curve| curve.winding_number(p)).sum();
+
+        match self.fill_rule {
+            FillRule::NonZero => winding_number != 0,
+            FillRule::EvenOdd => winding_number % 2 != 0,
+        }
+    }
+}
+impl From<&BezierSpline> for Shape {
+    /// The contours of the spline, which are split wherever a segment doesn't start at the end of
+    /// the one before it
+    fn from(spline: &BezierSpline) -> Self {
+        let mut contours: Vec<Vec<BezierSegment>> = Vec::new();
+        for &segment in spline.segments() {
+            match contours.last_mut() {
+                Some(contour)
+                    if contour.last().map(BezierSegment::end) == Some(segment.start()) =>
+                {
+                    contour.push(segment)
+                }
+                _ => contours.push(vec![segment]),
+            }
+        }
+
+        Shape {
+            contours,
+            fill_rule: spline.fill_rule(),
+        }
+    }
+}
+impl From<&Polygon> for Shape {
+    fn from(polygon: &Polygon) -> Self {
+        let vertices = polygon.vertices();
+        let next = vertices.iter().cycle().skip(1);
+        let contour: Vec<BezierSegment> = vertices
+            .iter()
+            .zip(next)
+            .filter(|(a, b)| a != b)
+            .map(|(&a, &b)| BezierSegment::Linear([a, b]))
+            .collect();
+
+        Shape {
+            contours: if contour.is_empty() {
+                Vec::new()
+            } else {
+                vec![contour]
+            },
+            fill_rule: FillRule::NonZero,
+        }
+    }
+}
+
+/// Change how MSDFs are generated
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    /// Width of the band of distances around the outline that can be told apart, in pixels (the
+    /// distance between neighbouring samples along x)
+    pub range: f64,
+    /// Where the direction of a contour changes by more than this angle (in radians), there is a
+    /// corner, whose edges get different colors
+    pub angle_threshold: f64,
+    /// Resets pixels to their median, whose channels clash with the ones of a neighbour, which
+    /// removes most artifacts between the edges
+    pub error_correction: bool,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            range: 4.0,
+            angle_threshold: 3.0,
+            error_correction: true,
+        }
+    }
+}
+
+/// Renders a multi-channel signed distance field of `shape`, with one pixel for every sample of
+/// `domain`
+///
+/// The edges of every contour are colored so that the edges at each corner have different
+/// colors, which makes every channel hold the distance to its own set of edges. The median of the
+/// three channels then keeps the corners sharp, even when the image is scaled up.
+///
+/// Like in msdfgen every channel stores `0.5 - d / range`, where `d` is the distance of the SDF
+/// (negative inside) and `range` is [`Settings::range`] in units of the SDF. So the outline is where
+/// the median is 0.5 and the inside is brighter, which is what MSDF shaders expect.
+pub fn image(shape: &Shape, domain: &Domain, settings: &Settings) -> RgbImage {
+    let pixel = if domain.steps.x > 1 {
+        (domain.p1.x - domain.p0.x).abs() / (domain.steps.x as f64 - 1.0)
+    } else {
+        1.0
+    };
+    let range = settings.range * pixel;
+    let values = channels(shape, domain, range, settings);
+
+    let to_byte = |v: f64| (255.0 * v.clamp(0.0, 1.0)).round() as u8;
+    RgbImage::from_fn(domain.steps.x as u32, domain.steps.y as u32, |x, y| {
+        Rgb(values[y as usize * domain.steps.x + x as usize].map(to_byte))
+    })
+}
+
+/// The channels of every sample of `domain` row by row, as `0.5 - d / range`
+fn channels(shape: &Shape, domain: &Domain, range: f64, settings: &Settings) -> Vec<[f64; 3]> {
+    let edges = edges(shape, settings.angle_threshold);
+    let xs: Vec<f64> = linspace(domain.p0.x, domain.p1.x, domain.steps.x).collect();
+    let ys: Vec<f64> = linspace(domain.p0.y, domain.p1.y, domain.steps.y).collect();
+
+    let mut values: Vec<[f64; 3]> = ys
+        .iter()
+        .flat_map(|&y| xs.iter().map(move |&x| Vec2::new(x, y)))
+        .map(|p| pseudo_distances(&edges, p).map(|d| 0.5 + d / range))
+        .collect();
+
+    if settings.error_correction {
+        correct_errors(&mut values, domain.steps.x, 1.001 / settings.range);
+    }
+
+    values
+}
+
+/// A segment of a contour with the channels it counts for
+struct Edge {
+    curve: Curve,
+    color: Color,
+}
+
+/// The edges of all contours, which are turned so that the inside is on their left, with colors
+fn edges(shape: &Shape, angle_threshold: f64) -> Vec<Edge> {
+    let curves: Vec<Curve> = shape.contours.iter().flatten().map(curve).collect();
+
+    let mut edges = Vec::new();
+    for contour in &shape.contours {
+        let Some(&probe) = contour.first() else {
+            continue;
+        };
+
+        // Probing both sides of a point on the contour shows which one the inside is on
+        let probe = curve(&probe);
+        let (q, direction) = (probe.point(0.5), probe.derivative(0.5));
+        let offset = Vec2::new(-direction.y, direction.x).normalize() * 1e-6 * (1.0 + q.length());
+        let turned = !shape.contains(&curves, q + offset) && shape.contains(&curves, q - offset);
+
+        let contour: Vec<BezierSegment> = if turned {
+            contour.iter().rev().map(reversed).collect()
+        } else {
+            contour.clone()
+        };
+        for (segment, color) in color_edges(contour, angle_threshold) {
+            edges.push(Edge {
+                curve: curve(&segment),
+                color,
+            });
+        }
+    }
+
+    edges
+}
+
+/// Colors the edges of a contour like the simple edge coloring of msdfgen
+///
+/// Between two corners all edges get the same color, which changes at every corner. A contour
+/// with a single corner is split into three parts, so the corner still sees two colors.
+fn color_edges(
+    mut contour: Vec<BezierSegment>,
+    angle_threshold: f64,
+) -> Vec<(BezierSegment, Color)> {
+    let threshold = angle_threshold.sin();
+    let is_corner = |a: Vec2, b: Vec2| {
+        let (a, b) = (a.normalize(), b.normalize());
+        a.dot(b) <= 0.0 || a.cross(b).abs() > threshold
+    };
+    let corners = |contour: &[BezierSegment]| -> Vec<usize> {
+        (0..contour.len())
+            .filter(|&i| {
+                let previous = &contour[(i + contour.len() - 1) % contour.len()];
+                is_corner(end_direction(previous), start_direction(&contour[i]))
+            })
+            .collect()
+    };
+
+    let mut seed = 0;
+    let mut corners_of_contour = corners(&contour);
+    match corners_of_contour.len() {
+        0 => contour
+            .into_iter()
+            .map(|segment| (segment, WHITE))
+            .collect(),
+        1 => {
+            if contour.len() < 3 {
+                // Thirds of the edges from the corner on, which is either at the first or the
+                // second edge
+                let start = corners_of_contour[0];
+                contour.rotate_left(start);
+                contour = contour.iter().flat_map(split_in_thirds).collect();
+                corners_of_contour = vec![0];
+            }
+
+            let mut colors = [WHITE; 3];
+            colors[0] = switch_color(WHITE, &mut seed, None);
+            colors[2] = switch_color(colors[0], &mut seed, None);
+            let (corner, m) = (corners_of_contour[0], contour.len());
+            let mut colored: Vec<(BezierSegment, Color)> =
+                contour.iter().map(|&segment| (segment, WHITE)).collect();
+            for i in 0..m {
+                colored[(corner + i) % m].1 = colors[(1 + trichotomy(i, m)) as usize];
+            }
+            colored
+        }
+        count => {
+            let start = corners_of_contour[0];
+            let mut color = switch_color(WHITE, &mut seed, None);
+            let initial = color;
+            let mut spline = 0;
+            let mut colored: Vec<(BezierSegment, Color)> =
+                contour.iter().map(|&segment| (segment, WHITE)).collect();
+            for i in 0..contour.len() {
+                let index = (start + i) % contour.len();
+                if spline + 1 < count && corners_of_contour[spline + 1] == index {
+                    spline += 1;
+                    // The last color must differ from the first one, which it meets at `start`
+                    let banned = (spline == count - 1).then_some(initial);
+                    color = switch_color(color, &mut seed, banned);
+                }
+                colored[index].1 = color;
+            }
+            colored
+        }
+    }
+}
+
+/// The next color of the edge coloring, which has two channels and differs from `color` (and
+/// `banned`) in one of them
+fn switch_color(color: Color, seed: &mut u64, banned: Option<Color>) -> Color {
+    if let Some(banned) = banned {
+        let combined = color & banned;
+        if combined.count_ones() == 1 {
+            return combined ^ WHITE;
+        }
+    }
+    if color == WHITE {
+        let start = [CYAN, MAGENTA, YELLOW][(*seed % 3) as usize];
+        *seed /= 3;
+        return start;
+    }
+
+    let shifted = color << (1 + (*seed & 1));
+    *seed >>= 1;
+
+    (shifted | shifted >> 3) & WHITE
+}
+
+/// Splits `0..n` into three parts of about the same size, as -1, 0 and 1, symmetrically
+fn trichotomy(position: usize, n: usize) -> i32 {
+    (3.0 + 2.875 * position as f64 / (n as f64 - 1.0) - 1.4375 + 0.5) as i32 - 3
+}
+
+/// The signed pseudo distances of the closest edges of every channel, positive inside
+///
+/// The distance to an edge, whose closest point is one of its ends, is the distance to the
+/// tangent there, if that is smaller. That is what keeps the corners sharp.
+fn pseudo_distances(edges: &[Edge], p: Vec2) -> [f64; 3] {
+    // The distance, how much the direction to the closest point is along the edge and the
+    // pseudo distance
+    let mut closest = [(f64::INFINITY, f64::INFINITY, f64::NEG_INFINITY); 3];
+
+    for edge in edges {
+        let (t, distance_squared) = edge.curve.closest(p);
+        let q = edge.curve.point(t);
+        let direction = direction(&edge.curve, t);
+        let distance = distance_squared.sqrt();
+        let offset = p - q;
+        let side = if direction.cross(offset) >= 0.0 {
+            1.0
+        } else {
+            -1.0
+        };
+
+        let mut pseudo = side * distance;
+        let mut alignment = 0.0;
+        if distance > 0.0 && (t == 0.0 || t == 1.0) {
+            alignment = direction.dot(offset).abs() / distance;
+            let beyond = if t == 0.0 {
+                direction.dot(offset) < 0.0
+            } else {
+                direction.dot(offset) > 0.0
+            };
+            let perpendicular = direction.cross(offset);
+            if beyond && perpendicular.abs() <= distance {
+                pseudo = perpendicular;
+            }
+        }
+
+        for (channel, closest) in closest.iter_mut().enumerate() {
+            if edge.color & (1 << channel) == 0 {
+                continue;
+            }
+            if distance < closest.0 || (distance == closest.0 && alignment < closest.1) {
+                *closest = (distance, alignment, pseudo);
+            }
+        }
+    }
+
+    closest.map(|(_, _, pseudo)| pseudo)
+}
+
+/// Sets pixels to their median, where neighbouring pixels clash
+///
+/// Two neighbours clash, if two of their channels change a lot between them while the third one
+/// doesn't, which happens between edges of different colors and leaves artifacts.
+fn correct_errors(values: &mut [[f64; 3]], width: usize, threshold: f64) {
+    if width == 0 {
+        return;
+    }
+    let height = values.len() / width;
+
+    let mut clashes = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            let a = values[y * width + x];
+            let neighbours = [
+                (x.wrapping_sub(1), y, threshold),
+                (x + 1, y, threshold),
+                (x, y.wrapping_sub(1), threshold),
+                (x, y + 1, threshold),
+                (x.wrapping_sub(1), y.wrapping_sub(1), 2.0 * threshold),
+                (x + 1, y.wrapping_sub(1), 2.0 * threshold),
+                (x.wrapping_sub(1), y + 1, 2.0 * threshold),
+                (x + 1, y + 1, 2.0 * threshold),
+            ];
+            let clash = neighbours.iter().any(|&(nx, ny, threshold)| {
+                nx < width && ny < height && clashes_with(a, values[ny * width + nx], threshold)
+            });
+            if clash {
+                clashes.push(y * width + x);
+            }
+        }
+    }
+
+    for i in clashes {
+        values[i] = [median(values[i]); 3];
+    }
+}
+
+/// Whether the pixel `a` clashes with its neighbour `b`
+fn clashes_with(a: [f64; 3], b: [f64; 3], threshold: f64) -> bool {
+    // The channels ordered by how much they change, the most first
+    let mut channels = [0, 1, 2];
+    channels.sort_by(|&i, &j| (b[j] - a[j]).abs().total_cmp(&(b[i] - a[i]).abs()));
+    let [first, second, third] = channels;
+
+    (b[second] - a[second]).abs() >= threshold
+        && !(b[first] == b[second] && b[first] == b[third])
+        && (a[third] - 0.5).abs() >= (b[third] - 0.5).abs()
+}
+
+fn median([a, b, c]: [f64; 3]) -> f64 {
+    a.min(b).max(a.max(b).min(c))
+}
+
+/// A shape to put into an [`Atlas`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasEntry {
+    /// Name of the shape in the metrics, like the character of a glyph
+    pub name: String,
+    /// The outlines of the shape
+    pub shape: Shape,
+    /// How far the pen moves after the shape, e.g. the advance of a glyph
+    pub advance: f64,
+}
+
+/// Change how an [`Atlas`] is generated
+#[derive(Clone, Debug, PartialEq)]
+pub struct AtlasSettings {
+    /// Number of pixels per unit of the shapes
+    pub scale: f64,
+    /// Width of the atlas, which only shapes wider than it exceed
+    pub max_width: u32,
+    /// Number of empty pixels around every shape, so their samples don't bleed into each other
+    pub padding: u32,
+    /// Settings of the MSDF of every shape, the range is in pixels of the atlas
+    pub msdf: Settings,
+}
+impl Default for AtlasSettings {
+    fn default() -> Self {
+        AtlasSettings {
+            scale: 32.0,
+            max_width: 512,
+            padding: 1,
+            msdf: Settings::default(),
+        }
+    }
+}
+
+/// Rectangles are given as `[left, top, right, bottom]`, with y pointing down like in images
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metrics {
+    /// Name of the shape
+    pub name: String,
+    /// How far the pen moves after the shape
+    pub advance: f64,
+    /// Bounds of the outlines in units of the shape, all 0 for empty shapes
+    pub bounds: [f64; 4],
+    /// The rectangle in units of the shape, that the pixels of the shape cover
+    pub plane: [f64; 4],
+    /// The pixels of the shape in the atlas
+    pub pixels: [u32; 4],
+    /// The pixels of the shape in texture coordinates, from 0 to 1
+    pub uv: [f64; 4],
+}
+
+/// Many MSDFs packed into one image, with the metrics to find and draw them
+#[derive(Clone, Debug, PartialEq)]
+pub struct Atlas {
+    /// All MSDFs
+    pub image: RgbImage,
+    /// Where every shape is, in the order of the entries
+    pub metrics: Vec<Metrics>,
+    /// [`Settings::range`] of the MSDFs in pixels
+    pub range: f64,
+}
+impl Atlas {
+    /// Writes the metrics as JSON, with the size of the atlas and the range
+    pub fn write_json<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let list = |values: &[String]| values.join(", ");
+        writeln!(writer, "{{")?;
+        writeln!(writer, "  \"width\": {},", self.image.width())?;
+        writeln!(writer, "  \"height\": {},", self.image.height())?;
+        writeln!(writer, "  \"range\": {},", self.range)?;
+        writeln!(writer, "  \"shapes\": [")?;
+        for (i, metrics) in self.metrics.iter().enumerate() {
+            let separator = if i + 1 < self.metrics.len() { "," } else { "" };
+            writeln!(
+                writer,
+                "    {{\"name\": {}, \"advance\": {}, \"bounds\": [{}], \"plane\": [{}], \
+                 \"pixels\": [{}], \"uv\": [{}]}}{separator}",
+                json_string(&metrics.name),
+                metrics.advance,
+                list(&metrics.bounds.map(|v| v.to_string())),
+                list(&metrics.plane.map(|v| v.to_string())),
+                list(&metrics.pixels.map(|v| v.to_string())),
+                list(&metrics.uv.map(|v| v.to_string())),
+            )?;
+        }
+        writeln!(writer, "  ]")?;
+        writeln!(writer, "}}")
+    }
+
+    /// Writes the metrics as CSV, with a header and one line for every shape
+    pub fn write_csv<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let columns = |prefix: &str| {
+            ["left", "top", "right", "bottom"].map(|side| format!("{prefix}_{side}"))
+        };
+        let mut header = vec!["name".to_string(), "advance".to_string()];
+        for prefix in ["bounds", "plane", "pixels", "uv"] {
+            header.extend(columns(prefix));
+        }
+        writeln!(writer, "{}", header.join(","))?;
+
+        for metrics in &self.metrics {
+            let mut row = vec![csv_field(&metrics.name), metrics.advance.to_string()];
+            row.extend(metrics.bounds.map(|v| v.to_string()));
+            row.extend(metrics.plane.map(|v| v.to_string()));
+            row.extend(metrics.pixels.map(|v| v.to_string()));
+            row.extend(metrics.uv.map(|v| v.to_string()));
+            writeln!(writer, "{}", row.join(","))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Generates the MSDFs of all `entries` and packs them into rows of an atlas, the tallest first
+///
+/// Every shape gets enough pixels around its bounds for half of the range, so its whole band of
+/// distances is in the atlas. Empty shapes (like spaces) only have metrics.
+pub fn atlas(entries: &[AtlasEntry], settings: &AtlasSettings) -> Atlas {
+    let scale = settings.scale;
+    let margin = (0.5 * settings.msdf.range).ceil();
+
+    // The bounds, plane and size in pixels of every shape
+    let cells: Vec<([f64; 4], [f64; 4], u32, u32)> = entries
+        .iter()
+        .map(|entry| match entry.shape.bounds() {
+            Some((min, max)) => {
+                let width = ((max.x - min.x) * scale).ceil() + 2.0 * margin;
+                let height = ((max.y - min.y) * scale).ceil() + 2.0 * margin;
+                let (left, top) = (min.x - margin / scale, min.y - margin / scale);
+                let plane = [left, top, left + width / scale, top + height / scale];
+
+                (
+                    [min.x, min.y, max.x, max.y],
+                    plane,
+                    width as u32,
+                    height as u32,
+                )
+            }
+            None => ([0.0; 4], [0.0; 4], 0, 0),
+        })
+        .collect();
+
+    // Shelves from top to bottom, each filled from left to right
+    let mut order: Vec<usize> = (0..entries.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(cells[i].3));
+    let mut positions = vec![(0, 0); entries.len()];
+    let (mut x, mut y, mut shelf) = (settings.padding, settings.padding, 0);
+    let mut width = 0;
+    for &i in order.iter().filter(|&&i| cells[i].2 > 0) {
+        let (w, h) = (cells[i].2, cells[i].3);
+        if x > settings.padding && x + w + settings.padding > settings.max_width {
+            x = settings.padding;
+            y += shelf + settings.padding;
+            shelf = 0;
+        }
+        positions[i] = (x, y);
+        x += w + settings.padding;
+        shelf = shelf.max(h);
+        width = width.max(x);
+    }
+    let height = if shelf > 0 {
+        y + shelf + settings.padding
+    } else {
+        0
+    };
+
+    let mut image = RgbImage::new(width, height);
+    let mut metrics = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let (bounds, plane, w, h) = cells[i];
+        let (x, y) = positions[i];
+
+        if w > 0 {
+            let domain = Domain {
+                p0: Vec2::new(plane[0] + 0.5 / scale, plane[1] + 0.5 / scale),
+                p1: Vec2::new(plane[2] - 0.5 / scale, plane[3] - 0.5 / scale),
+                steps: Vec2::new(w as usize, h as usize),
+            };
+            let cell = self::image(&entry.shape, &domain, &settings.msdf);
+            for (cx, cy, pixel) in cell.enumerate_pixels() {
+                image.put_pixel(x + cx, y + cy, *pixel);
+            }
+        }
+
+        let pixels = if w > 0 { [x, y, x + w, y + h] } else { [0; 4] };
+        let uv = if w > 0 {
+            [
+                f64::from(x) / f64::from(width),
+                f64::from(y) / f64::from(height),
+                f64::from(x + w) / f64::from(width),
+                f64::from(y + h) / f64::from(height),
+            ]
+        } else {
+            [0.0; 4]
+        };
+        metrics.push(Metrics {
+            name: entry.name.clone(),
+            advance: entry.advance,
+            bounds,
+            plane,
+            pixels,
+            uv,
+        });
+    }
+
+    Atlas {
+        image,
+        metrics,
+        range: settings.msdf.range,
+    }
+}
+
+/// `s` as a JSON string, with quotes
+fn json_string(s: &str) -> String {
+    let mut escaped = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if u32::from(c) < 0x20 => escaped.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+/// `s` as a field of a CSV line, which is quoted if it has to be
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn curve(segment: &BezierSegment) -> Curve {
+    Curve::new(&control_points(segment))
+}
+
+fn control_points(segment: &BezierSegment) -> Vec<Vec2> {
+    match *segment {
+        BezierSegment::Linear(points) => points.to_vec(),
+        BezierSegment::Quadratic(curve) => vec![curve.p0, curve.p1, curve.p2],
+        BezierSegment::Cubic(curve) => vec![curve.p0, curve.p1, curve.p2, curve.p3],
+    }
+}
+
+fn from_control_points(points: &[Vec2]) -> BezierSegment {
+    match *points {
+        [p0, p1] => BezierSegment::Linear([p0, p1]),
+        [p0, p1, p2] => BezierSegment::Quadratic(QuadraticBezier { p0, p1, p2 }),
+        [p0, p1, p2, p3] => BezierSegment::Cubic(CubicBezier { p0, p1, p2, p3 }),
+        _ => unreachable!("segments have 2 to 4 control points"),
+    }
+}
+
+/// The same segment, running the other way around
+fn reversed(segment: &BezierSegment) -> BezierSegment {
+    let mut points = control_points(segment);
+    points.reverse();
+
+    from_control_points(&points)
+}
+
+/// The parts of the segment before and after `t`, by de Casteljau's algorithm
+fn split(segment: &BezierSegment, t: f64) -> (BezierSegment, BezierSegment) {
+    let mut points = control_points(segment);
+    let (mut before, mut after) = (vec![points[0]], vec![points[points.len() - 1]]);
+    while points.len() > 1 {
+        points = points
+            .windows(2)
+            .map(|pair| pair[0] + (pair[1] - pair[0]) * t)
+            .collect();
+        before.push(points[0]);
+        after.push(points[points.len() - 1]);
+    }
+    after.reverse();
+
+    (from_control_points(&before), from_control_points(&after))
+}
+
+fn split_in_thirds(segment: &BezierSegment) -> [BezierSegment; 3] {
+    let (first, rest) = split(segment, 1.0 / 3.0);
+    let (second, third) = split(&rest, 0.5);
+
+    [first, second, third]
+}
+
+/// Direction in which the segment leaves its start, from the first control point that differs
+fn start_direction(segment: &BezierSegment) -> Vec2 {
+    let points = control_points(segment);
+    let next = points[1..]
+        .iter()
+        .find(|&&p| p != points[0])
+        .unwrap_or(&points[points.len() - 1]);
+
+    *next - points[0]
+}
+
+/// Direction in which the segment arrives at its end
+fn end_direction(segment: &BezierSegment) -> Vec2 {
+    let points = control_points(segment);
+    let end = points[points.len() - 1];
+    let previous = points[..points.len() - 1]
+        .iter()
+        .rev()
+        .find(|&&p| p != end)
+        .unwrap_or(&points[0]);
+
+    end - *previous
+}
+
+/// Direction of the curve at `t`, which uses the chord where the derivative vanishes
+fn direction(curve: &Curve, t: f64) -> Vec2 {
+    let derivative = curve.derivative(t);
+    let direction = if derivative.length_squared() > 0.0 {
+        derivative
+    } else {
+        curve.point(1.0) - curve.point(0.0)
+    };
+
+    direction.normalize()
+}
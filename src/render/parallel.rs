@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use image::{ImageBuffer, Rgb, RgbImage};
+use itertools_num::linspace;
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+use crate::{field::SampledField, objects::SignedDistanceField, Domain};
+
+/// Chooses the threads that render
+#[derive(Clone, Debug, Default)]
+pub struct Settings {
+    /// The thread pool that renders, `None` uses the global pool of rayon
+    pub pool: Option<Arc<ThreadPool>>,
+}
+
+impl Settings {
+    /// Settings with a new thread pool of `threads` threads
+    pub fn with_threads(threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new().num_threads(threads).build()?;
+
+        Ok(Settings {
+            pool: Some(Arc::new(pool)),
+        })
+    }
+
+    /// Runs `op` within the thread pool of the settings
+    pub fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Some(pool) => pool.install(op),
+            None => op(),
+        }
+    }
+}
+
+/// Renders a SDF into an image like [`super::image`], using all threads of the pool
+pub fn image(
+    sdf: &dyn SignedDistanceField,
+    domain: &Domain,
+    color_mapper: &(dyn Fn(&f64) -> Rgb<u8> + Sync),
+    settings: &Settings,
+) -> RgbImage {
+    image_from_field(&matrix(sdf, domain, settings), color_mapper, settings)
+}
+
+/// Maps every sample of a field to a pixel like [`super::image_from_field`], one row of pixels
+/// per task
+pub fn image_from_field(
+    field: &SampledField,
+    color_mapper: &(dyn Fn(&f64) -> Rgb<u8> + Sync),
+    settings: &Settings,
+) -> RgbImage {
+    let (width, height) = (field.width(), field.height());
+    let mut pixels = vec![0; width * height * 3];
+
+    if width > 0 {
+        settings.install(|| {
+            pixels
+                .par_chunks_mut(width * 3)
+                .zip(field.values().par_chunks(width))
+                .for_each(|(pixels, values)| {
+                    for (pixel, value) in pixels.chunks_mut(3).zip(values) {
+                        pixel.copy_from_slice(&color_mapper(value).0);
+                    }
+                })
+        });
+    }
+
+    ImageBuffer::from_raw(width as u32, height as u32, pixels)
+        .expect("the buffer has the size of the image")
+}
+
+/// Renders a SDF into a text like [`super::text`], using all threads of the pool for the samples
+pub fn text(
+    sdf: &dyn SignedDistanceField,
+    domain: &Domain,
+    text_mapper: &(dyn Fn(&SampledField) -> String + Sync),
+    settings: &Settings,
+) -> String {
+    text_mapper(&matrix(sdf, domain, settings))
+}
+
+/// Renders a SDF into a matrix of samples like [`super::matrix`], one row per task
+///
+/// Every sample is evaluated at exactly the same point as in the serial renderer, so the samples
+/// are bit-identical to it, no matter how many threads there are.
+pub fn matrix(sdf: &dyn SignedDistanceField, domain: &Domain, settings: &Settings) -> SampledField {
+    let xs: Vec<f64> = linspace(domain.p0.x, domain.p1.x, domain.steps.x).collect();
+    let ys: Vec<f64> = linspace(domain.p0.y, domain.p1.y, domain.steps.y).collect();
+    let mut field = SampledField::from_values(domain.clone(), vec![0.0; xs.len() * ys.len()]);
+
+    if !xs.is_empty() {
+        settings.install(|| {
+            field
+                .values_mut()
+                .par_chunks_mut(xs.len())
+                .zip(&ys)
+                .for_each(|(values, &y)| super::row(sdf, &xs, y, values))
+        });
+    }
+
+    field
+}
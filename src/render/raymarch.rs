@@ -0,0 +1,151 @@
+use image::{ImageBuffer, Rgb, RgbImage};
+
+use crate::{objects::SignedDistanceField3, Vec3};
+
+/// A pinhole camera
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Camera {
+    /// Position of the camera
+    pub position: Vec3,
+    /// The point the camera looks at, which ends up in the center of the image
+    pub look_at: Vec3,
+    /// The direction that is up in the image
+    pub up: Vec3,
+    /// Vertical field of view (in radians)
+    pub fov: f64,
+}
+impl Camera {
+    /// Direction of the ray through the point `(u, v)` of the image plane, where both range from
+    /// -1 (left, bottom) to 1 (right, top) and `aspect` is the ratio of width to height
+    pub fn ray_direction(&self, u: f64, v: f64, aspect: f64) -> Vec3 {
+        let forward = (self.look_at - self.position).normalize();
+        let right = forward.cross(self.up).normalize();
+        let up = right.cross(forward);
+        let scale = (0.5 * self.fov).tan();
+
+        (forward + right * (u * scale * aspect) + up * (v * scale)).normalize()
+    }
+}
+
+/// Settings for the sphere tracing and the shading
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Settings {
+    /// Maximum number of steps along a ray before it counts as a miss
+    pub max_steps: usize,
+    /// A ray hits the surface once the distance gets smaller than this, it is also used as the
+    /// step size for the normals
+    pub epsilon: f64,
+    /// Rays that travel further than this count as a miss
+    pub far: f64,
+    /// Direction from the surface towards the light, it has to have a length of 1
+    pub light: Vec3,
+    /// Color of the surface
+    pub color: Rgb<u8>,
+    /// Color of rays that miss
+    pub background: Rgb<u8>,
+    /// Share of the color that is always visible
+    pub ambient: f64,
+    /// Strength of the Lambertian (diffuse) reflection
+    pub diffuse: f64,
+    /// Strength of the Blinn-Phong (specular) highlight
+    pub specular: f64,
+    /// Exponent of the Blinn-Phong highlight, larger values make it smaller
+    pub shininess: f64,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            max_steps: 256,
+            epsilon: 1e-4,
+            far: 100.0,
+            light: Vec3::new(-1.0, -2.0, 3.0).normalize(),
+            color: Rgb([200, 200, 200]),
+            background: Rgb([0, 0, 0]),
+            ambient: 0.1,
+            diffuse: 0.8,
+            specular: 0.4,
+            shininess: 32.0,
+        }
+    }
+}
+
+/// Renders a 3D SDF into an image by sphere tracing a ray through every pixel
+pub fn image(
+    sdf: &dyn SignedDistanceField3,
+    camera: &Camera,
+    width: u32,
+    height: u32,
+    settings: &Settings,
+) -> RgbImage {
+    let aspect = width as f64 / height as f64;
+
+    ImageBuffer::from_fn(width, height, |x: u32, y: u32| -> Rgb<u8> {
+        let u = 2.0 * (x as f64 + 0.5) / width as f64 - 1.0;
+        let v = 1.0 - 2.0 * (y as f64 + 0.5) / height as f64;
+        let direction = camera.ray_direction(u, v, aspect);
+
+        match trace(sdf, camera.position, direction, settings) {
+            Some(t) => shade(sdf, camera.position + direction * t, direction, settings),
+            None => settings.background,
+        }
+    })
+}
+
+/// Marches along the ray from `origin` in `direction` (with a length of 1) and returns the
+/// distance to the first hit, if there is one
+pub fn trace(
+    sdf: &dyn SignedDistanceField3,
+    origin: Vec3,
+    direction: Vec3,
+    settings: &Settings,
+) -> Option<f64> {
+    let mut t = 0.0;
+
+    for _ in 0..settings.max_steps {
+        let distance = sdf.call(origin + direction * t);
+
+        if distance < settings.epsilon {
+            return Some(t);
+        }
+
+        t += distance;
+        if t > settings.far {
+            return None;
+        }
+    }
+
+    None
+}
+
+/// The surface normal at `p`, approximated with central differences
+pub fn normal(sdf: &dyn SignedDistanceField3, p: Vec3, epsilon: f64) -> Vec3 {
+    let dx = Vec3::new(epsilon, 0.0, 0.0);
+    let dy = Vec3::new(0.0, epsilon, 0.0);
+    let dz = Vec3::new(0.0, 0.0, epsilon);
+
+    Vec3::new(
+        sdf.call(p + dx) - sdf.call(p - dx),
+        sdf.call(p + dy) - sdf.call(p - dy),
+        sdf.call(p + dz) - sdf.call(p - dz),
+    )
+    .normalize()
+}
+
+/// Lambert and Blinn-Phong shading of the surface point `p`, seen along `direction`
+fn shade(sdf: &dyn SignedDistanceField3, p: Vec3, direction: Vec3, settings: &Settings) -> Rgb<u8> {
+    let n = normal(sdf, p, settings.epsilon);
+    let halfway = (settings.light - direction).normalize();
+
+    let diffuse = settings.diffuse * n.dot(settings.light).max(0.0);
+    let specular = if diffuse > 0.0 {
+        settings.specular * n.dot(halfway).max(0.0).powf(settings.shininess)
+    } else {
+        0.0
+    };
+    let intensity = settings.ambient + diffuse;
+
+    Rgb(settings
+        .color
+        .0
+        .map(|c| (c as f64 * intensity + 255.0 * specular).clamp(0.0, 255.0) as u8))
+}
@@ -0,0 +1,233 @@
+use std::io::{self, Write};
+
+pub use crate::objects::primitives::FillRule;
+use crate::{
+    contour,
+    objects::{primitives::Rectangle, SignedDistanceField},
+    Domain, Vec2,
+};
+
+/// Change the style of the SVG render
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    /// Every level gets its own path, which is the outline `sdf(p) = level`
+    pub iso_levels: Vec<f64>,
+    /// Color of the outlines, `None` doesn't draw them
+    pub stroke: Option<String>,
+    /// Width of the outlines in pixels
+    pub stroke_width: f64,
+    /// Color of the inside (`sdf(p) < level`), `None` doesn't fill it
+    pub fill: Option<String>,
+    /// Fill rule of the filled paths
+    pub fill_rule: FillRule,
+    /// Color of the background, `None` leaves it transparent
+    pub background: Option<String>,
+}
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            iso_levels: vec![0.0],
+            stroke: Some("black".to_string()),
+            stroke_width: 1.0,
+            fill: None,
+            fill_rule: FillRule::EvenOdd,
+            background: None,
+        }
+    }
+}
+
+/// Writes a SDF as an SVG document
+///
+/// The `viewBox` is the domain in its own units, grown by half a sample spacing on every side, so
+/// the paths are in domain coordinates and every sample of the domain is in the center of a pixel
+/// of the `domain.steps` sized document, which lines up with [`image`](super::image) renders of
+/// the same domain. Domains whose `p1` is smaller than `p0` are mirrored, like in those renders.
+pub fn write<W: Write>(
+    writer: &mut W,
+    sdf: &dyn SignedDistanceField,
+    domain: &Domain,
+    settings: &Settings,
+) -> io::Result<()> {
+    let (width, height) = (domain.steps.x, domain.steps.y);
+    let size = domain.p1 - domain.p0;
+    let cell = Vec2::new(
+        size.x / (domain.steps.x.max(2) - 1) as f64,
+        size.y / (domain.steps.y.max(2) - 1) as f64,
+    )
+    .abs();
+    let min = domain.p0.min(domain.p1) - cell * 0.5;
+    let view = size.abs() + cell;
+    // Enough decimals for a thousandth of a sample spacing
+    let precision = (3.0 - cell.min_element().log10().floor()).clamp(0.0, 17.0) as usize;
+
+    writeln!(
+        writer,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="{} {} {} {}">"#,
+        min.x, min.y, view.x, view.y
+    )?;
+
+    if let Some(background) = &settings.background {
+        writeln!(
+            writer,
+            r#"  <rect x="{}" y="{}" width="{}" height="{}" fill="{}"/>"#,
+            min.x,
+            min.y,
+            view.x,
+            view.y,
+            escape(background)
+        )?;
+    }
+
+    // Mirrors the axes along which the domain runs backwards
+    let flipped = (size.x < 0.0, size.y < 0.0);
+    let indent = if flipped == (false, false) {
+        "  "
+    } else {
+        let center = domain.p0 + domain.p1;
+        let (a, e) = if flipped.0 {
+            (-1.0, center.x)
+        } else {
+            (1.0, 0.0)
+        };
+        let (d, f) = if flipped.1 {
+            (-1.0, center.y)
+        } else {
+            (1.0, 0.0)
+        };
+        writeln!(writer, r#"  <g transform="matrix({a} 0 0 {d} {e} {f})">"#)?;
+
+        "    "
+    };
+
+    for &level in &settings.iso_levels {
+        if let Some(fill) = &settings.fill {
+            let rule = match settings.fill_rule {
+                FillRule::EvenOdd => "evenodd",
+                FillRule::NonZero => "nonzero",
+            };
+            let d = path_data(&filled_outlines(sdf, domain, level), precision);
+
+            writeln!(
+                writer,
+                r#"{indent}<path d="{d}" fill="{}" fill-rule="{rule}" stroke="none"/>"#,
+                escape(fill)
+            )?;
+        }
+
+        if let Some(stroke) = &settings.stroke {
+            let d = path_data(&contour::polylines(sdf, domain, level), precision);
+
+            writeln!(
+                writer,
+                r#"{indent}<path d="{d}" fill="none" stroke="{}" stroke-width="{}" vector-effect="non-scaling-stroke"/>"#,
+                escape(stroke),
+                settings.stroke_width
+            )?;
+        }
+    }
+
+    if flipped != (false, false) {
+        writeln!(writer, "  </g>")?;
+    }
+    writeln!(writer, "</svg>")
+}
+
+/// `value` with the characters escaped, that can't be in an attribute value
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Closed outlines of the inside (`sdf(p) < level`) clipped to the domain, oriented so that the
+/// inside is on their left
+fn filled_outlines(
+    sdf: &dyn SignedDistanceField,
+    domain: &Domain,
+    level: f64,
+) -> Vec<Vec<[f64; 2]>> {
+    let size = domain.p1 - domain.p0;
+    let cell = Vec2::new(
+        size.x / (domain.steps.x.max(2) - 1) as f64,
+        size.y / (domain.steps.y.max(2) - 1) as f64,
+    );
+    let bounds = Rectangle {
+        w: size.x.abs(),
+        h: size.y.abs(),
+    };
+    let center = (domain.p0 + domain.p1) * 0.5;
+    let clipped = |p: Vec2| (sdf.call(p) - level).max(bounds.call(p - center));
+
+    // Sample one extra cell around the domain, so shapes touching its border get closed there
+    let padded = Domain {
+        p0: domain.p0 - cell,
+        p1: domain.p1 + cell,
+        steps: Vec2::new(domain.steps.x + 2, domain.steps.y + 2),
+    };
+
+    contour::polylines(&ClosureField(&clipped), &padded, 0.0)
+        .into_iter()
+        .filter(|polyline| polyline.len() > 3 && polyline.first() == polyline.last())
+        .map(|mut polyline| {
+            // Crossings close to a sample can be arbitrarily close together, so the longest
+            // segment is used to find out on which side the inside is
+            let (a, b) = polyline
+                .windows(2)
+                .map(|segment| (Vec2::from(segment[0]), Vec2::from(segment[1])))
+                .max_by(|(a0, b0), (a1, b1)| a0.distance(*b0).total_cmp(&a1.distance(*b1)))
+                .unwrap();
+            let left = (a + b) * 0.5 + (b - a).perp() * 1e-3;
+
+            if clipped(left) >= 0.0 {
+                polyline.reverse();
+            }
+            polyline
+        })
+        .collect()
+}
+
+/// Wraps a borrowed closure, so it can be contoured
+struct ClosureField<'a, F>(&'a F);
+impl<F: Fn(Vec2) -> f64 + Send + Sync> SignedDistanceField for ClosureField<'_, F> {
+    fn call(&self, p: Vec2) -> f64 {
+        (self.0)(p)
+    }
+}
+
+/// Turns polylines in domain coordinates into the `d` attribute of an SVG path, with `precision`
+/// decimals
+fn path_data(polylines: &[Vec<[f64; 2]>], precision: usize) -> String {
+    let mut d = String::new();
+    for polyline in polylines {
+        let closed = polyline.len() > 2 && polyline.first() == polyline.last();
+        let points = if closed {
+            &polyline[..polyline.len() - 1]
+        } else {
+            &polyline[..]
+        };
+
+        for (i, &[x, y]) in points.iter().enumerate() {
+            let command = if i == 0 { 'M' } else { 'L' };
+            if !d.is_empty() {
+                d.push(' ');
+            }
+            d.push_str(&format!("{command}{x:.precision$} {y:.precision$}"));
+        }
+
+        if closed {
+            d.push_str(" Z");
+        }
+    }
+
+    d
+}
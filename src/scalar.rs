@@ -0,0 +1,392 @@
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
+};
+
+use crate::{
+    objects::{SignedDistanceField, SignedDistanceField3},
+    Vec2, Vec3,
+};
+
+/// A number SDFs can be evaluated with
+///
+/// Parameters of SDFs stay `f64`, which is why every scalar can be combined and compared with
+/// `f64`s. Branches (like in `min` and `max`) are taken on the [`value`](Scalar::value).
+pub trait Scalar:
+    Copy
+    + Debug
+    + Default
+    + PartialOrd
+    + PartialOrd<f64>
+    + Send
+    + Sync
+    + 'static
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + Add<f64, Output = Self>
+    + Sub<f64, Output = Self>
+    + Mul<f64, Output = Self>
+    + Div<f64, Output = Self>
+    + AddAssign
+    + SubAssign
+{
+    /// Zero
+    const ZERO: Self;
+    /// One
+    const ONE: Self;
+
+    /// A constant
+    fn from_f64(v: f64) -> Self;
+
+    /// The plain value, without any derivatives
+    fn value(self) -> f64;
+
+    /// Square root
+    fn sqrt(self) -> Self;
+
+    /// Sine (in radians)
+    fn sin(self) -> Self;
+
+    /// Cosine (in radians)
+    fn cos(self) -> Self;
+
+    /// `2^self`
+    fn exp2(self) -> Self;
+
+    /// Logarithm to the base 2
+    fn log2(self) -> Self;
+
+    /// Absolute value
+    fn abs(self) -> Self {
+        if self < Self::ZERO {
+            -self
+        } else {
+            self
+        }
+    }
+
+    /// `1` for positive numbers (and `+0`), `-1` for negative ones
+    fn signum(self) -> Self {
+        Self::from_f64(self.value().signum())
+    }
+
+    /// The smaller of the numbers
+    fn min(self, other: Self) -> Self {
+        if self <= other {
+            self
+        } else {
+            other
+        }
+    }
+
+    /// The larger of the numbers
+    fn max(self, other: Self) -> Self {
+        if self >= other {
+            self
+        } else {
+            other
+        }
+    }
+
+    /// The number restricted to `[min, max]`
+    fn clamp(self, min: f64, max: f64) -> Self {
+        self.max(Self::from_f64(min)).min(Self::from_f64(max))
+    }
+
+    /// `sqrt(self² + other²)`
+    fn hypot(self, other: Self) -> Self {
+        (self * self + other * other).sqrt()
+    }
+}
+
+impl Scalar for f64 {
+    const ZERO: f64 = 0.0;
+    const ONE: f64 = 1.0;
+
+    fn from_f64(v: f64) -> f64 {
+        v
+    }
+
+    fn value(self) -> f64 {
+        self
+    }
+
+    fn sqrt(self) -> f64 {
+        f64::sqrt(self)
+    }
+
+    fn sin(self) -> f64 {
+        f64::sin(self)
+    }
+
+    fn cos(self) -> f64 {
+        f64::cos(self)
+    }
+
+    fn exp2(self) -> f64 {
+        f64::exp2(self)
+    }
+
+    fn log2(self) -> f64 {
+        f64::log2(self)
+    }
+
+    fn abs(self) -> f64 {
+        f64::abs(self)
+    }
+
+    fn signum(self) -> f64 {
+        f64::signum(self)
+    }
+
+    fn min(self, other: f64) -> f64 {
+        f64::min(self, other)
+    }
+
+    fn max(self, other: f64) -> f64 {
+        f64::max(self, other)
+    }
+
+    fn clamp(self, min: f64, max: f64) -> f64 {
+        f64::clamp(self, min, max)
+    }
+
+    fn hypot(self, other: f64) -> f64 {
+        f64::hypot(self, other)
+    }
+}
+
+/// A dual number `value + derivative * ε` with `ε² = 0`, which carries a derivative through every
+/// calculation (forward mode automatic differentiation)
+///
+/// Dual numbers of dual numbers carry second derivatives, see [`hessian`]. Like all scalars, dual
+/// numbers are compared by their value.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Dual<S = f64> {
+    /// The value
+    pub value: S,
+    /// The derivative with respect to the seeded variable
+    pub derivative: S,
+}
+impl<S: Scalar> Dual<S> {
+    /// A number with a derivative
+    pub fn new(value: S, derivative: S) -> Self {
+        Dual { value, derivative }
+    }
+
+    /// A constant, which has a derivative of zero
+    pub fn constant(value: S) -> Self {
+        Dual::new(value, S::ZERO)
+    }
+
+    /// The variable that is differentiated by, which has a derivative of one
+    pub fn variable(value: S) -> Self {
+        Dual::new(value, S::ONE)
+    }
+}
+
+impl<S: Scalar> PartialEq for Dual<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+impl<S: Scalar> PartialOrd for Dual<S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.value.partial_cmp(&other.value)
+    }
+}
+
+impl<S: Scalar> PartialEq<f64> for Dual<S> {
+    fn eq(&self, other: &f64) -> bool {
+        self.value() == *other
+    }
+}
+impl<S: Scalar> PartialOrd<f64> for Dual<S> {
+    fn partial_cmp(&self, other: &f64) -> Option<Ordering> {
+        self.value().partial_cmp(other)
+    }
+}
+
+impl<S: Scalar> Add for Dual<S> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Dual::new(self.value + rhs.value, self.derivative + rhs.derivative)
+    }
+}
+impl<S: Scalar> Sub for Dual<S> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Dual::new(self.value - rhs.value, self.derivative - rhs.derivative)
+    }
+}
+impl<S: Scalar> Mul for Dual<S> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Dual::new(
+            self.value * rhs.value,
+            self.derivative * rhs.value + self.value * rhs.derivative,
+        )
+    }
+}
+impl<S: Scalar> Div for Dual<S> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self {
+        Dual::new(
+            self.value / rhs.value,
+            (self.derivative * rhs.value - self.value * rhs.derivative) / (rhs.value * rhs.value),
+        )
+    }
+}
+impl<S: Scalar> Neg for Dual<S> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Dual::new(-self.value, -self.derivative)
+    }
+}
+impl<S: Scalar> Add<f64> for Dual<S> {
+    type Output = Self;
+
+    fn add(self, rhs: f64) -> Self {
+        Dual::new(self.value + rhs, self.derivative)
+    }
+}
+impl<S: Scalar> Sub<f64> for Dual<S> {
+    type Output = Self;
+
+    fn sub(self, rhs: f64) -> Self {
+        Dual::new(self.value - rhs, self.derivative)
+    }
+}
+impl<S: Scalar> Mul<f64> for Dual<S> {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        Dual::new(self.value * rhs, self.derivative * rhs)
+    }
+}
+impl<S: Scalar> Div<f64> for Dual<S> {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self {
+        Dual::new(self.value / rhs, self.derivative / rhs)
+    }
+}
+impl<S: Scalar> AddAssign for Dual<S> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+impl<S: Scalar> SubAssign for Dual<S> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<S: Scalar> Scalar for Dual<S> {
+    const ZERO: Self = Dual {
+        value: S::ZERO,
+        derivative: S::ZERO,
+    };
+    const ONE: Self = Dual {
+        value: S::ONE,
+        derivative: S::ZERO,
+    };
+
+    fn from_f64(v: f64) -> Self {
+        Dual::constant(S::from_f64(v))
+    }
+
+    fn value(self) -> f64 {
+        self.value.value()
+    }
+
+    /// The derivative isn't defined at zero, where it is set to zero, so the length of a vector
+    /// that is clamped to zero (like in `max(p, 0).length()`) doesn't change
+    fn sqrt(self) -> Self {
+        let sqrt = self.value.sqrt();
+        if self.value == S::ZERO {
+            Dual::constant(sqrt)
+        } else {
+            Dual::new(sqrt, self.derivative / (sqrt * 2.0))
+        }
+    }
+
+    fn sin(self) -> Self {
+        Dual::new(self.value.sin(), self.derivative * self.value.cos())
+    }
+
+    fn cos(self) -> Self {
+        Dual::new(self.value.cos(), -self.derivative * self.value.sin())
+    }
+
+    fn exp2(self) -> Self {
+        let exp2 = self.value.exp2();
+        Dual::new(exp2, self.derivative * exp2 * std::f64::consts::LN_2)
+    }
+
+    fn log2(self) -> Self {
+        Dual::new(
+            self.value.log2(),
+            self.derivative / (self.value * std::f64::consts::LN_2),
+        )
+    }
+
+    fn signum(self) -> Self {
+        Dual::constant(self.value.signum())
+    }
+}
+
+/// The distance and the exact gradient of `sdf` at `p`, computed with dual numbers
+pub fn gradient<T: SignedDistanceField<Dual> + ?Sized>(sdf: &T, p: Vec2) -> (f64, Vec2) {
+    let dx = sdf.call(Vec2::new(Dual::variable(p.x), Dual::constant(p.y)));
+    let dy = sdf.call(Vec2::new(Dual::constant(p.x), Dual::variable(p.y)));
+
+    (dx.value, Vec2::new(dx.derivative, dy.derivative))
+}
+
+/// The distance and the exact gradient of the 3D `sdf` at `p`, computed with dual numbers
+pub fn gradient3<T: SignedDistanceField3<Dual> + ?Sized>(sdf: &T, p: Vec3) -> (f64, Vec3) {
+    let seeded = |axis: usize| {
+        let seed = |a: usize, v: f64| {
+            if a == axis {
+                Dual::variable(v)
+            } else {
+                Dual::constant(v)
+            }
+        };
+        sdf.call(Vec3::new(seed(0, p.x), seed(1, p.y), seed(2, p.z)))
+    };
+    let [dx, dy, dz] = [0, 1, 2].map(seeded);
+
+    (
+        dx.value,
+        Vec3::new(dx.derivative, dy.derivative, dz.derivative),
+    )
+}
+
+/// The matrix of second derivatives of `sdf` at `p`, computed with dual numbers of dual numbers
+///
+/// Entry `[i][j]` is the derivative along axis `i` of the derivative along axis `j`.
+pub fn hessian<T: SignedDistanceField<Dual<Dual>> + ?Sized>(sdf: &T, p: Vec2) -> [[f64; 2]; 2] {
+    let seeded = |v: f64, inner: bool, outer: bool| {
+        Dual::new(
+            Dual::new(v, if inner { 1.0 } else { 0.0 }),
+            Dual::constant(if outer { 1.0 } else { 0.0 }),
+        )
+    };
+    let second = |i: usize, j: usize| {
+        let q = Vec2::new(seeded(p.x, j == 0, i == 0), seeded(p.y, j == 1, i == 1));
+        sdf.call(q).derivative.derivative
+    };
+
+    [[second(0, 0), second(0, 1)], [second(1, 0), second(1, 1)]]
+}
@@ -0,0 +1,438 @@
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+use crate::scalar::Scalar;
+
+/// A 2D vector, used for points and directions in the plane
+///
+/// Arrays, tuples and `math_vector::Vector`s convert into it:
+/// ```
+/// use signed_distance_fields::Vec2;
+///
+/// let a: Vec2 = [1.0, 2.0].into();
+/// let b: Vec2 = (1.0, 2.0).into();
+/// let c: Vec2 = math_vector::Vector::new(1.0, 2.0, 0.0).into();
+///
+/// assert_eq!(a, b);
+/// assert_eq!(b, c);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vec2<T = f64> {
+    /// x component
+    pub x: T,
+    /// y component
+    pub y: T,
+}
+
+/// A 3D vector, used for points and directions in space
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vec3<T = f64> {
+    /// x component
+    pub x: T,
+    /// y component
+    pub y: T,
+    /// z component
+    pub z: T,
+}
+
+impl<T> Vec2<T> {
+    /// Create a new vector
+    pub const fn new(x: T, y: T) -> Self {
+        Vec2 { x, y }
+    }
+
+    /// Extend the vector by a z component
+    pub fn extend(self, z: T) -> Vec3<T> {
+        Vec3 {
+            x: self.x,
+            y: self.y,
+            z,
+        }
+    }
+}
+
+impl<T: Copy> Vec2<T> {
+    /// Create a vector with both components set to `v`
+    pub const fn splat(v: T) -> Self {
+        Vec2 { x: v, y: v }
+    }
+}
+
+impl Vec2 {
+    /// The zero vector
+    pub const ZERO: Vec2 = Vec2 { x: 0.0, y: 0.0 };
+
+    /// Convert the components to another [`Scalar`], as constants
+    pub fn cast<S: Scalar>(self) -> Vec2<S> {
+        Vec2::new(S::from_f64(self.x), S::from_f64(self.y))
+    }
+}
+
+impl<S: Scalar> Vec2<S> {
+    /// Dot product
+    pub fn dot(self, other: Vec2<S>) -> S {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// z component of the cross product of the vectors extended to 3D
+    pub fn cross(self, other: Vec2<S>) -> S {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Squared euclidean length
+    pub fn length_squared(self) -> S {
+        self.dot(self)
+    }
+
+    /// Euclidean length
+    pub fn length(self) -> S {
+        self.x.hypot(self.y)
+    }
+
+    /// Euclidean distance to `other`
+    pub fn distance(self, other: Vec2<S>) -> S {
+        (self - other).length()
+    }
+
+    /// The vector scaled to a length of 1
+    pub fn normalize(self) -> Vec2<S> {
+        self / self.length()
+    }
+
+    /// Component wise absolute value
+    pub fn abs(self) -> Vec2<S> {
+        Vec2::new(self.x.abs(), self.y.abs())
+    }
+
+    /// Component wise maximum
+    pub fn max(self, other: Vec2<S>) -> Vec2<S> {
+        Vec2::new(self.x.max(other.x), self.y.max(other.y))
+    }
+
+    /// Component wise minimum
+    pub fn min(self, other: Vec2<S>) -> Vec2<S> {
+        Vec2::new(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// The largest component
+    pub fn max_element(self) -> S {
+        self.x.max(self.y)
+    }
+
+    /// The smallest component
+    pub fn min_element(self) -> S {
+        self.x.min(self.y)
+    }
+
+    /// The vector rotated counter clockwise by 90°
+    pub fn perp(self) -> Vec2<S> {
+        Vec2::new(-self.y, self.x)
+    }
+
+    /// The vector rotated counter clockwise by `alpha` (in radians)
+    pub fn rotate(self, alpha: f64) -> Vec2<S> {
+        let (sin, cos) = alpha.sin_cos();
+        Vec2::new(self.x * cos - self.y * sin, self.x * sin + self.y * cos)
+    }
+
+    /// Component wise product
+    pub fn mul_components(self, other: Vec2<S>) -> Vec2<S> {
+        Vec2::new(self.x * other.x, self.y * other.y)
+    }
+
+    /// Component wise quotient
+    pub fn div_components(self, other: Vec2<S>) -> Vec2<S> {
+        Vec2::new(self.x / other.x, self.y / other.y)
+    }
+}
+
+impl<T> Vec3<T> {
+    /// Create a new vector
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    /// Drop the z component
+    pub fn truncate(self) -> Vec2<T> {
+        Vec2 {
+            x: self.x,
+            y: self.y,
+        }
+    }
+}
+
+impl<T: Copy> Vec3<T> {
+    /// Create a vector with all components set to `v`
+    pub const fn splat(v: T) -> Self {
+        Vec3 { x: v, y: v, z: v }
+    }
+}
+
+impl Vec3 {
+    /// The zero vector
+    pub const ZERO: Vec3 = Vec3 {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    /// Convert the components to another [`Scalar`], as constants
+    pub fn cast<S: Scalar>(self) -> Vec3<S> {
+        Vec3::new(
+            S::from_f64(self.x),
+            S::from_f64(self.y),
+            S::from_f64(self.z),
+        )
+    }
+}
+
+impl<S: Scalar> Vec3<S> {
+    /// Dot product
+    pub fn dot(self, other: Vec3<S>) -> S {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Cross product
+    pub fn cross(self, other: Vec3<S>) -> Vec3<S> {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    /// Squared euclidean length
+    pub fn length_squared(self) -> S {
+        self.dot(self)
+    }
+
+    /// Euclidean length
+    pub fn length(self) -> S {
+        self.length_squared().sqrt()
+    }
+
+    /// Euclidean distance to `other`
+    pub fn distance(self, other: Vec3<S>) -> S {
+        (self - other).length()
+    }
+
+    /// The vector scaled to a length of 1
+    pub fn normalize(self) -> Vec3<S> {
+        self / self.length()
+    }
+
+    /// Component wise absolute value
+    pub fn abs(self) -> Vec3<S> {
+        Vec3::new(self.x.abs(), self.y.abs(), self.z.abs())
+    }
+
+    /// Component wise maximum
+    pub fn max(self, other: Vec3<S>) -> Vec3<S> {
+        Vec3::new(
+            self.x.max(other.x),
+            self.y.max(other.y),
+            self.z.max(other.z),
+        )
+    }
+
+    /// Component wise minimum
+    pub fn min(self, other: Vec3<S>) -> Vec3<S> {
+        Vec3::new(
+            self.x.min(other.x),
+            self.y.min(other.y),
+            self.z.min(other.z),
+        )
+    }
+
+    /// The largest component
+    pub fn max_element(self) -> S {
+        self.x.max(self.y).max(self.z)
+    }
+
+    /// The smallest component
+    pub fn min_element(self) -> S {
+        self.x.min(self.y).min(self.z)
+    }
+
+    /// The vector rotated counter clockwise by `alpha` (in radians) around `axis`, which has to
+    /// have a length of 1
+    pub fn rotate(self, alpha: f64, axis: Vec3) -> Vec3<S> {
+        let (sin, cos) = alpha.sin_cos();
+        let axis: Vec3<S> = axis.cast();
+
+        self * S::from_f64(cos)
+            + axis.cross(self) * S::from_f64(sin)
+            + axis * axis.dot(self) * S::from_f64(1.0 - cos)
+    }
+
+    /// Component wise product
+    pub fn mul_components(self, other: Vec3<S>) -> Vec3<S> {
+        Vec3::new(self.x * other.x, self.y * other.y, self.z * other.z)
+    }
+
+    /// Component wise quotient
+    pub fn div_components(self, other: Vec3<S>) -> Vec3<S> {
+        Vec3::new(self.x / other.x, self.y / other.y, self.z / other.z)
+    }
+}
+
+/// Implements the component wise arithmetic operators for a vector type
+macro_rules! impl_ops {
+    ($vec:ident { $($c:ident),+ }) => {
+        impl<T: Add<Output = T>> Add for $vec<T> {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self {
+                $vec { $($c: self.$c + rhs.$c),+ }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $vec<T> {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                $vec { $($c: self.$c - rhs.$c),+ }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $vec<T> {
+            type Output = Self;
+
+            fn mul(self, rhs: T) -> Self {
+                $vec { $($c: self.$c * rhs),+ }
+            }
+        }
+
+        impl<T: Copy + Div<Output = T>> Div<T> for $vec<T> {
+            type Output = Self;
+
+            fn div(self, rhs: T) -> Self {
+                $vec { $($c: self.$c / rhs),+ }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $vec<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self {
+                $vec { $($c: -self.$c),+ }
+            }
+        }
+
+        impl<T: Copy + Add<Output = T>> AddAssign for $vec<T> {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<T: Copy + Sub<Output = T>> SubAssign for $vec<T> {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+    };
+}
+
+impl_ops!(Vec2 { x, y });
+impl_ops!(Vec3 { x, y, z });
+
+impl Mul<Vec2> for f64 {
+    type Output = Vec2;
+
+    fn mul(self, rhs: Vec2) -> Vec2 {
+        rhs * self
+    }
+}
+
+impl Mul<Vec3> for f64 {
+    type Output = Vec3;
+
+    fn mul(self, rhs: Vec3) -> Vec3 {
+        rhs * self
+    }
+}
+
+impl<T> From<[T; 2]> for Vec2<T> {
+    fn from([x, y]: [T; 2]) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T> From<(T, T)> for Vec2<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Vec2 { x, y }
+    }
+}
+
+impl<T> From<Vec2<T>> for [T; 2] {
+    fn from(v: Vec2<T>) -> Self {
+        [v.x, v.y]
+    }
+}
+
+impl<T> From<Vec2<T>> for (T, T) {
+    fn from(v: Vec2<T>) -> Self {
+        (v.x, v.y)
+    }
+}
+
+/// The z component is dropped
+impl<T> From<math_vector::Vector<T>> for Vec2<T> {
+    fn from(v: math_vector::Vector<T>) -> Self {
+        Vec2 { x: v.x, y: v.y }
+    }
+}
+
+/// The z component is set to zero
+impl<T: Default> From<Vec2<T>> for math_vector::Vector<T> {
+    fn from(v: Vec2<T>) -> Self {
+        math_vector::Vector {
+            x: v.x,
+            y: v.y,
+            z: T::default(),
+        }
+    }
+}
+
+impl<T> From<[T; 3]> for Vec3<T> {
+    fn from([x, y, z]: [T; 3]) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T> From<(T, T, T)> for Vec3<T> {
+    fn from((x, y, z): (T, T, T)) -> Self {
+        Vec3 { x, y, z }
+    }
+}
+
+impl<T> From<Vec3<T>> for [T; 3] {
+    fn from(v: Vec3<T>) -> Self {
+        [v.x, v.y, v.z]
+    }
+}
+
+impl<T> From<Vec3<T>> for (T, T, T) {
+    fn from(v: Vec3<T>) -> Self {
+        (v.x, v.y, v.z)
+    }
+}
+
+impl<T> From<math_vector::Vector<T>> for Vec3<T> {
+    fn from(v: math_vector::Vector<T>) -> Self {
+        Vec3 {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
+
+impl<T> From<Vec3<T>> for math_vector::Vector<T> {
+    fn from(v: Vec3<T>) -> Self {
+        math_vector::Vector {
+            x: v.x,
+            y: v.y,
+            z: v.z,
+        }
+    }
+}
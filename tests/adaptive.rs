@@ -0,0 +1,98 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use signed_distance_fields::{
+    objects::{
+        primitives::{Circle, Rectangle},
+        SignedDistanceField, SignedDistanceFieldExt, F,
+    },
+    render::{
+        self,
+        adaptive::{self, Fill},
+        color_mappers,
+    },
+    Domain, Vec2,
+};
+
+fn scene() -> impl SignedDistanceField + Clone {
+    (Circle { r: 6.0 } - Rectangle { w: 4.0, h: 20.0 }).translate(Vec2::new(1.0, -2.0))
+        | Circle { r: 1.5 }.translate(Vec2::new(-9.0, 8.0))
+}
+
+fn domain(steps: usize) -> Domain {
+    Domain {
+        p0: Vec2::new(-12.0, -12.0),
+        p1: Vec2::new(12.0, 12.0),
+        steps: Vec2::new(steps, steps),
+    }
+}
+
+#[test]
+fn adaptive_render_matches_the_uniform_one_near_the_surface() {
+    let domain = domain(157);
+    let cell = 24.0 / 156.0;
+    let exact = render::matrix(&scene(), &domain);
+
+    for fill in [Fill::Interpolate, Fill::Conservative] {
+        let adaptive = adaptive::matrix(&scene(), &domain, fill);
+        assert_eq!(adaptive.domain(), exact.domain());
+
+        for (&value, &expected) in adaptive.values().iter().zip(exact.values()) {
+            assert_eq!(value.signum(), expected.signum());
+            if expected.abs() <= 2.0 * cell {
+                assert_eq!(value, expected);
+            }
+            if fill == Fill::Conservative {
+                assert!(value.abs() <= expected.abs() + 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn adaptive_images_are_the_same() {
+    let domain = domain(120);
+    let mapper = &color_mappers::inside_black_outside_white;
+
+    assert_eq!(
+        adaptive::image(&scene(), &domain, Fill::Interpolate, mapper),
+        render::image(&scene(), &domain, mapper)
+    );
+}
+
+#[test]
+fn most_samples_are_skipped() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let sdf = scene();
+    let counted: F = F {
+        f: Arc::new(move |p: Vec2| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            sdf.call(p)
+        }),
+    };
+
+    adaptive::matrix(&counted, &domain(512), Fill::Interpolate);
+    assert!(calls.load(Ordering::Relaxed) < 512 * 512 / 4);
+}
+
+#[test]
+fn degenerate_domains() {
+    for steps in [
+        Vec2::new(1, 1),
+        Vec2::new(1, 40),
+        Vec2::new(40, 1),
+        Vec2::new(0, 5),
+    ] {
+        let domain = Domain { steps, ..domain(0) };
+        let exact = render::matrix(&scene(), &domain);
+        let adaptive = adaptive::matrix(&scene(), &domain, Fill::Interpolate);
+
+        assert_eq!(adaptive.values().len(), exact.values().len());
+        for (value, expected) in adaptive.values().iter().zip(exact.values()) {
+            assert_eq!(value.signum(), expected.signum());
+        }
+    }
+}
@@ -0,0 +1,121 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::points;
+use signed_distance_fields::{
+    objects::{
+        operators::boolean::{Blend, SmoothUnion},
+        primitives::{Circle, Line, Rectangle},
+        primitives3::{Cuboid, Sphere, Torus},
+        SignedDistanceField, SignedDistanceField3Ext, SignedDistanceFieldExt, F, F3,
+    },
+    scalar::{self, Dual, Scalar},
+    Vec2, Vec3,
+};
+
+/// Dual numbers have to give the same distance as `f64`s and the same gradient as the analytic
+/// implementations
+fn assert_matches_analytic<T: SignedDistanceField + SignedDistanceField<Dual>>(sdf: &T) {
+    for p in points(8, 1.0) {
+        let (distance, gradient) = scalar::gradient(sdf, p);
+        let (expected_distance, expected_gradient) =
+            SignedDistanceField::<f64>::call_with_gradient(sdf, p);
+
+        assert!((distance - expected_distance).abs() < 1e-12, "{p:?}");
+        assert!(
+            (gradient - expected_gradient).length() < 1e-9,
+            "{p:?}: {gradient:?} vs {expected_gradient:?}"
+        );
+    }
+}
+
+#[test]
+fn dual_numbers_agree_with_analytic_gradients() {
+    let a = Circle { r: 4.0 };
+    let b = Rectangle { w: 6.0, h: 3.0 }.translate(Vec2::new(2.0, 1.0));
+
+    assert_matches_analytic(&a);
+    assert_matches_analytic(&b);
+    assert_matches_analytic(&Line { l: 5.0 }.rotate(0.4));
+    assert_matches_analytic(&(a | b.clone()));
+    assert_matches_analytic(&(a - b.clone()).scale(1.5));
+    assert_matches_analytic(&b.clone().matrix([[1.0, 0.5], [0.2, 1.5]]));
+    assert_matches_analytic(&SmoothUnion {
+        a,
+        b,
+        k: 2.0,
+        blend: Blend::Exponential,
+    });
+}
+
+#[test]
+fn closures_get_exact_gradients() {
+    let f: F<Dual> = F {
+        f: Arc::new(|p: Vec2<Dual>| p.x * p.x + p.y * 3.0 - p.y.sin()),
+    };
+    let p = Vec2::new(2.0, 5.0);
+
+    assert_eq!(f.call(p), 4.0 + 15.0 - 5.0f64.sin());
+    assert_eq!(f.gradient(p), Vec2::new(4.0, 3.0 - 5.0f64.cos()));
+
+    // The closure composes with every operator, which then differentiates it exactly as well
+    let scene = (f.clone() & Circle { r: 1.0 }).translate(Vec2::new(1.0, 0.0));
+    let (_, gradient) = scalar::gradient(&scene, Vec2::new(3.0, 5.0));
+    assert_eq!(gradient, Vec2::new(4.0, 3.0 - 5.0f64.cos()));
+}
+
+#[test]
+fn nested_dual_numbers_give_hessians() {
+    // The Hessian of a circle's distance is the curvature across the radius
+    let p = Vec2::new(3.0, 4.0);
+    let hessian = scalar::hessian(&Circle { r: 1.0 }, p);
+    let expected = [[16.0 / 125.0, -12.0 / 125.0], [-12.0 / 125.0, 9.0 / 125.0]];
+
+    for i in 0..2 {
+        for j in 0..2 {
+            assert!((hessian[i][j] - expected[i][j]).abs() < 1e-12);
+        }
+    }
+}
+
+#[test]
+fn gradients_of_3d_sdfs() {
+    let sphere = Sphere { r: 2.0 };
+    let (distance, gradient) = scalar::gradient3(&sphere, Vec3::new(0.0, 3.0, 4.0));
+    assert_eq!(distance, 3.0);
+    assert!((gradient - Vec3::new(0.0, 0.6, 0.8)).length() < 1e-12);
+
+    let cuboid = Cuboid {
+        w: 2.0,
+        h: 2.0,
+        d: 2.0,
+    }
+    .translate(Vec3::new(0.0, 0.0, 5.0));
+    let (_, gradient) = scalar::gradient3(&cuboid, Vec3::new(0.2, -0.3, 7.0));
+    assert_eq!(gradient, Vec3::new(0.0, 0.0, 1.0));
+
+    let f: F3<Dual> = F3 {
+        f: Arc::new(|p: Vec3<Dual>| p.x * p.y + p.z.exp2()),
+    };
+    let (_, gradient) = scalar::gradient3(
+        &(f & Torus {
+            major: 3.0,
+            minor: 1.0,
+        }),
+        Vec3::new(2.0, 3.0, 1.0),
+    );
+    assert!((gradient - Vec3::new(3.0, 2.0, 2.0 * std::f64::consts::LN_2)).length() < 1e-12);
+}
+
+#[test]
+fn dual_numbers_compare_by_value() {
+    let a = Dual::new(1.0, 5.0);
+    let b = Dual::new(1.0, -5.0);
+
+    assert_eq!(a, b);
+    assert!(Dual::variable(2.0) > 1.0);
+    assert_eq!(a.max(Dual::constant(3.0)).derivative, 0.0);
+    assert_eq!((a * b).derivative, 0.0);
+    assert_eq!(Dual::variable(0.0).sqrt().derivative, 0.0);
+}
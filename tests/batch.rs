@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use signed_distance_fields::{
+    objects::{
+        operators::boolean::{Blend, SmoothDifference},
+        primitives::{Circle, Line, Plane, Rectangle, Straight},
+        SharedSignedDistanceField, SignedDistanceField, SignedDistanceFieldExt, BATCH_SIZE, F,
+    },
+    render,
+    scalar::Dual,
+    Domain, Vec2,
+};
+
+/// Points on a grid, with some of them exactly on the axes
+fn points(n: usize) -> Vec<Vec2> {
+    (0..n)
+        .map(|i| Vec2::new((i % 17) as f64 * 0.9 - 7.2, (i / 17) as f64 * 1.3 - 5.2))
+        .collect()
+}
+
+/// Batches have to give exactly the distances of `call`, no matter how long they are
+fn assert_matches_call(sdf: &dyn SignedDistanceField) {
+    for n in [0, 1, BATCH_SIZE - 1, BATCH_SIZE, BATCH_SIZE + 1, 300] {
+        let points = points(n);
+        let mut out = vec![f64::NAN; n];
+        sdf.call_batch(&points, &mut out);
+
+        for (p, distance) in points.iter().zip(out) {
+            assert_eq!(distance.to_bits(), sdf.call(*p).to_bits(), "{p:?}");
+        }
+    }
+}
+
+#[test]
+fn primitives_match_call() {
+    assert_matches_call(&Circle { r: 3.0 });
+    assert_matches_call(&Rectangle { w: 6.0, h: 3.0 });
+    assert_matches_call(&Line { l: 5.0 });
+    assert_matches_call(&Straight {});
+    assert_matches_call(&Plane {});
+    assert_matches_call(&F {
+        f: Arc::new(|p: Vec2| p.x * p.y),
+    });
+}
+
+#[test]
+fn operators_match_call() {
+    let a = || Circle { r: 4.0 };
+    let b = || Rectangle { w: 6.0, h: 3.0 }.translate(Vec2::new(2.0, 1.0));
+
+    assert_matches_call(&(a() | b()));
+    assert_matches_call(&(a() & b()));
+    assert_matches_call(&(a() - b()));
+    assert_matches_call(&(a() ^ b()));
+    assert_matches_call(&!a());
+    assert_matches_call(&b().round(1.0));
+    assert_matches_call(&b().rotate(0.7));
+    assert_matches_call(&b().scale(0.5));
+    assert_matches_call(&b().matrix([[1.0, 0.5], [0.2, 1.5]]));
+    assert_matches_call(&b().affine([[0.0, -2.0], [2.0, 0.0]], Vec2::new(1.0, 1.0)));
+    assert_matches_call(&a().smooth_union(b(), 1.5).rotate(0.2));
+    assert_matches_call(&a().smooth_intersection(b(), 1.5));
+    assert_matches_call(&SmoothDifference {
+        a: a(),
+        b: b(),
+        k: 2.0,
+        blend: Blend::Exponential,
+    });
+
+    let shared: SharedSignedDistanceField = Arc::new(a() - b());
+    assert_matches_call(&shared.scale(2.0));
+}
+
+#[test]
+fn dual_numbers_are_batched_too() {
+    let sdf = (Circle { r: 4.0 } - Line { l: 3.0 }).rotate(0.3);
+    let points: Vec<Vec2<Dual>> = points(100)
+        .into_iter()
+        .map(|p| Vec2::new(Dual::variable(p.x), Dual::constant(p.y)))
+        .collect();
+    let mut out = vec![Dual::default(); points.len()];
+    sdf.call_batch(&points, &mut out);
+
+    for (p, distance) in points.iter().zip(out) {
+        let expected = sdf.call(*p);
+        assert_eq!(distance.value, expected.value);
+        assert_eq!(distance.derivative, expected.derivative);
+    }
+}
+
+#[test]
+#[should_panic]
+fn lengths_have_to_match() {
+    Circle { r: 1.0 }.call_batch(&points(10), &mut [0.0; 9]);
+}
+
+#[test]
+fn render_uses_the_batch_path() {
+    let sdf = Circle { r: 4.0 } - Rectangle { w: 2.0, h: 9.0 };
+    let domain = Domain {
+        p0: Vec2::new(-6.0, -6.0),
+        p1: Vec2::new(6.0, 6.0),
+        steps: Vec2::new(37, 129),
+    };
+    let field = render::matrix(&sdf, &domain);
+
+    assert_eq!(field.width(), 37);
+    assert_eq!(field[(18, 64)], sdf.call(Vec2::new(0.0, 0.0)));
+    assert_eq!(field[(0, 128)], sdf.call(Vec2::new(-6.0, 6.0)));
+}
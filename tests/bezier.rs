@@ -0,0 +1,241 @@
+mod common;
+
+use common::{points, v};
+use signed_distance_fields::{
+    objects::{
+        primitives::{
+            BezierSegment, BezierSpline, Circle, CubicBezier, Line, QuadraticBezier, Rectangle,
+        },
+        SignedDistanceField,
+    },
+    scalar, Vec2,
+};
+
+/// The distance to the closest of many points along the curve `f`, refined around the closest one
+fn brute_force(f: impl Fn(f64) -> Vec2, p: Vec2) -> f64 {
+    let n = 2000;
+    let distance = |t: f64| (f(t) - p).length();
+    let closest = (0..=n)
+        .min_by(|&i, &j| distance(i as f64 / n as f64).total_cmp(&distance(j as f64 / n as f64)))
+        .unwrap();
+
+    let mut a = (closest.max(1) - 1) as f64 / n as f64;
+    let mut b = (closest.min(n - 1) + 1) as f64 / n as f64;
+    for _ in 0..100 {
+        let (c, d) = (a + (b - a) / 3.0, b - (b - a) / 3.0);
+        if distance(c) < distance(d) {
+            b = d;
+        } else {
+            a = c;
+        }
+    }
+
+    distance(0.5 * (a + b))
+}
+
+fn quadratic(curve: &QuadraticBezier, t: f64) -> Vec2 {
+    let s = 1.0 - t;
+
+    curve.p0 * (s * s) + curve.p1 * (2.0 * s * t) + curve.p2 * (t * t)
+}
+
+fn cubic(curve: &CubicBezier, t: f64) -> Vec2 {
+    let s = 1.0 - t;
+
+    curve.p0 * (s * s * s)
+        + curve.p1 * (3.0 * s * s * t)
+        + curve.p2 * (3.0 * s * t * t)
+        + curve.p3 * (t * t * t)
+}
+
+/// A cubic curve for every quarter of a circle with the radius `r`
+fn circle(r: f64) -> Vec<BezierSegment> {
+    let k = 0.5522847498 * r;
+    let quarter = |(x, y): (f64, f64)| {
+        BezierSegment::Cubic(CubicBezier {
+            p0: v(r * x, r * y),
+            p1: v(r * x - k * y, r * y + k * x),
+            p2: v(r * -y + k * x, r * x + k * y),
+            p3: v(r * -y, r * x),
+        })
+    };
+
+    [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0), (0.0, -1.0)]
+        .map(quarter)
+        .to_vec()
+}
+
+#[test]
+fn quadratic_curves_match_sampling() {
+    let curves = [
+        QuadraticBezier {
+            p0: v(-4.0, -2.0),
+            p1: v(0.0, 6.0),
+            p2: v(4.0, -2.0),
+        },
+        QuadraticBezier {
+            p0: v(-3.0, 1.0),
+            p1: v(4.0, 3.0),
+            p2: v(-2.0, -4.0),
+        },
+    ];
+
+    for curve in curves {
+        for p in points(12, 0.4) {
+            let expected = brute_force(|t| quadratic(&curve, t), p);
+            let distance = curve.call(p);
+            assert!(distance <= expected + 1e-12, "{p:?}");
+            assert!(
+                expected - distance < 1e-9,
+                "{p:?}: {distance} vs {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn cubic_curves_match_sampling() {
+    // An S, a loop and a cusp
+    let curves = [
+        CubicBezier {
+            p0: v(-4.0, -3.0),
+            p1: v(8.0, -3.0),
+            p2: v(-8.0, 3.0),
+            p3: v(4.0, 3.0),
+        },
+        CubicBezier {
+            p0: v(-3.0, -3.0),
+            p1: v(5.0, 4.0),
+            p2: v(-5.0, 4.0),
+            p3: v(3.0, -3.0),
+        },
+        CubicBezier {
+            p0: v(-4.0, 0.0),
+            p1: v(4.0, 4.0),
+            p2: v(-4.0, 4.0),
+            p3: v(4.0, 0.0),
+        },
+    ];
+
+    for curve in curves {
+        for p in points(12, 0.4) {
+            let expected = brute_force(|t| cubic(&curve, t), p);
+            let distance = curve.call(p);
+            assert!(distance <= expected + 1e-12, "{p:?}");
+            assert!(
+                expected - distance < 1e-9,
+                "{p:?}: {distance} vs {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn straight_curves_are_lines() {
+    let line = Line { l: 6.0 };
+    let quadratic = QuadraticBezier {
+        p0: v(-3.0, 0.0),
+        p1: v(0.0, 0.0),
+        p2: v(3.0, 0.0),
+    };
+    let cubic = CubicBezier {
+        p0: v(-3.0, 0.0),
+        p1: v(-1.0, 0.0),
+        p2: v(1.0, 0.0),
+        p3: v(3.0, 0.0),
+    };
+
+    for p in points(12, 0.4) {
+        assert!((quadratic.call(p) - line.call(p)).abs() < 1e-12, "{p:?}");
+        assert!((cubic.call(p) - line.call(p)).abs() < 1e-12, "{p:?}");
+    }
+}
+
+#[test]
+fn linear_splines_are_polygons() {
+    let corners = [v(-2.0, -1.0), v(2.0, -1.0), v(2.0, 1.0), v(-2.0, 1.0)];
+    let rectangle = Rectangle { w: 4.0, h: 2.0 };
+
+    for clockwise in [false, true] {
+        let mut segments: Vec<BezierSegment> = (0..4)
+            .map(|i| BezierSegment::Linear([corners[i], corners[(i + 1) % 4]]))
+            .collect();
+        if clockwise {
+            segments = segments
+                .iter()
+                .rev()
+                .map(|segment| BezierSegment::Linear([segment.end(), segment.start()]))
+                .collect();
+        }
+
+        let spline = BezierSpline::new(segments);
+        for p in points(12, 0.4) {
+            assert!((spline.call(p) - rectangle.call(p)).abs() < 1e-12, "{p:?}");
+        }
+        // Rays through the corners
+        for p in [v(-3.0, 1.0), v(0.0, 1.0), v(0.0, -1.0), v(3.0, -1.0)] {
+            assert_eq!(spline.call(p), rectangle.call(p), "{p:?}");
+        }
+    }
+}
+
+#[test]
+fn splines_are_signed_by_winding() {
+    let r = 4.0;
+    let spline = BezierSpline::new(circle(r));
+    let round = Circle { r };
+    for p in points(12, 0.4) {
+        assert!((spline.call(p) - round.call(p)).abs() < 3e-4 * r, "{p:?}");
+    }
+
+    // A ring, whose hole runs the other way around
+    let hole = circle(2.0).into_iter().rev().map(|segment| match segment {
+        BezierSegment::Cubic(curve) => BezierSegment::Cubic(CubicBezier {
+            p0: curve.p3,
+            p1: curve.p2,
+            p2: curve.p1,
+            p3: curve.p0,
+        }),
+        segment => segment,
+    });
+    let ring = BezierSpline::new(circle(r).into_iter().chain(hole).collect());
+    for p in points(12, 0.4) {
+        let expected = (p.length() - 3.0).abs() - 1.0;
+        assert!((ring.call(p) - expected).abs() < 3e-4 * r, "{p:?}");
+    }
+    // Rays through the ends of the curves
+    assert!(ring.call(v(0.0, 3.0)) < 0.0);
+    assert!(ring.call(v(-3.0, 0.0)) < 0.0);
+    assert!(ring.call(v(0.0, 0.0)) > 0.0);
+}
+
+fn assert_exact_gradients<T: SignedDistanceField + SignedDistanceField<scalar::Dual>>(sdf: &T) {
+    for p in points(12, 0.4) {
+        let (value, gradient) = sdf.call_with_gradient(p);
+        let (dual_value, dual_gradient) = scalar::gradient(sdf, p);
+        assert!((value - dual_value).abs() < 1e-12, "{p:?}");
+        assert!((gradient - dual_gradient).length() < 1e-9, "{p:?}");
+        assert!((gradient.length() - 1.0).abs() < 1e-9, "{p:?}");
+    }
+}
+
+#[test]
+fn gradients_are_exact() {
+    let curve = CubicBezier {
+        p0: v(-4.0, -3.0),
+        p1: v(8.0, -3.0),
+        p2: v(-8.0, 3.0),
+        p3: v(4.0, 3.0),
+    };
+    let spline = BezierSpline::new(circle(3.0));
+
+    assert_exact_gradients(&curve);
+    assert_exact_gradients(&spline);
+}
+
+#[test]
+fn empty_splines_are_far_away() {
+    let spline = BezierSpline::new(Vec::new());
+
+    assert_eq!(spline.call(v(1.0, 2.0)), f64::INFINITY);
+}
@@ -0,0 +1,170 @@
+use signed_distance_fields::objects::{
+    operators::{
+        boolean::{
+            Blend, Complement, Difference, Intersection, SmoothDifference, SmoothIntersection,
+            SmoothUnion, Union, Xor,
+        },
+        transforms::Translate,
+    },
+    primitives::Circle,
+    SignedDistanceField,
+};
+use signed_distance_fields::Vec2;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Side {
+    Inside,
+    Boundary,
+    Outside,
+}
+use Side::*;
+
+fn side(d: f64) -> Side {
+    if d.abs() < 1e-9 {
+        Boundary
+    } else if d < 0.0 {
+        Inside
+    } else {
+        Outside
+    }
+}
+
+fn a() -> Circle {
+    Circle { r: 2.0 }
+}
+
+fn b() -> Translate<Circle> {
+    Translate {
+        p: Vec2::new(2.0, 0.0),
+        sdf: Circle { r: 2.0 },
+    }
+}
+
+/// Sample points together with their side relative to `a` and `b`
+const POINTS: [(f64, f64); 7] = [
+    (-1.0, 0.0), // only inside of a
+    (1.0, 0.0),  // inside of both
+    (3.0, 0.0),  // only inside of b
+    (0.0, 5.0),  // outside of both
+    (-2.0, 0.0), // on the boundary of a, outside of b
+    (4.0, 0.0),  // on the boundary of b, outside of a
+    (0.0, 0.0),  // on the boundary of b, inside of a
+];
+
+fn assert_sides(sdf: &dyn SignedDistanceField, expected: [Side; 7]) {
+    for ((x, y), expected) in POINTS.into_iter().zip(expected) {
+        let d = sdf.call(Vec2::new(x, y));
+        assert_eq!(side(d), expected, "at ({x}, {y}) the distance is {d}");
+    }
+}
+
+#[test]
+fn operands() {
+    assert_sides(
+        &a(),
+        [Inside, Inside, Outside, Outside, Boundary, Outside, Inside],
+    );
+    assert_sides(
+        &b(),
+        [
+            Outside, Inside, Inside, Outside, Outside, Boundary, Boundary,
+        ],
+    );
+}
+
+#[test]
+fn union() {
+    assert_sides(
+        &Union { a: a(), b: b() },
+        [Inside, Inside, Inside, Outside, Boundary, Boundary, Inside],
+    );
+}
+
+#[test]
+fn intersection() {
+    assert_sides(
+        &Intersection { a: a(), b: b() },
+        [
+            Outside, Inside, Outside, Outside, Outside, Outside, Boundary,
+        ],
+    );
+}
+
+#[test]
+fn difference() {
+    assert_sides(
+        &Difference { a: a(), b: b() },
+        [
+            Inside, Outside, Outside, Outside, Boundary, Outside, Boundary,
+        ],
+    );
+    assert_sides(
+        &Difference { a: b(), b: a() },
+        [
+            Outside, Outside, Inside, Outside, Outside, Boundary, Outside,
+        ],
+    );
+}
+
+#[test]
+fn xor() {
+    assert_sides(
+        &Xor { a: a(), b: b() },
+        [
+            Inside, Outside, Inside, Outside, Boundary, Boundary, Boundary,
+        ],
+    );
+}
+
+#[test]
+fn complement() {
+    assert_sides(
+        &Complement { sdf: a() },
+        [Outside, Outside, Inside, Inside, Boundary, Inside, Outside],
+    );
+}
+
+#[test]
+fn smooth_operators_agree_away_from_the_seam() {
+    let k = 0.1;
+
+    for blend in [
+        Blend::Polynomial,
+        Blend::Cubic,
+        Blend::Exponential,
+        Blend::Root,
+    ] {
+        let union = SmoothUnion {
+            a: a(),
+            b: b(),
+            k,
+            blend,
+        };
+        let intersection = SmoothIntersection {
+            a: a(),
+            b: b(),
+            k,
+            blend,
+        };
+        let difference = SmoothDifference {
+            a: a(),
+            b: b(),
+            k,
+            blend,
+        };
+
+        for (x, y) in [(-1.0, 0.0), (1.0, 0.0), (3.0, 0.0), (0.0, 5.0)] {
+            let p = Vec2::new(x, y);
+
+            assert_eq!(side(union.call(p)), side(Union { a: a(), b: b() }.call(p)));
+            assert_eq!(
+                side(intersection.call(p)),
+                side(Intersection { a: a(), b: b() }.call(p))
+            );
+            assert_eq!(
+                side(difference.call(p)),
+                side(Difference { a: a(), b: b() }.call(p))
+            );
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use signed_distance_fields::objects::{
+    operators::{
+        boolean::{Blend, Intersection, SmoothUnion, Union},
+        Smooth,
+    },
+    primitives::{Circle, Rectangle},
+    SignedDistanceField, SignedDistanceFieldExt,
+};
+use signed_distance_fields::Vec2;
+
+fn points() -> Vec<Vec2> {
+    let mut points = Vec::new();
+    for x in [-6.0, -2.5, 0.0, 1.0, 4.0] {
+        for y in [-3.0, 0.0, 0.5, 2.0] {
+            points.push(Vec2::new(x, y));
+        }
+    }
+    points
+}
+
+#[test]
+fn operators_match_boolean_logic() {
+    let a = Circle { r: 2.0 };
+    let b = Rectangle { w: 6.0, h: 1.0 };
+
+    for p in points() {
+        let (da, db) = (a.call(p), b.call(p));
+
+        assert_eq!((a | b).call(p), da.min(db));
+        assert_eq!((a & b).call(p), da.max(db));
+        assert_eq!((a | b).call(p), a.union(b).call(p));
+        assert_eq!((a & b).call(p), a.intersection(b).call(p));
+        assert_eq!((a - b).call(p), a.difference(b).call(p));
+    }
+}
+
+#[test]
+fn fluent_methods_match_struct_literals() {
+    let a = Circle { r: 2.0 };
+    let b = Rectangle { w: 6.0, h: 1.0 };
+    let smooth = SmoothUnion {
+        a,
+        b,
+        k: 0.5,
+        blend: Blend::Polynomial,
+    };
+
+    for p in points() {
+        assert_eq!(a.smooth_union(b, 0.5).call(p), smooth.call(p));
+        assert_eq!(a.round(0.5).call(p), a.call(p) - 0.5);
+        assert_eq!(
+            a.translate(Vec2::new(1.0, 0.0)).call(p),
+            a.call(Vec2::new(p.x - 1.0, p.y))
+        );
+    }
+}
+
+#[test]
+fn expressions_nest() {
+    let a = Circle { r: 2.0 };
+    let b = Rectangle { w: 6.0, h: 1.0 };
+    let c = Circle { r: 0.5 };
+
+    let scene = (a | b) & c.round(0.1);
+    let nested = Intersection {
+        a: Union { a, b },
+        b: Smooth { sdf: c, k: 0.1 },
+    };
+
+    for p in points() {
+        assert_eq!(scene.call(p), nested.call(p));
+    }
+}
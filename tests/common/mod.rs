@@ -0,0 +1,53 @@
+//! Fixtures shared by the integration tests, each of which only uses some of them
+
+#![allow(dead_code)]
+
+use std::ops::RangeInclusive;
+
+use signed_distance_fields::{objects::SignedDistanceField, Vec2};
+
+pub fn v(x: f64, y: f64) -> Vec2 {
+    Vec2::new(x, y)
+}
+
+/// Points on a grid `step` apart, shifted a bit so none of them lands exactly on an edge or a
+/// crease
+pub fn grid(
+    x: RangeInclusive<i32>,
+    y: RangeInclusive<i32>,
+    step: f64,
+) -> impl Iterator<Item = Vec2> {
+    x.flat_map(move |i| {
+        y.clone()
+            .map(move |j| v(i as f64 * step + 0.013, j as f64 * step - 0.007))
+    })
+}
+
+/// The points of a [`grid`] from `-n` to `n` steps in both directions
+pub fn points(n: i32, step: f64) -> impl Iterator<Item = Vec2> {
+    grid(-n..=n, -n..=n, step)
+}
+
+pub fn assert_close(actual: f64, expected: f64) {
+    assert!(
+        (actual - expected).abs() < 1e-12,
+        "expected {expected}, got {actual}"
+    );
+}
+
+/// Compares the distances of two SDFs at all `points`
+pub fn assert_sdfs_close(
+    a: &dyn SignedDistanceField,
+    b: &dyn SignedDistanceField,
+    points: impl Iterator<Item = Vec2>,
+    tolerance: f64,
+) {
+    for p in points {
+        assert!(
+            (a.call(p) - b.call(p)).abs() < tolerance,
+            "{p:?}: {} vs {}",
+            a.call(p),
+            b.call(p)
+        );
+    }
+}
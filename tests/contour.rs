@@ -0,0 +1,68 @@
+use signed_distance_fields::{
+    contour,
+    objects::{
+        primitives::{Circle, Rectangle},
+        SignedDistanceField, SignedDistanceFieldExt,
+    },
+    Domain, Vec2,
+};
+
+fn domain() -> Domain {
+    Domain {
+        p0: Vec2::new(-10.0, -10.0),
+        p1: Vec2::new(10.0, 10.0),
+        steps: Vec2::new(81, 81),
+    }
+}
+
+fn is_closed(polyline: &[[f64; 2]]) -> bool {
+    polyline.first() == polyline.last()
+}
+
+#[test]
+fn circle_gives_one_closed_polyline_on_the_surface() {
+    let circle = Circle { r: 5.0 };
+    let polylines = contour::polylines(&circle, &domain(), 0.0);
+
+    assert_eq!(polylines.len(), 1);
+    assert!(is_closed(&polylines[0]));
+    assert!(polylines[0].len() > 40);
+    for [x, y] in &polylines[0] {
+        assert!(circle.call(Vec2::new(*x, *y)).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn iso_value_gives_offset_outline() {
+    let polylines = contour::polylines(&Circle { r: 5.0 }, &domain(), 1.5);
+
+    assert_eq!(polylines.len(), 1);
+    for [x, y] in &polylines[0] {
+        assert!((Vec2::new(*x, *y).length() - 6.5).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn holes_and_separate_shapes_give_separate_polylines() {
+    let ring = Circle { r: 4.0 } - Circle { r: 2.0 };
+    let scene = ring | Circle { r: 1.0 }.translate(Vec2::new(7.0, 7.0));
+    let polylines = contour::polylines(&scene, &domain(), 0.0);
+
+    assert_eq!(polylines.len(), 3);
+    assert!(polylines.iter().all(|polyline| is_closed(polyline)));
+}
+
+#[test]
+fn shapes_leaving_the_domain_give_open_polylines() {
+    let bar = Rectangle { w: 30.0, h: 4.0 };
+    let polylines = contour::polylines(&bar, &domain(), 0.0);
+
+    assert_eq!(polylines.len(), 2);
+    for polyline in &polylines {
+        assert!(!is_closed(polyline));
+        let [x0, _] = polyline[0];
+        let [x1, _] = polyline[polyline.len() - 1];
+        assert_eq!(x0.abs().max(x1.abs()), 10.0);
+        assert!(polyline.iter().all(|[_, y]| (y.abs() - 2.0).abs() < 1e-9));
+    }
+}
@@ -0,0 +1,124 @@
+use itertools_num::linspace;
+use signed_distance_fields::{
+    field::{SampledField, Stats},
+    objects::{primitives::Circle, SignedDistanceField},
+    render::{self, text_mappers},
+    Domain, Vec2,
+};
+
+fn domain() -> Domain {
+    Domain {
+        p0: Vec2::new(-2.0, 3.0),
+        p1: Vec2::new(4.0, -1.0),
+        steps: Vec2::new(4, 3),
+    }
+}
+
+#[test]
+fn samples_are_indexed_like_pixels() {
+    let field = SampledField::from_fn(domain(), |x, y| (10 * y + x) as f64);
+
+    assert_eq!((field.width(), field.height()), (4, 3));
+    assert_eq!(
+        field.values(),
+        &[0., 1., 2., 3., 10., 11., 12., 13., 20., 21., 22., 23.]
+    );
+    assert_eq!(field[(3, 1)], 13.0);
+    assert_eq!(field.get(1, 2), Some(21.0));
+    assert_eq!(field.get(4, 0), None);
+    assert_eq!(field.get(0, 3), None);
+
+    assert_eq!(field.row(2), &[20.0, 21.0, 22.0, 23.0]);
+    assert_eq!(field.rows().count(), 3);
+    assert_eq!(field.column(1).collect::<Vec<_>>(), vec![1.0, 11.0, 21.0]);
+    let columns: Vec<Vec<f64>> = field.columns().map(Iterator::collect).collect();
+    assert_eq!(columns.len(), 4);
+    assert_eq!(columns[3], vec![3.0, 13.0, 23.0]);
+
+    let mut field = field;
+    field[(0, 2)] = -5.0;
+    assert_eq!(field.row(2)[0], -5.0);
+}
+
+#[test]
+fn coordinates_map_between_world_and_pixels() {
+    let field = SampledField::from_fn(domain(), |_, _| 0.0);
+
+    assert_eq!(field.to_world(0, 0), Vec2::new(-2.0, 3.0));
+    assert_eq!(field.to_world(3, 2), Vec2::new(4.0, -1.0));
+    assert_eq!(field.to_world(1, 1), Vec2::new(0.0, 1.0));
+    assert_eq!(field.to_pixel(Vec2::new(0.0, 1.0)), Vec2::new(1.0, 1.0));
+    assert_eq!(field.to_pixel(Vec2::new(1.0, 2.0)), Vec2::new(1.5, 0.5));
+
+    // The samples are exactly the points the renderers evaluate
+    let domain = Domain {
+        p0: Vec2::new(-1.3, 0.7),
+        p1: Vec2::new(2.9, -5.1),
+        steps: Vec2::new(37, 23),
+    };
+    let xs: Vec<f64> = linspace(domain.p0.x, domain.p1.x, 37).collect();
+    let ys: Vec<f64> = linspace(domain.p0.y, domain.p1.y, 23).collect();
+    let sdf = Circle { r: 2.0 };
+    let field = render::matrix(&sdf, &domain);
+    for (x, y) in [(0, 0), (5, 17), (36, 22), (20, 3)] {
+        assert_eq!(field.to_world(x, y), Vec2::new(xs[x], ys[y]));
+        assert_eq!(field[(x, y)], sdf.call(field.to_world(x, y)));
+    }
+
+    // Axes with a single sample collapse onto `p0`
+    let line = SampledField::from_values(
+        Domain {
+            steps: Vec2::new(1, 2),
+            ..domain
+        },
+        vec![0.0, 1.0],
+    );
+    assert_eq!(line.to_world(0, 1), Vec2::new(-1.3, -5.1));
+    assert_eq!(line.to_pixel(Vec2::new(7.0, -5.1)), Vec2::new(0.0, 1.0));
+}
+
+#[test]
+fn statistics() {
+    let field = SampledField::from_values(domain(), (0..12).map(|v| v as f64 - 3.0).collect());
+
+    assert_eq!(field.min(), Some(-3.0));
+    assert_eq!(field.max(), Some(8.0));
+    assert_eq!(
+        field.stats(),
+        Some(Stats {
+            min: -3.0,
+            max: 8.0,
+            mean: 2.5,
+            inside: 3,
+        })
+    );
+
+    let empty = SampledField::from_values(
+        Domain {
+            steps: Vec2::new(0, 3),
+            ..domain()
+        },
+        Vec::new(),
+    );
+    assert_eq!(empty.min(), None);
+    assert_eq!(empty.stats(), None);
+    assert_eq!(empty.rows().count(), 3);
+    assert_eq!(empty.columns().count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn values_have_to_fill_the_domain() {
+    SampledField::from_values(domain(), vec![0.0; 11]);
+}
+
+#[test]
+fn text_mappers_read_rows() {
+    let field = SampledField::from_values(
+        domain(),
+        vec![-1., 0., 0.2, 1., -0.1, 2., 2., 2., 1., 1., 1., -3.],
+    );
+
+    assert_eq!(text_mappers::default(&field), " *+ \n-   \n    \n");
+    assert_eq!(text_mappers::fill_inside(&field), "##  \n#   \n   #\n");
+}
@@ -0,0 +1,248 @@
+#![cfg(feature = "font")]
+
+mod common;
+
+use common::{grid, v};
+use signed_distance_fields::{
+    import::font::{Error, Font},
+    objects::{
+        primitives::{BezierSegment, Rectangle},
+        SignedDistanceField, SignedDistanceFieldExt,
+    },
+    render::{self, color_mappers},
+    Domain, Vec2,
+};
+
+/// A glyph of the test font, made of contours of points that are on or off the curve
+struct Glyph {
+    advance: u16,
+    contours: Vec<Vec<(i16, i16, bool)>>,
+}
+
+/// A TrueType font with 1000 units per em and the glyphs `.notdef`, `I` and `O`, where the pair
+/// `II` is kerned by -100 units
+fn font() -> Vec<u8> {
+    let glyphs = [
+        // A box
+        Glyph {
+            advance: 600,
+            contours: vec![vec![
+                (100, 0, true),
+                (100, 700, true),
+                (500, 700, true),
+                (500, 0, true),
+            ]],
+        },
+        // A bar
+        Glyph {
+            advance: 400,
+            contours: vec![vec![
+                (100, 0, true),
+                (100, 700, true),
+                (300, 700, true),
+                (300, 0, true),
+            ]],
+        },
+        // A ring of quadratic curves, whose hole runs the other way around
+        Glyph {
+            advance: 1000,
+            contours: vec![
+                vec![
+                    (500, 0, true),
+                    (0, 0, false),
+                    (0, 500, true),
+                    (0, 1000, false),
+                    (500, 1000, true),
+                    (1000, 1000, false),
+                    (1000, 500, true),
+                    (1000, 0, false),
+                ],
+                vec![
+                    (500, 250, true),
+                    (750, 250, false),
+                    (750, 500, true),
+                    (750, 750, false),
+                    (500, 750, true),
+                    (250, 750, false),
+                    (250, 500, true),
+                    (250, 250, false),
+                ],
+            ],
+        },
+    ];
+
+    let mut glyf = Vec::new();
+    let mut loca = vec![0u32];
+    for glyph in &glyphs {
+        let points: Vec<_> = glyph.contours.iter().flatten().copied().collect();
+        glyf.extend((glyph.contours.len() as i16).to_be_bytes());
+        for bound in [
+            points.iter().map(|p| p.0).min(),
+            points.iter().map(|p| p.1).min(),
+            points.iter().map(|p| p.0).max(),
+            points.iter().map(|p| p.1).max(),
+        ] {
+            glyf.extend(bound.unwrap().to_be_bytes());
+        }
+        let mut end = 0;
+        for contour in &glyph.contours {
+            end += contour.len() as u16;
+            glyf.extend((end - 1).to_be_bytes());
+        }
+        glyf.extend(0u16.to_be_bytes());
+        // Flags without any compression, coordinates as 16 bit differences
+        glyf.extend(points.iter().map(|p| u8::from(p.2)));
+        let mut previous = 0;
+        for p in &points {
+            glyf.extend((p.0 - previous).to_be_bytes());
+            previous = p.0;
+        }
+        previous = 0;
+        for p in &points {
+            glyf.extend((p.1 - previous).to_be_bytes());
+            previous = p.1;
+        }
+        loca.push(glyf.len() as u32);
+    }
+
+    let be16 =
+        |values: &[u16]| -> Vec<u8> { values.iter().flat_map(|v| v.to_be_bytes()).collect() };
+    let mut head = be16(&[1, 0, 1, 0, 0, 0, 0x5F0F, 0x3CF5, 0, 1000]);
+    head.extend([0; 16]);
+    head.extend(be16(&[0, 0, 1000, 1000, 0, 3, 2, 1, 0]));
+    let mut hhea = be16(&[1, 0, 800, (-200i16) as u16, 100]);
+    hhea.extend([0; 24]);
+    hhea.extend(be16(&[3]));
+    let maxp = be16(&[0, 0x5000, 3]);
+    let hmtx: Vec<u8> = glyphs
+        .iter()
+        .flat_map(|glyph| be16(&[glyph.advance, 0]))
+        .collect();
+    let loca: Vec<u8> = loca
+        .iter()
+        .flat_map(|offset| offset.to_be_bytes())
+        .collect();
+    // Format 12, mapping `I` and `O` to 1 and 2
+    let mut cmap = be16(&[0, 1, 3, 10, 0, 12, 12, 0, 0, 40, 0, 0, 0, 2]);
+    for (c, glyph) in [('I', 1u32), ('O', 2)] {
+        cmap.extend(
+            [c as u32, c as u32, glyph]
+                .iter()
+                .flat_map(|v| v.to_be_bytes()),
+        );
+    }
+    let kern = be16(&[0, 1, 0, 20, 1, 1, 6, 0, 0, 1, 1, (-100i16) as u16]);
+
+    let tables = [
+        (b"cmap", cmap),
+        (b"glyf", glyf),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"kern", kern),
+        (b"loca", loca),
+        (b"maxp", maxp),
+    ];
+    let mut font = be16(&[1, 0, tables.len() as u16, 0, 0, 0]);
+    let mut offset = 12 + 16 * tables.len();
+    let mut data = Vec::new();
+    for (tag, table) in &tables {
+        font.extend(*tag);
+        font.extend(0u32.to_be_bytes());
+        font.extend((offset as u32).to_be_bytes());
+        font.extend((table.len() as u32).to_be_bytes());
+        data.extend(table);
+        while data.len() % 4 != 0 {
+            data.push(0);
+        }
+        offset = 12 + 16 * tables.len() + data.len();
+    }
+    font.extend(data);
+
+    font
+}
+
+#[test]
+fn glyphs_are_placed_on_the_baseline() {
+    let font = Font::new(font()).unwrap();
+    assert_eq!(font.units_per_em(), 1000);
+
+    // A size of 10 makes a font unit 0.01 long, y is flipped
+    let text = font.text("I", 10.0, v(2.0, 3.0));
+    let bar = Rectangle { w: 2.0, h: 7.0 }.translate(v(4.0, -0.5));
+    for p in grid(-10..=40, -15..=5, 0.5) {
+        assert!((text.call(p) - bar.call(p)).abs() < 1e-12, "{p:?}");
+    }
+}
+
+#[test]
+fn kerning_and_advances() {
+    let font = Font::new(font()).unwrap();
+
+    // `II` is kerned, `IO` isn't
+    let text = font.text("IIO", 10.0, Vec2::ZERO);
+    let bars = Rectangle { w: 2.0, h: 7.0 }.translate(v(2.0, -3.5))
+        | Rectangle { w: 2.0, h: 7.0 }.translate(v(5.0, -3.5));
+    for p in grid(-10..=40, -15..=5, 0.5).filter(|p| p.x < 6.5) {
+        assert!((text.call(p) - bars.call(p)).abs() < 1e-12, "{p:?}");
+    }
+    assert_eq!(font.width("IIO", 10.0), 4.0 - 1.0 + 4.0 + 10.0);
+    assert_eq!(font.width("O\nII", 10.0), 10.0);
+}
+
+#[test]
+fn quadratic_contours() {
+    let font = Font::new(font()).unwrap();
+    let ring = font.text("O", 10.0, Vec2::ZERO);
+
+    assert_eq!(ring.segments().len(), 8);
+    assert!(ring
+        .segments()
+        .iter()
+        .all(|segment| matches!(segment, BezierSegment::Quadratic(_))));
+    // The center is in the hole, the ring is between the contours
+    assert!(ring.call(v(5.0, -5.0)) > 0.0);
+    assert!(ring.call(v(1.0, -5.0)) < 0.0);
+    assert!((ring.call(v(-1.0, -5.0)) - 1.0).abs() < 1e-12);
+    assert!((ring.call(v(5.0, -5.0)) - 2.5).abs() < 1e-12);
+}
+
+#[test]
+fn missing_characters_and_lines() {
+    let font = Font::new(font()).unwrap();
+
+    // `?` isn't in the font and becomes the box, the second line is 1100 units lower
+    let text = font.text("?\nI", 10.0, Vec2::ZERO);
+    let expected = Rectangle { w: 4.0, h: 7.0 }.translate(v(3.0, -3.5))
+        | Rectangle { w: 2.0, h: 7.0 }.translate(v(2.0, 7.5));
+    for p in grid(-10..=40, -15..=5, 0.5) {
+        assert!((text.call(p) - expected.call(p)).abs() < 1e-12, "{p:?}");
+    }
+}
+
+#[test]
+fn rendering_text() {
+    let font = Font::new(font()).unwrap();
+    let label = font.text("IO", 10.0, v(0.0, 0.0)) | Rectangle { w: 1.0, h: 1.0 };
+    let domain = Domain {
+        p0: v(-1.0, -11.0),
+        p1: v(15.0, 1.0),
+        steps: Vec2::new(33, 25),
+    };
+
+    let image = render::image(&label, &domain, &color_mappers::default);
+    assert_eq!(image.dimensions(), (33, 25));
+    // The sample at (2, -3.5) is in the middle of the bar
+    let field = render::matrix(&label, &domain);
+    assert_eq!(field.to_world(6, 15), v(2.0, -3.5));
+    assert!((field[(6, 15)] + 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn errors() {
+    assert!(matches!(Font::new(vec![0; 12]), Err(Error::Font(_))));
+    assert!(matches!(
+        Font::read(&mut font().as_slice()),
+        Ok(font) if font.units_per_em() == 1000
+    ));
+}
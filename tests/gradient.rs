@@ -0,0 +1,152 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::points;
+use signed_distance_fields::{
+    objects::{
+        central_difference,
+        operators::{
+            boolean::{Blend, SmoothDifference, SmoothIntersection, SmoothUnion},
+            transforms::{Scale, Transform2},
+        },
+        primitives::{Circle, Line, Plane, Rectangle, Straight},
+        SignedDistanceField, SignedDistanceFieldExt, F,
+    },
+    Vec2,
+};
+
+/// Compares the gradient with central differences wherever the SDF is smooth, which is checked by
+/// comparing central differences with two step sizes
+fn assert_gradient_matches(sdf: &dyn SignedDistanceField) {
+    let mut checked = 0;
+
+    for p in points(8, 1.0) {
+        let coarse = central_difference(sdf, p, 1e-3);
+        let fine = central_difference(sdf, p, 1e-5);
+        if (coarse - fine).length() > 1e-4 {
+            continue;
+        }
+        checked += 1;
+
+        let gradient = sdf.gradient(p);
+        assert!(
+            (gradient - fine).length() < 1e-4,
+            "{p:?}: {gradient:?} vs {fine:?}"
+        );
+
+        let (distance, with_gradient) = sdf.call_with_gradient(p);
+        assert_eq!(distance, sdf.call(p));
+        assert_eq!(with_gradient, gradient);
+    }
+
+    assert!(checked > points(8, 1.0).count() / 2);
+}
+
+#[test]
+fn primitives_have_analytic_gradients() {
+    assert_gradient_matches(&Circle { r: 3.0 });
+    assert_gradient_matches(&Rectangle { w: 6.0, h: 3.0 });
+    assert_gradient_matches(&Line { l: 5.0 });
+    assert_gradient_matches(&Straight {});
+    assert_gradient_matches(&Plane {});
+
+    assert_eq!(Circle { r: 1.0 }.gradient(Vec2::ZERO), Vec2::ZERO);
+    assert_eq!(
+        Rectangle { w: 6.0, h: 3.0 }.gradient(Vec2::new(0.5, -0.2)),
+        Vec2::new(0.0, -1.0)
+    );
+}
+
+#[test]
+fn gradients_propagate_through_transforms() {
+    let rectangle = Rectangle { w: 6.0, h: 3.0 };
+
+    assert_gradient_matches(&rectangle.translate(Vec2::new(1.0, -2.0)));
+    assert_gradient_matches(&rectangle.rotate(0.7));
+    assert_gradient_matches(&rectangle.scale(1.5));
+    assert_gradient_matches(&Scale {
+        scale: Vec2::new(2.0, 0.5),
+        sdf: rectangle,
+    });
+    assert_gradient_matches(&rectangle.matrix([[1.0, 0.5], [0.2, 1.5]]));
+    assert_gradient_matches(&rectangle.affine([[0.0, -2.0], [2.0, 0.0]], Vec2::new(1.0, 1.0)));
+    assert_gradient_matches(
+        &rectangle
+            .transformed(Transform2::from_rotation(0.3))
+            .scale(1.5)
+            .translate(Vec2::new(-1.0, 0.0)),
+    );
+}
+
+#[test]
+fn gradients_propagate_through_boolean_operators() {
+    let a = || Circle { r: 4.0 };
+    let b = || Rectangle { w: 6.0, h: 3.0 }.translate(Vec2::new(2.0, 1.0));
+
+    assert_gradient_matches(&(a() | b()));
+    assert_gradient_matches(&(a() & b()));
+    assert_gradient_matches(&(a() - b()));
+    assert_gradient_matches(&(a() ^ b()));
+    assert_gradient_matches(&!a());
+    assert_gradient_matches(&a().round(1.0));
+
+    for blend in [
+        Blend::Polynomial,
+        Blend::Cubic,
+        Blend::Exponential,
+        Blend::Root,
+    ] {
+        let k = 2.0;
+        assert_gradient_matches(&SmoothUnion {
+            a: a(),
+            b: b(),
+            k,
+            blend,
+        });
+        assert_gradient_matches(&SmoothIntersection {
+            a: a(),
+            b: b(),
+            k,
+            blend,
+        });
+        assert_gradient_matches(&SmoothDifference {
+            a: a(),
+            b: b(),
+            k,
+            blend,
+        });
+    }
+}
+
+#[test]
+fn closures_fall_back_to_central_differences() {
+    let f = F {
+        f: Arc::new(|p: Vec2| p.x * p.x + 3.0 * p.y),
+    };
+
+    let gradient = f.gradient(Vec2::new(2.0, 5.0));
+    assert!((gradient - Vec2::new(4.0, 3.0)).length() < 1e-6);
+}
+
+#[test]
+fn the_step_of_central_differences_can_be_chosen() {
+    // Far from the origin the default step vanishes in the rounding of the coordinates
+    let circle = F {
+        f: Arc::new(|p: Vec2| p.length() - 1e12),
+    };
+    let p = Vec2::new(3e12, 4e12);
+    assert_eq!(circle.gradient(p), Vec2::ZERO);
+
+    let circle = circle.gradient_epsilon(1e3);
+    assert!((circle.gradient(p) - Vec2::new(0.6, 0.8)).length() < 1e-6);
+    assert_eq!(circle.call(p), 4e12);
+    assert_eq!(circle.call_with_gradient(p), (4e12, circle.gradient(p)));
+
+    // It also replaces analytic gradients
+    let rectangle = Rectangle { w: 6.0, h: 3.0 }.gradient_epsilon(1e-3);
+    assert_eq!(
+        rectangle.gradient(Vec2::new(0.5, -0.2)),
+        central_difference(&Rectangle { w: 6.0, h: 3.0 }, Vec2::new(0.5, -0.2), 1e-3)
+    );
+}
@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use signed_distance_fields::{
+    interval::{Classification, Interval},
+    objects::{
+        operators::{
+            boolean::{Blend, SmoothDifference, SmoothIntersection, SmoothUnion},
+            transforms::{Scale, Transform2},
+        },
+        primitives::{Circle, Line, Plane, Rectangle, Straight},
+        SignedDistanceField, SignedDistanceFieldExt, F,
+    },
+    Vec2,
+};
+
+/// Rectangles of different sizes all over the scene
+fn regions() -> impl Iterator<Item = Vec2<Interval>> {
+    let sizes = [0.1, 0.7, 2.5, 9.0];
+    (-6..=6).flat_map(move |i| {
+        (-6..=6).flat_map(move |j| {
+            sizes.into_iter().map(move |size| {
+                let p0 = Vec2::new(i as f64 * 1.3 - 0.05, j as f64 * 1.1 + 0.02);
+                Vec2::from_corners(p0, p0 + Vec2::new(size, 0.6 * size))
+            })
+        })
+    })
+}
+
+/// Every distance within a region has to lie within the interval of the region
+fn assert_bounds(sdf: &dyn SignedDistanceField) {
+    for region in regions() {
+        let interval = sdf.eval_interval(region);
+        let (p0, p1) = (region.min_corner(), region.max_corner());
+
+        for i in 0..=8 {
+            for j in 0..=8 {
+                let t = Vec2::new(i as f64 / 8.0, j as f64 / 8.0);
+                let p = p0 + (p1 - p0).mul_components(t);
+                let distance = sdf.call(p);
+
+                assert!(
+                    interval.min - 1e-9 <= distance && distance <= interval.max + 1e-9,
+                    "{distance} at {p:?} isn't within {interval:?}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn primitives_are_bounded() {
+    assert_bounds(&Circle { r: 3.0 });
+    assert_bounds(&Rectangle { w: 6.0, h: 3.0 });
+    assert_bounds(&Line { l: 5.0 });
+    assert_bounds(&Straight {});
+    assert_bounds(&Plane {});
+}
+
+#[test]
+fn operators_are_bounded() {
+    let a = || Circle { r: 4.0 };
+    let b = || Rectangle { w: 6.0, h: 3.0 }.translate(Vec2::new(2.0, 1.0));
+
+    assert_bounds(&(a() | b()));
+    assert_bounds(&(a() & b()));
+    assert_bounds(&(a() - b()));
+    assert_bounds(&(a() ^ b()));
+    assert_bounds(&!a());
+    assert_bounds(&b().round(1.0));
+    assert_bounds(&b().rotate(0.7));
+    assert_bounds(&b().scale(0.5));
+    assert_bounds(&Scale {
+        scale: Vec2::new(-2.0, 0.5),
+        sdf: b(),
+    });
+    assert_bounds(&b().matrix([[1.0, 0.5], [0.2, 1.5]]));
+    assert_bounds(&b().affine([[0.0, -2.0], [2.0, 0.0]], Vec2::new(1.0, 1.0)));
+    assert_bounds(&b().transformed(Transform2::new(Vec2::new(-1.0, 0.5), 0.3, 1.5)));
+
+    for blend in [
+        Blend::Polynomial,
+        Blend::Cubic,
+        Blend::Exponential,
+        Blend::Root,
+    ] {
+        let k = 2.0;
+        assert_bounds(&SmoothUnion {
+            a: a(),
+            b: b(),
+            k,
+            blend,
+        });
+        assert_bounds(&SmoothIntersection {
+            a: a(),
+            b: b(),
+            k,
+            blend,
+        });
+        assert_bounds(&SmoothDifference {
+            a: a(),
+            b: b(),
+            k,
+            blend,
+        });
+    }
+}
+
+#[test]
+fn closures_fall_back_to_a_lipschitz_bound() {
+    let f: F = F {
+        f: Arc::new(|p: Vec2| p.x - 1.0),
+    };
+    assert_bounds(&f);
+
+    let region = Vec2::from_corners(Vec2::new(2.0, 0.0), Vec2::new(8.0, 8.0));
+    assert_eq!(f.eval_interval(region), Interval::new(-1.0, 9.0));
+}
+
+#[test]
+fn regions_are_classified() {
+    let scene = Circle { r: 4.0 } - Circle { r: 1.0 };
+    let classify = |p0: (f64, f64), p1: (f64, f64)| {
+        scene
+            .eval_interval(Vec2::from_corners(p0.into(), p1.into()))
+            .classify()
+    };
+
+    assert_eq!(classify((2.0, -0.5), (3.0, 0.5)), Classification::Inside);
+    assert_eq!(classify((-0.5, -0.5), (0.5, 0.5)), Classification::Outside);
+    assert_eq!(classify((5.0, 5.0), (9.0, 6.0)), Classification::Outside);
+    assert_eq!(classify((3.5, -1.0), (4.5, 1.0)), Classification::Ambiguous);
+}
+
+#[test]
+fn interval_arithmetic() {
+    let a = Interval::new(1.0, -2.0);
+    assert_eq!(
+        a,
+        Interval {
+            min: -2.0,
+            max: 1.0
+        }
+    );
+    assert_eq!(a.abs(), Interval::new(0.0, 2.0));
+    assert_eq!(a.square(), Interval::new(0.0, 4.0));
+    assert_eq!(a * Interval::new(-3.0, 2.0), Interval::new(-4.0, 6.0));
+    assert_eq!(a * -2.0, Interval::new(-2.0, 4.0));
+    assert_eq!(a - Interval::new(0.0, 1.0), Interval::new(-3.0, 1.0));
+    assert_eq!(a.min(Interval::point(0.0)), Interval::new(-2.0, 0.0));
+    assert_eq!(Interval::new(-1.0, 9.0).sqrt(), Interval::new(0.0, 3.0));
+
+    let region = Vec2::from_corners(Vec2::new(3.0, -4.0), Vec2::new(0.0, 4.0));
+    assert_eq!(region.center(), Vec2::new(1.5, 0.0));
+    assert_eq!(region.length(), Interval::new(0.0, 5.0));
+}
@@ -0,0 +1,111 @@
+use std::collections::HashMap;
+
+use signed_distance_fields::{
+    mesh::{self, Mesh},
+    objects::{
+        primitives3::{Cuboid, Sphere, Torus},
+        SignedDistanceField3, SignedDistanceField3Ext,
+    },
+    Domain3, Vec3,
+};
+
+fn domain() -> Domain3 {
+    Domain3 {
+        p0: Vec3::splat(-2.0),
+        p1: Vec3::splat(2.0),
+        steps: Vec3::new(21, 21, 21),
+    }
+}
+
+/// Every edge has to be used once in each direction by the triangles of a closed, consistently
+/// oriented mesh
+fn is_closed(mesh: &Mesh) -> bool {
+    let mut edges: HashMap<(usize, usize), i32> = HashMap::new();
+    for &[a, b, c] in &mesh.triangles {
+        for (from, to) in [(a, b), (b, c), (c, a)] {
+            *edges.entry((from, to)).or_default() += 1;
+        }
+    }
+
+    edges
+        .iter()
+        .all(|(&(from, to), &count)| count == 1 && edges.get(&(to, from)) == Some(&1))
+}
+
+fn euler_characteristic(mesh: &Mesh) -> i64 {
+    // On a closed mesh every edge belongs to two triangles
+    let edges = mesh.triangles.len() * 3 / 2;
+
+    mesh.vertices.len() as i64 - edges as i64 + mesh.triangles.len() as i64
+}
+
+fn faces_outwards(mesh: &Mesh, center: Vec3) -> bool {
+    mesh.triangles.iter().all(|&[a, b, c]| {
+        let [a, b, c] = [a, b, c].map(|i| mesh.vertices[i]);
+        (b - a).cross(c - a).dot((a + b + c) / 3.0 - center) > 0.0
+    })
+}
+
+#[test]
+fn marching_cubes_gives_closed_sphere_on_the_surface() {
+    let sphere = Sphere { r: 1.3 };
+    let mesh = mesh::marching_cubes(&sphere, &domain(), 0.0);
+
+    assert!(mesh.triangles.len() > 100);
+    assert!(is_closed(&mesh));
+    assert_eq!(euler_characteristic(&mesh), 2);
+    assert!(faces_outwards(&mesh, Vec3::ZERO));
+
+    for (p, n) in mesh.vertices.iter().zip(&mesh.normals) {
+        assert!(sphere.call(*p).abs() < 1e-9);
+        assert!((*n - p.normalize()).length() < 1e-6);
+    }
+}
+
+#[test]
+fn marching_cubes_keeps_the_hole_of_a_torus() {
+    let torus = Torus {
+        major: 1.2,
+        minor: 0.4,
+    };
+    let mesh = mesh::marching_cubes(&torus, &domain(), 0.0);
+
+    assert!(is_closed(&mesh));
+    assert_eq!(euler_characteristic(&mesh), 0);
+}
+
+#[test]
+fn marching_cubes_handles_ambiguous_cells() {
+    // Two spheres that touch diagonally within single cells give ambiguous faces
+    let spheres = Sphere { r: 0.75 }
+        .translate(Vec3::new(-0.7, -0.7, 0.0))
+        .union(Sphere { r: 0.75 }.translate(Vec3::new(0.7, 0.7, 0.0)));
+    let mesh = mesh::marching_cubes(&spheres, &domain(), 0.0);
+
+    assert!(is_closed(&mesh));
+}
+
+#[test]
+fn surface_nets_gives_closed_mesh_on_the_surface() {
+    let cuboid = Cuboid {
+        w: 2.0,
+        h: 1.5,
+        d: 1.0,
+    };
+    let mesh = mesh::surface_nets(&cuboid, &domain(), 0.0);
+
+    assert!(is_closed(&mesh));
+    assert_eq!(euler_characteristic(&mesh), 2);
+    assert!(faces_outwards(&mesh, Vec3::ZERO));
+
+    for p in &mesh.vertices {
+        assert!(cuboid.call(*p).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn empty_domain_gives_empty_mesh() {
+    let mesh = mesh::marching_cubes(&Sphere { r: 5.0 }, &domain(), -10.0);
+
+    assert_eq!(mesh, Mesh::default());
+}
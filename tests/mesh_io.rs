@@ -0,0 +1,256 @@
+use std::{collections::HashMap, f64::consts::PI};
+
+use signed_distance_fields::{
+    mesh::{self, obj, ply, stl, Error, Mesh},
+    objects::{
+        primitives::{Circle, Plane, Rectangle},
+        SignedDistanceFieldExt, F,
+    },
+    Domain, Vec2, Vec3,
+};
+
+fn domain() -> Domain {
+    Domain {
+        p0: Vec2::new(-5.0, -5.0),
+        p1: Vec2::new(5.0, 5.0),
+        steps: Vec2::new(41, 41),
+    }
+}
+
+/// A plate with a round hole
+fn plate() -> Mesh {
+    let profile = Rectangle { w: 6.0, h: 4.0 }.difference(Circle { r: 1.0 });
+    mesh::extrude(&profile, &domain(), 2.0)
+}
+
+/// Merges vertices at the same position, so the topology can be checked without the normals
+fn welded_triangles(mesh: &Mesh) -> Vec<[usize; 3]> {
+    let mut ids: HashMap<[u64; 3], usize> = HashMap::new();
+    let welded: Vec<usize> = mesh
+        .vertices
+        .iter()
+        .map(|p| {
+            let key = [p.x.to_bits(), p.y.to_bits(), p.z.to_bits()];
+            let next = ids.len();
+            *ids.entry(key).or_insert(next)
+        })
+        .collect();
+
+    mesh.triangles
+        .iter()
+        .map(|t| t.map(|i| welded[i]))
+        .collect()
+}
+
+/// Every edge has to be used once in each direction by a closed, consistently oriented mesh
+fn is_closed(triangles: &[[usize; 3]]) -> bool {
+    let mut edges: HashMap<(usize, usize), i32> = HashMap::new();
+    for &[a, b, c] in triangles {
+        for edge in [(a, b), (b, c), (c, a)] {
+            *edges.entry(edge).or_default() += 1;
+        }
+    }
+
+    edges
+        .iter()
+        .all(|(&(from, to), &count)| count == 1 && edges.get(&(to, from)) == Some(&1))
+}
+
+fn volume(mesh: &Mesh) -> f64 {
+    mesh.triangles
+        .iter()
+        .map(|&[a, b, c]| {
+            let [a, b, c] = [a, b, c].map(|i| mesh.vertices[i]);
+            a.dot(b.cross(c)) / 6.0
+        })
+        .sum()
+}
+
+fn assert_same_triangles(a: &Mesh, b: &Mesh, tolerance: f64) {
+    assert_eq!(a.triangles.len(), b.triangles.len());
+    for (ta, tb) in a.triangles.iter().zip(&b.triangles) {
+        for (&ia, &ib) in ta.iter().zip(tb) {
+            assert!(a.vertices[ia].distance(b.vertices[ib]) <= tolerance);
+        }
+    }
+}
+
+#[test]
+fn extrusion_is_closed_and_has_the_right_volume() {
+    let mesh = plate();
+
+    assert!(is_closed(&welded_triangles(&mesh)));
+    let expected = (24.0 - PI) * 2.0;
+    assert!((volume(&mesh) - expected).abs() < 0.01 * expected);
+
+    for (p, n) in mesh.vertices.iter().zip(&mesh.normals) {
+        assert!((n.length() - 1.0).abs() < 1e-9);
+        if p.z == 0.0 || p.z == 2.0 {
+            continue;
+        }
+        // The walls of the hole point towards its center, the outer walls away from it
+        let radial = Vec3::new(p.x, p.y, 0.0);
+        if radial.length() < 1.5 {
+            assert!(n.dot(radial) < 0.0);
+        } else {
+            assert!(n.dot(radial) > 0.0);
+        }
+    }
+}
+
+#[test]
+fn extrusion_is_closed_along_the_border_of_the_domain() {
+    let mesh = mesh::extrude(&Circle { r: 6.0 }, &domain(), 1.0);
+
+    assert!(is_closed(&welded_triangles(&mesh)));
+    assert!(volume(&mesh) > 0.0);
+}
+
+#[test]
+#[should_panic(expected = "has to be positive")]
+fn flat_extrusions_panic() {
+    mesh::extrude(&Circle { r: 6.0 }, &domain(), 0.0);
+}
+
+#[test]
+#[should_panic(expected = "has to be positive")]
+fn negative_extrusions_panic() {
+    mesh::extrude(&Circle { r: 6.0 }, &domain(), -1.0);
+}
+
+#[test]
+fn extrusion_normals_are_finite_on_plateaus() {
+    // Outside of the circle the union is 0 everywhere, so its gradient is 0 there
+    let mesh = mesh::extrude(&(Plane {} | Circle { r: 3.0 }), &domain(), 1.0);
+    let mut buffer = Vec::new();
+    ply::write(&mut buffer, &mesh).unwrap();
+    let read = ply::read(&mut buffer.as_slice()).unwrap();
+
+    assert!(!read.normals.is_empty());
+    for (p, n) in read.vertices.iter().zip(&read.normals) {
+        assert!(
+            n.x.is_finite() && n.y.is_finite() && n.z.is_finite(),
+            "{p:?}"
+        );
+        assert!((n.length() - 1.0).abs() < 1e-6, "{p:?}");
+    }
+}
+
+#[test]
+fn extrusion_normals_scale_with_the_domain() {
+    // A circle with a radius of 3µm, whose gradient is approximated with central differences
+    let circle = F {
+        f: std::sync::Arc::new(|p: Vec2| p.length() - 3e-6),
+    };
+    let domain = Domain {
+        p0: Vec2::new(-5e-6, -5e-6),
+        p1: Vec2::new(5e-6, 5e-6),
+        steps: Vec2::new(41, 41),
+    };
+    let mesh = mesh::extrude(&circle, &domain, 1e-6);
+
+    for (p, n) in mesh.vertices.iter().zip(&mesh.normals) {
+        if p.z == 0.0 || p.z == 1e-6 {
+            continue;
+        }
+        let radial = Vec3::new(p.x, p.y, 0.0).normalize();
+        assert!(n.dot(radial) > 0.999, "{p:?}: {n:?}");
+    }
+}
+
+#[test]
+fn ascii_stl_round_trip() {
+    let mesh = plate();
+    let mut buffer = Vec::new();
+    stl::write_ascii(&mut buffer, &mesh).unwrap();
+
+    assert!(buffer.starts_with(b"solid"));
+    let read = stl::read(&mut buffer.as_slice()).unwrap();
+    assert_same_triangles(&mesh, &read, 0.0);
+    assert!(is_closed(&welded_triangles(&read)));
+}
+
+#[test]
+fn binary_stl_round_trip() {
+    let mesh = plate();
+    let mut buffer = Vec::new();
+    stl::write_binary(&mut buffer, &mesh).unwrap();
+
+    assert_eq!(buffer.len(), 84 + 50 * mesh.triangles.len());
+    let read = stl::read(&mut buffer.as_slice()).unwrap();
+    assert_same_triangles(&mesh, &read, 1e-5);
+}
+
+#[test]
+fn obj_round_trip() {
+    let mesh = plate();
+    let mut buffer = Vec::new();
+    obj::write(&mut buffer, &mesh).unwrap();
+
+    let read = obj::read(&mut buffer.as_slice()).unwrap();
+    assert_eq!(read, mesh);
+}
+
+#[test]
+fn ply_round_trip() {
+    let mesh = plate();
+    let mut buffer = Vec::new();
+    ply::write(&mut buffer, &mesh).unwrap();
+
+    let read = ply::read(&mut buffer.as_slice()).unwrap();
+    assert_eq!(read, mesh);
+}
+
+#[test]
+fn obj_reader_splits_polygons_and_computes_missing_normals() {
+    let quad = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\nf 1 2 3 -1\n";
+    let read = obj::read(&mut quad.as_bytes()).unwrap();
+
+    assert_eq!(read.triangles, vec![[0, 1, 2], [0, 2, 3]]);
+    for n in &read.normals {
+        assert_eq!(*n, Vec3::new(0.0, 0.0, 1.0));
+    }
+}
+
+#[test]
+fn invalid_meshes_and_files_give_errors() {
+    let broken = Mesh {
+        vertices: vec![Vec3::ZERO],
+        normals: vec![Vec3::ZERO],
+        triangles: vec![[0, 1, 2]],
+    };
+    assert!(matches!(
+        stl::write_binary(&mut Vec::new(), &broken),
+        Err(Error::InvalidMesh(_))
+    ));
+
+    let obj = "v 0 0 0\nv 1 0 0\nf 1 2 7\n";
+    assert!(matches!(
+        obj::read(&mut obj.as_bytes()),
+        Err(Error::Parse { line: Some(3), .. })
+    ));
+
+    let stl = "solid\n  facet normal 0 0 one\n";
+    assert!(matches!(
+        stl::read(&mut stl.as_bytes()),
+        Err(Error::Parse { line: Some(2), .. })
+    ));
+
+    let ply = "ply\nformat binary_little_endian 1.0\nend_header\n";
+    assert!(matches!(
+        ply::read(&mut ply.as_bytes()),
+        Err(Error::Parse { line: Some(2), .. })
+    ));
+
+    // A face count that overflows the end of its index list
+    let ply = format!(
+        "ply\nformat ascii 1.0\nelement vertex 3\nproperty float x\nproperty float y\n\
+         property float z\nelement face 1\nproperty list uchar int vertex_indices\nend_header\n\
+         0 0 0\n1 0 0\n0 1 0\n{} 0 1 2\n",
+        usize::MAX
+    );
+    assert!(matches!(
+        ply::read(&mut ply.as_bytes()),
+        Err(Error::Parse { line: Some(13), .. })
+    ));
+}
@@ -0,0 +1,241 @@
+mod common;
+
+use common::v;
+use signed_distance_fields::{
+    objects::{
+        primitives::{BezierSegment, BezierSpline, Circle, CubicBezier, FillRule, Polygon},
+        SignedDistanceField,
+    },
+    render::msdf::{self, AtlasEntry, AtlasSettings, Settings, Shape},
+    Domain, Vec2,
+};
+
+/// 33 by 33 pixels, a quarter unit apart
+fn domain() -> Domain {
+    Domain {
+        p0: v(-4.0, -4.0),
+        p1: v(4.0, 4.0),
+        steps: Vec2::new(33, 33),
+    }
+}
+
+fn point(domain: &Domain, x: u32, y: u32) -> Vec2 {
+    let pixel = (domain.p1 - domain.p0)
+        .div_components(v(domain.steps.x as f64 - 1.0, domain.steps.y as f64 - 1.0));
+
+    domain.p0 + pixel.mul_components(v(x as f64, y as f64))
+}
+
+/// The distance a pixel stands for, like a SDF negative inside
+fn decode(value: f64, range: f64) -> f64 {
+    (0.5 - value / 255.0) * range
+}
+
+fn median([a, b, c]: [u8; 3]) -> f64 {
+    f64::from(a.min(b).max(a.max(b).min(c)))
+}
+
+/// A cubic curve for every quarter of a circle with the radius `r`
+fn circle(r: f64) -> Vec<BezierSegment> {
+    let k = 0.5522847498 * r;
+    let quarter = |(x, y): (f64, f64)| {
+        BezierSegment::Cubic(CubicBezier {
+            p0: v(r * x, r * y),
+            p1: v(r * x - k * y, r * y + k * x),
+            p2: v(r * -y + k * x, r * x + k * y),
+            p3: v(r * -y, r * x),
+        })
+    };
+
+    [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0), (0.0, -1.0)]
+        .map(quarter)
+        .to_vec()
+}
+
+#[test]
+fn squares_decode_to_their_sdf() {
+    let square = vec![v(-2.0, -2.0), v(2.0, -2.0), v(2.0, 2.0), v(-2.0, 2.0)];
+    let domain = domain();
+    let range = 0.25 * Settings::default().range;
+
+    let image = msdf::image(
+        &Shape::from(&Polygon::new(square)),
+        &domain,
+        &Settings::default(),
+    );
+    assert_eq!(image.dimensions(), (33, 33));
+
+    for (x, y, pixel) in image.enumerate_pixels() {
+        // The corners stay sharp, so outside of them the distance is to the nearest edge's line
+        let p = point(&domain, x, y);
+        let expected = (p.x.abs() - 2.0).max(p.y.abs() - 2.0);
+        let distance = decode(median(pixel.0), range);
+        if expected.abs() < 0.45 * range {
+            assert!(
+                (distance - expected).abs() < 0.01,
+                "{x}, {y}: {distance} vs {expected}"
+            );
+        } else {
+            assert_eq!(distance < 0.0, expected < 0.0, "{x}, {y}");
+        }
+    }
+}
+
+#[test]
+fn orientation_and_fill_rule_dont_matter() {
+    let square = vec![v(-2.0, -1.5), v(2.5, -1.5), v(2.5, 2.0), v(-2.0, 2.0)];
+    let clockwise = Shape::from(&Polygon::new(square.clone()));
+    let counter_clockwise = Shape::from(&Polygon::new(square.into_iter().rev().collect()));
+    let mut even_odd = counter_clockwise.clone();
+    even_odd.fill_rule = FillRule::EvenOdd;
+
+    // The edges may get other colors, but the medians are the same
+    let medians = |shape: &Shape| -> Vec<f64> {
+        msdf::image(shape, &domain(), &Settings::default())
+            .pixels()
+            .map(|pixel| median(pixel.0))
+            .collect()
+    };
+    let expected = medians(&clockwise);
+    assert_eq!(expected, medians(&counter_clockwise));
+    assert_eq!(expected, medians(&even_odd));
+    assert!(expected.contains(&0.0) && expected.contains(&255.0));
+}
+
+#[test]
+fn smooth_outlines_have_equal_channels() {
+    let shape = Shape::from(&BezierSpline::new(circle(2.5)));
+    let domain = domain();
+    let range = 0.25 * Settings::default().range;
+
+    let image = msdf::image(&shape, &domain, &Settings::default());
+    for (x, y, pixel) in image.enumerate_pixels() {
+        let [r, g, b] = pixel.0;
+        assert!(r == g && g == b, "{x}, {y}: {:?}", pixel.0);
+
+        let expected = Circle { r: 2.5 }.call(point(&domain, x, y));
+        if expected.abs() < 0.45 * range {
+            let distance = decode(f64::from(r), range);
+            assert!(
+                (distance - expected).abs() < 0.01,
+                "{x}, {y}: {distance} vs {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn single_corners_are_split() {
+    // A teardrop, whose only curve starts and ends in its tip
+    let teardrop = BezierSpline::new(vec![BezierSegment::Cubic(CubicBezier {
+        p0: v(0.0, -3.0),
+        p1: v(5.0, 3.0),
+        p2: v(-5.0, 3.0),
+        p3: v(0.0, -3.0),
+    })]);
+    let shape = Shape::from(&teardrop);
+    let domain = domain();
+
+    let image = msdf::image(&shape, &domain, &Settings::default());
+    let mut colors = std::collections::HashSet::new();
+    for (x, y, pixel) in image.enumerate_pixels() {
+        let expected = teardrop.call(point(&domain, x, y));
+        if expected.abs() > 0.25 {
+            assert_eq!(median(pixel.0) > 127.5, expected < 0.0, "{x}, {y}");
+        }
+        colors.insert(pixel.0.map(|c| c > 127));
+    }
+    // The channels differ around the tip
+    assert!(colors.len() > 2);
+}
+
+#[test]
+fn atlases_pack_all_shapes() {
+    let square = |r: f64| {
+        Shape::from(&Polygon::new(vec![
+            v(0.0, 0.0),
+            v(r, 0.0),
+            v(r, r),
+            v(0.0, r),
+        ]))
+    };
+    let entries = vec![
+        AtlasEntry {
+            name: "small".to_string(),
+            shape: square(0.5),
+            advance: 0.6,
+        },
+        AtlasEntry {
+            name: "space".to_string(),
+            shape: Shape::from(&BezierSpline::new(Vec::new())),
+            advance: 0.3,
+        },
+        AtlasEntry {
+            name: "circle, \"round\"".to_string(),
+            shape: Shape::from(&BezierSpline::new(circle(1.0))),
+            advance: 2.2,
+        },
+        AtlasEntry {
+            name: "large".to_string(),
+            shape: square(1.5),
+            advance: 1.6,
+        },
+    ];
+    let settings = AtlasSettings {
+        scale: 16.0,
+        max_width: 64,
+        ..AtlasSettings::default()
+    };
+
+    let atlas = msdf::atlas(&entries, &settings);
+    assert_eq!(atlas.metrics.len(), 4);
+    assert!(atlas.image.width() <= 64);
+
+    let cells: Vec<_> = atlas.metrics.iter().filter(|m| m.pixels[2] > 0).collect();
+    assert_eq!(cells.len(), 3);
+    assert_eq!(atlas.metrics[1].pixels, [0; 4]);
+    assert_eq!(atlas.metrics[2].bounds, [-1.0, -1.0, 1.0, 1.0]);
+    for (i, a) in cells.iter().enumerate() {
+        assert!(a.uv.iter().all(|uv| (0.0..=1.0).contains(uv)));
+        assert!(a.plane[0] < a.bounds[0] && a.bounds[2] < a.plane[2]);
+        for b in &cells[i + 1..] {
+            let apart = a.pixels[2] <= b.pixels[0]
+                || b.pixels[2] <= a.pixels[0]
+                || a.pixels[3] <= b.pixels[1]
+                || b.pixels[3] <= a.pixels[1];
+            assert!(apart, "{} and {} overlap", a.name, b.name);
+        }
+    }
+
+    // Every cell is the MSDF of its shape, sampled at the pixel centers of its plane
+    for (entry, metrics) in entries
+        .iter()
+        .zip(&atlas.metrics)
+        .filter(|(_, m)| m.pixels[2] > 0)
+    {
+        let [left, top, right, bottom] = metrics.pixels;
+        let half = 0.5 / settings.scale;
+        let domain = Domain {
+            p0: v(metrics.plane[0] + half, metrics.plane[1] + half),
+            p1: v(metrics.plane[2] - half, metrics.plane[3] - half),
+            steps: Vec2::new((right - left) as usize, (bottom - top) as usize),
+        };
+        let cell = msdf::image(&entry.shape, &domain, &settings.msdf);
+        for (x, y, pixel) in cell.enumerate_pixels() {
+            assert_eq!(atlas.image.get_pixel(left + x, top + y), pixel);
+        }
+    }
+
+    let mut json = Vec::new();
+    atlas.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.contains("\"name\": \"circle, \\\"round\\\"\""));
+    assert!(json.contains("\"advance\": 0.3"));
+
+    let mut csv = Vec::new();
+    atlas.write_csv(&mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    assert_eq!(csv.lines().count(), 5);
+    assert!(csv.starts_with("name,advance,bounds_left,"));
+    assert!(csv.contains("\n\"circle, \"\"round\"\"\",2.2,"));
+}
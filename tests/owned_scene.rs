@@ -0,0 +1,43 @@
+use std::{sync::Arc, thread};
+
+use signed_distance_fields::objects::{
+    operators::{boolean::Union, transforms::Translate},
+    primitives::{Circle, Rectangle},
+    SharedSignedDistanceField, SignedDistanceField,
+};
+use signed_distance_fields::Vec2;
+
+fn scene() -> impl SignedDistanceField + Clone {
+    Union {
+        a: Circle { r: 1.0 },
+        b: Translate {
+            p: Vec2::new(3.0, 0.0),
+            sdf: Rectangle { w: 2.0, h: 2.0 },
+        },
+    }
+}
+
+#[test]
+fn scene_can_be_returned_cloned_and_sent() {
+    let scene = scene();
+    let copy = scene.clone();
+
+    let p = Vec2::new(3.0, 0.0);
+    let distance = thread::spawn(move || copy.call(p)).join().unwrap();
+
+    assert_eq!(distance, scene.call(p));
+    assert_eq!(distance, -1.0);
+}
+
+#[test]
+fn type_erased_children_can_be_mixed() {
+    let children: Vec<SharedSignedDistanceField> =
+        vec![Arc::new(Circle { r: 1.0 }), Arc::new(scene())];
+
+    let union = Union {
+        a: children[0].clone(),
+        b: children[1].clone(),
+    };
+
+    assert_eq!(union.call(Vec2::new(0.0, 0.0)), -1.0);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "parallel")]
+
+use itertools_num::linspace;
+use signed_distance_fields::{
+    field::SampledField,
+    objects::{
+        primitives::{Circle, Rectangle},
+        SignedDistanceField, SignedDistanceFieldExt,
+    },
+    render::{self, color_mappers, parallel, text_mappers},
+    Domain, Vec2,
+};
+
+fn scene() -> impl SignedDistanceField {
+    (Circle { r: 6.0 } - Rectangle { w: 4.0, h: 20.0 }).rotate(0.3)
+        | Circle { r: 1.5 }.translate(Vec2::new(-9.0, 8.0))
+}
+
+fn domain() -> Domain {
+    Domain {
+        p0: Vec2::new(-12.0, -12.0),
+        p1: Vec2::new(12.0, 12.0),
+        steps: Vec2::new(173, 91),
+    }
+}
+
+/// The samples of plain nested loops, one call per point
+fn serial_matrix(sdf: &dyn SignedDistanceField, domain: &Domain) -> SampledField {
+    let xs: Vec<f64> = linspace(domain.p0.x, domain.p1.x, domain.steps.x).collect();
+    let ys: Vec<f64> = linspace(domain.p0.y, domain.p1.y, domain.steps.y).collect();
+
+    SampledField::from_fn(domain.clone(), |x, y| sdf.call(Vec2::new(xs[x], ys[y])))
+}
+
+#[test]
+fn matrices_are_bit_identical_for_any_pool() {
+    let (sdf, domain) = (scene(), domain());
+    let expected = serial_matrix(&sdf, &domain);
+
+    assert_eq!(render::matrix(&sdf, &domain), expected);
+    for threads in [1, 3, 8] {
+        let settings = parallel::Settings::with_threads(threads).unwrap();
+        assert_eq!(parallel::matrix(&sdf, &domain, &settings), expected);
+    }
+}
+
+#[test]
+fn images_and_texts_match_the_matrix() {
+    let (sdf, domain) = (scene(), domain());
+    let matrix = serial_matrix(&sdf, &domain);
+    let settings = parallel::Settings::with_threads(4).unwrap();
+
+    let image = parallel::image(&sdf, &domain, &color_mappers::red_blue_repeating, &settings);
+    assert_eq!(image.dimensions(), (173, 91));
+    for (x, y, pixel) in image.enumerate_pixels() {
+        assert_eq!(
+            *pixel,
+            color_mappers::red_blue_repeating(&matrix[(x as usize, y as usize)])
+        );
+    }
+    assert_eq!(
+        render::image(&sdf, &domain, &color_mappers::red_blue_repeating),
+        image
+    );
+
+    assert_eq!(
+        parallel::text(&sdf, &domain, &text_mappers::default, &settings),
+        text_mappers::default(&matrix)
+    );
+}
+
+#[test]
+fn empty_domains() {
+    let domain = Domain {
+        p0: Vec2::new(0.0, 0.0),
+        p1: Vec2::new(1.0, 1.0),
+        steps: Vec2::new(0, 4),
+    };
+    let settings = parallel::Settings::default();
+
+    assert!(parallel::matrix(&scene(), &domain, &settings)
+        .values()
+        .is_empty());
+    assert_eq!(
+        parallel::image(&scene(), &domain, &color_mappers::default, &settings).dimensions(),
+        (0, 4)
+    );
+}
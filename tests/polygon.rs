@@ -0,0 +1,221 @@
+mod common;
+
+use std::f64::consts::TAU;
+
+use common::{points, v};
+use signed_distance_fields::{
+    objects::{
+        primitives::{Circle, MultiPolygon, Polygon, PolygonWithHoles, Rectangle},
+        SignedDistanceField, SignedDistanceFieldExt,
+    },
+    scalar, Vec2,
+};
+
+/// The distance to the closest edge of all outlines, with a sign from counting crossings
+fn brute_force(outlines: &[Vec<Vec2>], p: Vec2) -> f64 {
+    let mut distance = f64::INFINITY;
+    let mut inside = false;
+
+    for outline in outlines {
+        for (i, &a) in outline.iter().enumerate() {
+            let b = outline[(i + 1) % outline.len()];
+            let (pa, ba) = (p - a, b - a);
+            let h = (pa.dot(ba) / ba.length_squared()).clamp(0.0, 1.0);
+            distance = distance.min((pa - ba * h).length());
+
+            if (a.y > p.y) != (b.y > p.y) && p.x < a.x + (p.y - a.y) / (b.y - a.y) * (b.x - a.x) {
+                inside = !inside;
+            }
+        }
+    }
+
+    if inside {
+        -distance
+    } else {
+        distance
+    }
+}
+
+fn assert_brute_force(sdf: &dyn SignedDistanceField, outlines: &[Vec<Vec2>]) {
+    for p in points(30, 0.2) {
+        let expected = brute_force(outlines, p);
+        assert!(
+            (sdf.call(p) - expected).abs() < 1e-12,
+            "{p:?}: {} vs {expected}",
+            sdf.call(p)
+        );
+    }
+}
+
+#[test]
+fn squares_are_rectangles() {
+    let square = vec![v(-2.0, -1.0), v(2.0, -1.0), v(2.0, 1.0), v(-2.0, 1.0)];
+    let rectangle = Rectangle { w: 4.0, h: 2.0 };
+
+    for vertices in [square.clone(), square.into_iter().rev().collect()] {
+        let polygon = Polygon::new(vertices);
+        for p in points(30, 0.2) {
+            assert!((polygon.call(p) - rectangle.call(p)).abs() < 1e-12, "{p:?}");
+        }
+    }
+}
+
+#[test]
+fn concave_polygons() {
+    // An L and a star, whose spikes are concave
+    let l = vec![
+        v(-3.0, -3.0),
+        v(3.0, -3.0),
+        v(3.0, -1.0),
+        v(-1.0, -1.0),
+        v(-1.0, 4.0),
+        v(-3.0, 4.0),
+    ];
+    let star: Vec<Vec2> = (0..14)
+        .map(|i| {
+            let angle = i as f64 * TAU / 14.0;
+            let r = if i % 2 == 0 { 5.5 } else { 2.0 };
+            v(r * angle.cos(), r * angle.sin())
+        })
+        .collect();
+
+    assert_brute_force(&Polygon::new(l.clone()), &[l]);
+    assert_brute_force(&Polygon::new(star.clone()), &[star]);
+}
+
+#[test]
+fn self_touching_outlines() {
+    // Two squares, that only touch in the corner (1, 1), traced as one outline
+    let outline = vec![
+        v(0.0, 0.0),
+        v(1.0, 0.0),
+        v(1.0, 1.0),
+        v(2.0, 1.0),
+        v(2.0, 2.0),
+        v(1.0, 2.0),
+        v(1.0, 1.0),
+        v(0.0, 1.0),
+    ];
+    let polygon = Polygon::new(outline);
+
+    assert!((polygon.call(v(0.5, 0.5)) + 0.5).abs() < 1e-12);
+    assert!((polygon.call(v(1.5, 1.5)) + 0.5).abs() < 1e-12);
+    assert!((polygon.call(v(1.5, 0.5)) - 0.5).abs() < 1e-12);
+    assert!((polygon.call(v(0.5, 1.5)) - 0.5).abs() < 1e-12);
+    assert_eq!(polygon.call(v(1.0, 1.0)), 0.0);
+}
+
+#[test]
+fn large_polygons() {
+    let n = 4_000;
+    let vertices: Vec<Vec2> = (0..n)
+        .map(|i| {
+            let angle = i as f64 * TAU / n as f64;
+            // A wobbly circle, so the polygon isn't convex
+            let r = 4.0 + 0.3 * (angle * 7.0).sin();
+            v(r * angle.cos(), r * angle.sin())
+        })
+        .collect();
+    let polygon = Polygon::new(vertices.clone());
+
+    assert_eq!(polygon.vertices().len(), n);
+    assert_brute_force(&polygon, &[vertices]);
+
+    let n = 100_000;
+    let circle: Vec<Vec2> = (0..n)
+        .map(|i| {
+            let angle = i as f64 * TAU / n as f64;
+            v(5.0 * angle.cos(), 5.0 * angle.sin())
+        })
+        .collect();
+    let polygon = Polygon::new(circle);
+    for p in points(30, 0.2) {
+        assert!((polygon.call(p) - Circle { r: 5.0 }.call(p)).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn multi_polygons_with_holes() {
+    let square = |c: Vec2, r: f64| vec![c + v(-r, -r), c + v(r, -r), c + v(r, r), c + v(-r, r)];
+    let outlines = [
+        square(v(-2.0, 0.0), 2.5),
+        // The hole has the same orientation as its exterior
+        square(v(-2.0, 0.0), 1.0),
+        square(v(4.0, 1.0), 1.5),
+    ];
+    let multi = MultiPolygon::new(vec![
+        PolygonWithHoles {
+            exterior: outlines[0].clone(),
+            holes: vec![outlines[1].clone()],
+        },
+        PolygonWithHoles {
+            exterior: outlines[2].iter().rev().copied().collect(),
+            holes: Vec::new(),
+        },
+    ]);
+
+    assert_eq!(multi.polygons().len(), 2);
+    assert!((multi.call(v(-2.0, 0.0)) - 1.0).abs() < 1e-12);
+    assert!((multi.call(v(-2.0, 1.8)) + 0.7).abs() < 1e-12);
+    assert!((multi.call(v(4.0, 1.0)) + 1.5).abs() < 1e-12);
+    assert_brute_force(&multi, &outlines);
+}
+
+#[test]
+fn overlapping_polygons_are_united() {
+    let a = vec![v(-3.0, -1.0), v(1.0, -1.0), v(1.0, 1.0), v(-3.0, 1.0)];
+    let b = vec![v(-1.0, -2.0), v(3.0, -2.0), v(3.0, 2.0), v(-1.0, 2.0)];
+    let multi = MultiPolygon::new(vec![
+        PolygonWithHoles {
+            exterior: a,
+            holes: Vec::new(),
+        },
+        PolygonWithHoles {
+            exterior: b,
+            holes: Vec::new(),
+        },
+    ]);
+
+    assert!(multi.call(v(0.0, 0.0)) < 0.0);
+    assert!(multi.call(v(-2.0, 0.0)) < 0.0);
+    assert!(multi.call(v(2.0, 1.5)) < 0.0);
+    assert!(multi.call(v(-2.0, 1.5)) > 0.0);
+
+    // The edges of one square within the other one don't count
+    assert!((multi.call(v(-1.1, 0.0)) + 1.0).abs() < 1e-12);
+    assert!((multi.call(v(1.2, 0.5)) + 1.5).abs() < 1e-12);
+    assert!((multi.call(v(0.0, 1.5)) + 0.5).abs() < 1e-12);
+    assert!((multi.call(v(-2.0, 1.5)) - 0.5).abs() < 1e-12);
+    assert!((multi.call(v(0.0, 3.0)) - 1.0).abs() < 1e-12);
+
+    // Just like the union of two rectangles
+    let a = Rectangle { w: 4.0, h: 2.0 }.translate(v(-1.0, 0.0));
+    let b = Rectangle { w: 4.0, h: 4.0 }.translate(v(1.0, 0.0));
+    for p in points(30, 0.2) {
+        let expected = a.call(p).min(b.call(p));
+        assert!((multi.call(p) - expected).abs() < 1e-12, "{p:?}");
+
+        let gradient = if a.call(p) < b.call(p) {
+            a.gradient(p)
+        } else {
+            b.gradient(p)
+        };
+        assert!((multi.gradient(p) - gradient).length() < 1e-12, "{p:?}");
+    }
+}
+
+#[test]
+fn gradients_and_empty_polygons() {
+    let polygon = Polygon::new(vec![v(0.0, 0.0), v(4.0, 0.0), v(0.0, 3.0)]);
+
+    for p in [v(2.0, -1.0), v(1.0, 1.0), v(5.0, 1.0), v(-1.0, -1.0)] {
+        let (distance, gradient) = scalar::gradient(&polygon, p);
+        assert_eq!(distance, polygon.call(p));
+        assert!((gradient - polygon.gradient(p)).length() < 1e-12, "{p:?}");
+    }
+    assert_eq!(polygon.gradient(v(2.0, -1.0)), v(0.0, -1.0));
+    assert_eq!(polygon.gradient(v(1.0, 0.5)), v(0.0, -1.0));
+
+    assert_eq!(Polygon::new(Vec::new()).call(v(1.0, 2.0)), f64::INFINITY);
+    assert_eq!(Polygon::new(vec![v(1.0, 1.0)]).call(v(4.0, 5.0)), 5.0);
+}
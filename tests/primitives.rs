@@ -0,0 +1,20 @@
+mod common;
+
+use common::{assert_close, v};
+use signed_distance_fields::objects::{primitives::Rectangle, SignedDistanceField};
+
+#[test]
+fn rectangles_inside_and_outside() {
+    let rectangle = Rectangle { w: 4.0, h: 2.0 };
+
+    // Inside the distance is the one to the closest edge
+    assert_close(rectangle.call(v(0.0, 0.0)), -1.0);
+    assert_close(rectangle.call(v(1.5, 0.2)), -0.5);
+    assert_close(rectangle.call(v(-0.5, -0.75)), -0.25);
+
+    // Outside the inside distance must not be added on top of the outside one
+    assert_close(rectangle.call(v(3.0, 0.0)), 1.0);
+    assert_close(rectangle.call(v(0.0, -1.5)), 0.5);
+    assert_close(rectangle.call(v(3.0, 2.0)), 2.0_f64.sqrt());
+    assert_close(rectangle.call(v(2.0, 1.0)), 0.0);
+}
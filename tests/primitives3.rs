@@ -0,0 +1,165 @@
+mod common;
+
+use common::assert_close;
+use signed_distance_fields::{
+    objects::{
+        operators::{
+            boolean::{Difference, Union},
+            transforms::{Rotate3, Translate},
+        },
+        primitives3::{
+            Capsule, Cone, Cuboid, Cylinder, Ellipsoid, Plane, RoundedCuboid, Sphere, Torus,
+        },
+        SignedDistanceField3, SignedDistanceField3Ext,
+    },
+    render, Domain3, Vec3,
+};
+
+fn v(x: f64, y: f64, z: f64) -> Vec3 {
+    Vec3::new(x, y, z)
+}
+
+#[test]
+fn primitives() {
+    assert_close(Sphere { r: 2.0 }.call(v(0.0, 3.0, 0.0)), 1.0);
+
+    let cuboid = Cuboid {
+        w: 2.0,
+        h: 4.0,
+        d: 6.0,
+    };
+    assert_close(cuboid.call(v(0.0, 0.0, 0.0)), -1.0);
+    assert_close(cuboid.call(v(2.0, 3.0, 0.0)), 2f64.sqrt());
+
+    let rounded = RoundedCuboid {
+        w: 2.0,
+        h: 2.0,
+        d: 2.0,
+        r: 0.5,
+    };
+    assert_close(rounded.call(v(2.0, 0.0, 0.0)), 1.0);
+    assert_close(rounded.call(v(1.0, 1.0, 0.0)), 0.5 * 2f64.sqrt() - 0.5);
+
+    let torus = Torus {
+        major: 3.0,
+        minor: 1.0,
+    };
+    assert_close(torus.call(v(3.0, 0.0, 0.0)), -1.0);
+    assert_close(torus.call(v(0.0, 0.0, 0.0)), 2.0);
+    assert_close(torus.call(v(0.0, 3.0, 2.0)), 1.0);
+
+    let capsule = Capsule {
+        a: v(0.0, 0.0, -1.0),
+        b: v(0.0, 0.0, 1.0),
+        r: 0.5,
+    };
+    assert_close(capsule.call(v(1.0, 0.0, 0.0)), 0.5);
+    assert_close(capsule.call(v(0.0, 0.0, 3.0)), 1.5);
+
+    let cylinder = Cylinder { r: 1.0, h: 2.0 };
+    assert_close(cylinder.call(v(0.0, 0.0, 0.0)), -1.0);
+    assert_close(cylinder.call(v(2.0, 0.0, 0.0)), 1.0);
+    assert_close(cylinder.call(v(0.0, 0.0, 3.0)), 2.0);
+    assert_close(cylinder.call(v(4.0, 0.0, 5.0)), 5.0);
+
+    let cone = Cone { r: 1.0, h: 1.0 };
+    assert_close(cone.call(v(0.0, 0.0, -1.0)), 1.0);
+    assert_close(cone.call(v(0.0, 0.0, 2.0)), 1.0);
+    assert_close(cone.call(v(0.0, 0.5, 0.5)), 0.0);
+    assert!(cone.call(v(0.0, 0.0, 0.25)) < 0.0);
+
+    let plane = Plane {
+        n: v(0.0, 0.0, 1.0),
+        h: 1.0,
+    };
+    assert_close(plane.call(v(5.0, -3.0, 4.0)), 3.0);
+    assert_close(plane.call(v(0.0, 0.0, 0.0)), -1.0);
+
+    let ellipsoid = Ellipsoid {
+        r: v(1.0, 2.0, 3.0),
+    };
+    assert_close(ellipsoid.call(v(2.0, 0.0, 0.0)), 1.0);
+    assert_close(ellipsoid.call(v(0.0, 0.0, 3.0)), 0.0);
+    assert!(ellipsoid.call(v(0.0, 0.0, 0.0)) < 0.0);
+}
+
+#[test]
+fn operators() {
+    let union = Union {
+        a: Sphere { r: 1.0 },
+        b: Translate {
+            p: v(3.0, 0.0, 0.0),
+            sdf: Sphere { r: 1.0 },
+        },
+    };
+    assert_close(union.call(v(3.0, 0.0, 0.0)), -1.0);
+    assert_close(union.call(v(1.5, 0.0, 0.0)), 0.5);
+
+    let hollow = Difference {
+        a: Sphere { r: 2.0 },
+        b: Sphere { r: 1.0 },
+    };
+    assert_close(hollow.call(v(0.0, 0.0, 0.0)), 1.0);
+    assert_close(hollow.call(v(0.0, 1.5, 0.0)), -0.5);
+
+    let rotated = Rotate3 {
+        alpha: std::f64::consts::FRAC_PI_2,
+        axis: v(0.0, 0.0, 1.0),
+        sdf: Cuboid {
+            w: 4.0,
+            h: 2.0,
+            d: 2.0,
+        },
+    };
+    assert_close(rotated.call(v(0.0, 2.0, 0.0)), 0.0);
+    assert_close(rotated.call(v(1.0, 0.0, 0.0)), 0.0);
+
+    let scaled = Sphere { r: 1.0 }.scale(2.0).translate(v(0.0, 0.0, 1.0));
+    assert_close(scaled.call(v(0.0, 0.0, 4.0)), 1.0);
+
+    let ops = Sphere { r: 1.0 }
+        | Cuboid {
+            w: 4.0,
+            h: 0.5,
+            d: 0.5,
+        };
+    assert_close(ops.call(v(1.75, 0.0, 0.0)), -0.25);
+}
+
+#[test]
+fn degenerate_capsules_are_spheres() {
+    let center = v(1.0, 2.0, -0.5);
+    let capsule = Capsule {
+        a: center,
+        b: center,
+        r: 1.5,
+    };
+    let sphere = Sphere { r: 1.5 }.translate(center);
+
+    for p in [
+        center,
+        v(0.0, 0.0, 0.0),
+        v(1.0, 2.0, 1.0),
+        v(-3.0, 4.5, 2.0),
+        v(2.5, 1.75, -0.25),
+    ] {
+        assert_close(capsule.call(p), sphere.call(p));
+    }
+}
+
+#[test]
+fn volume_samples_domain() {
+    let domain = Domain3 {
+        p0: v(-2.0, -2.0, -2.0),
+        p1: v(2.0, 2.0, 2.0),
+        steps: Vec3::new(5, 5, 5),
+    };
+    let volume = render::volume(&Sphere { r: 1.0 }, &domain);
+
+    assert_eq!(volume.len(), 5);
+    assert_eq!(volume[0].len(), 5);
+    assert_eq!(volume[0][0].len(), 5);
+    assert_close(volume[2][2][2], -1.0);
+    assert_close(volume[2][2][4], 1.0);
+    assert_close(volume[0][2][2], 1.0);
+}
@@ -0,0 +1,62 @@
+use image::Rgb;
+use signed_distance_fields::{
+    objects::primitives3::Sphere,
+    render::raymarch::{self, Camera, Settings},
+    Vec3,
+};
+
+fn camera() -> Camera {
+    Camera {
+        position: Vec3::new(0.0, -5.0, 0.0),
+        look_at: Vec3::ZERO,
+        up: Vec3::new(0.0, 0.0, 1.0),
+        fov: 0.8,
+    }
+}
+
+#[test]
+fn trace_hits_sphere() {
+    let settings = Settings::default();
+    let t = raymarch::trace(
+        &Sphere { r: 1.0 },
+        Vec3::new(0.0, -5.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0),
+        &settings,
+    )
+    .unwrap();
+
+    assert!((t - 4.0).abs() < 1e-3);
+    assert!(raymarch::trace(
+        &Sphere { r: 1.0 },
+        Vec3::new(0.0, -5.0, 0.0),
+        Vec3::new(0.0, -1.0, 0.0),
+        &settings,
+    )
+    .is_none());
+}
+
+#[test]
+fn normal_points_outwards() {
+    let n = raymarch::normal(&Sphere { r: 1.0 }, Vec3::new(0.0, 0.0, 1.0), 1e-4);
+
+    assert!((n.z - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn image_shows_sphere_on_background() {
+    let settings = Settings {
+        light: Vec3::new(0.0, -1.0, 0.0),
+        background: Rgb([1, 2, 3]),
+        ..Settings::default()
+    };
+    let image = raymarch::image(&Sphere { r: 1.0 }, &camera(), 64, 48, &settings);
+
+    assert_eq!(image.dimensions(), (64, 48));
+    assert_eq!(*image.get_pixel(0, 0), Rgb([1, 2, 3]));
+
+    // The light comes from the camera, so the center of the sphere is the brightest spot
+    let center = image.get_pixel(32, 24).0;
+    let side = image.get_pixel(38, 24).0;
+    assert_ne!(center, [1, 2, 3]);
+    assert!(center[0] > side[0]);
+}
@@ -0,0 +1,236 @@
+mod common;
+
+use std::sync::Arc;
+
+use common::{assert_close, v};
+use signed_distance_fields::objects::{
+    central_difference,
+    operators::boolean::{Blend, SmoothDifference, SmoothIntersection, SmoothUnion},
+    SignedDistanceField, F,
+};
+use signed_distance_fields::Vec2;
+
+const BLENDS: [Blend; 4] = [
+    Blend::Polynomial,
+    Blend::Cubic,
+    Blend::Exponential,
+    Blend::Root,
+];
+
+/// Two half planes whose distances are simply `x` and `y`, so `a` and `b` are known everywhere
+fn half_planes() -> (F, F) {
+    (
+        F {
+            f: Arc::new(|p| p.x),
+        },
+        F {
+            f: Arc::new(|p| p.y),
+        },
+    )
+}
+
+#[test]
+fn seam_matches_closed_form() {
+    let (a, b) = half_planes();
+    let k = 0.8;
+    let p = v(0.3, 0.3);
+
+    let expected = [
+        (Blend::Polynomial, 0.3 - k / 4.0),
+        (Blend::Cubic, 0.3 - k / 6.0),
+        (Blend::Exponential, 0.3 - k),
+        (Blend::Root, 0.3 - k),
+    ];
+
+    for (blend, value) in expected {
+        let union = SmoothUnion {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        assert_close(union.call(p), value);
+
+        let intersection = SmoothIntersection {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        assert_close(intersection.call(p), 0.6 - value);
+    }
+}
+
+#[test]
+fn inside_blend_region_matches_closed_form() {
+    let (a, b) = half_planes();
+    let k = 1.0;
+    let p = v(0.0, 0.5);
+    let h: f64 = 0.5;
+
+    let expected = [
+        (Blend::Polynomial, -h * h * k / 4.0),
+        (Blend::Cubic, -h * h * h * k / 6.0),
+        (Blend::Exponential, -k * (1.0 + 2f64.powf(-0.5 / k)).log2()),
+        (Blend::Root, 0.5 * (0.5 - (0.25f64 + 4.0 * k * k).sqrt())),
+    ];
+
+    for (blend, value) in expected {
+        let union = SmoothUnion {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        assert_close(union.call(p), value);
+    }
+}
+
+#[test]
+fn polynomial_blends_are_hard_outside_blend_region() {
+    let (a, b) = half_planes();
+    let k = 0.5;
+    let p = v(-1.0, 2.0);
+
+    for blend in [Blend::Polynomial, Blend::Cubic] {
+        let union = SmoothUnion {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        assert_close(union.call(p), -1.0);
+
+        let intersection = SmoothIntersection {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        assert_close(intersection.call(p), 2.0);
+
+        let difference = SmoothDifference {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        assert_close(difference.call(p), -1.0);
+    }
+}
+
+#[test]
+fn difference_is_intersection_with_complement() {
+    let (a, b) = half_planes();
+    let not_b: F = F {
+        f: Arc::new(|p| -p.y),
+    };
+    let k = 0.7;
+
+    for blend in BLENDS {
+        let difference = SmoothDifference {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        let intersection = SmoothIntersection {
+            a: &a,
+            b: &not_b,
+            k,
+            blend,
+        };
+
+        for p in [v(0.1, -0.2), v(-2.0, 0.4), v(0.5, 0.5)] {
+            assert_close(difference.call(p), intersection.call(p));
+        }
+    }
+}
+
+#[test]
+fn never_exceeds_hard_operators() {
+    let (a, b) = half_planes();
+    let k = 0.6;
+
+    for blend in BLENDS {
+        let union = SmoothUnion {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        let intersection = SmoothIntersection {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+
+        for x in [-1.0, -0.25, 0.0, 0.3, 1.5] {
+            for y in [-0.8, 0.0, 0.1, 2.0] {
+                let p = v(x, y);
+                assert!(union.call(p) <= x.min(y) + 1e-12);
+                assert!(intersection.call(p) >= x.max(y) - 1e-12);
+            }
+        }
+    }
+}
+
+#[test]
+fn zero_radius_is_hard_operator() {
+    let (a, b) = half_planes();
+
+    for blend in BLENDS {
+        let union = SmoothUnion {
+            a: &a,
+            b: &b,
+            k: 0.0,
+            blend,
+        };
+        assert_close(union.call(v(0.2, 0.2)), 0.2);
+    }
+}
+
+#[test]
+fn gradients_are_continuous_on_the_seam() {
+    let (a, b) = half_planes();
+    let k = 0.8;
+
+    for blend in BLENDS {
+        let union = SmoothUnion {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        let intersection = SmoothIntersection {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+        let difference = SmoothDifference {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+        };
+
+        // `a` and `b` are equal there (or opposite for the difference), so both weigh a half
+        let seams: [(&dyn SignedDistanceField, Vec2); 3] = [
+            (&union, v(0.3, 0.3)),
+            (&intersection, v(-0.2, -0.2)),
+            (&difference, v(0.3, -0.3)),
+        ];
+        for (sdf, p) in seams {
+            let expected = central_difference(sdf, p, 1e-6);
+            let (distance, gradient) = sdf.call_with_gradient(p);
+            assert_eq!(distance, sdf.call(p));
+            assert_eq!(gradient, sdf.gradient(p));
+            assert!(
+                (gradient - expected).length() < 1e-6,
+                "{blend:?} at {p:?}: {gradient:?} vs {expected:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use signed_distance_fields::{
+    objects::primitives::{Circle, Rectangle},
+    objects::SignedDistanceFieldExt,
+    render::{
+        self,
+        svg::{FillRule, Settings},
+    },
+    Domain, Vec2,
+};
+
+fn domain() -> Domain {
+    Domain {
+        p0: Vec2::new(-10.0, -10.0),
+        p1: Vec2::new(10.0, 10.0),
+        steps: Vec2::new(81, 81),
+    }
+}
+
+/// The `d` attributes of all paths in the document
+fn paths(svg: &str) -> Vec<&str> {
+    svg.split(r#"<path d=""#)
+        .skip(1)
+        .map(|rest| rest.split('"').next().unwrap())
+        .collect()
+}
+
+/// The subpaths of a `d` attribute as lists of points
+fn subpaths(d: &str) -> Vec<Vec<(f64, f64)>> {
+    d.split('M')
+        .filter(|subpath| !subpath.trim().is_empty())
+        .map(|subpath| {
+            subpath
+                .trim()
+                .trim_end_matches('Z')
+                .split('L')
+                .map(|point| {
+                    let mut coordinates = point.split_whitespace().map(|c| c.parse().unwrap());
+                    (coordinates.next().unwrap(), coordinates.next().unwrap())
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn signed_area(points: &[(f64, f64)]) -> f64 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (x0, y0) = points[i];
+            let (x1, y1) = points[(i + 1) % n];
+            x0 * y1 - x1 * y0
+        })
+        .sum::<f64>()
+        * 0.5
+}
+
+#[test]
+fn document_maps_domain_to_view_box() {
+    let svg = render::svg(&Circle { r: 5.0 }, &domain(), &Settings::default());
+
+    assert!(svg.starts_with("<svg"));
+    // Half a sample spacing of 0.25 around the domain
+    assert!(svg.contains(r#"width="81" height="81" viewBox="-10.125 -10.125 20.25 20.25""#));
+    assert!(svg.trim_end().ends_with("</svg>"));
+
+    // The paths are in the units of the domain
+    let outline = subpaths(paths(&svg)[0]);
+    assert_eq!(outline.len(), 1);
+    for &(x, y) in &outline[0] {
+        let r = x.hypot(y);
+        assert!((r - 5.0).abs() < 1e-2, "{r}");
+    }
+}
+
+#[test]
+fn flipped_domains_are_mirrored() {
+    let domain = Domain {
+        p0: Vec2::new(-10.0, 10.0),
+        p1: Vec2::new(10.0, -10.0),
+        steps: Vec2::new(81, 81),
+    };
+    let circle = Circle { r: 2.0 }.translate(Vec2::new(3.0, 4.0));
+    let svg = render::svg(&circle, &domain, &Settings::default());
+
+    assert!(svg.contains(r#"viewBox="-10.125 -10.125 20.25 20.25""#));
+    assert!(svg.contains(r#"<g transform="matrix(1 0 0 -1 0 0)">"#));
+    for &(x, y) in &subpaths(paths(&svg)[0])[0] {
+        assert!(((x - 3.0).hypot(y - 4.0) - 2.0).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn colors_are_escaped() {
+    let settings = Settings {
+        stroke: Some(r##"url("#a")&b"##.to_string()),
+        fill: Some("<red>".to_string()),
+        background: Some("'white'".to_string()),
+        ..Default::default()
+    };
+    let svg = render::svg(&Circle { r: 5.0 }, &domain(), &settings);
+
+    assert!(svg.contains(r##"stroke="url(&quot;#a&quot;)&amp;b""##));
+    assert!(svg.contains(r#"fill="&lt;red&gt;""#));
+    assert!(svg.contains(r#"fill="&apos;white&apos;""#));
+}
+
+#[test]
+fn iso_levels_give_one_path_each() {
+    let settings = Settings {
+        iso_levels: vec![0.0, 1.0, 2.0],
+        ..Default::default()
+    };
+    let svg = render::svg(&Circle { r: 5.0 }, &domain(), &settings);
+
+    assert_eq!(paths(&svg).len(), 3);
+}
+
+#[test]
+fn holes_wind_the_other_way_for_nonzero_fill() {
+    let ring = Circle { r: 8.0 }.difference(Circle { r: 4.0 });
+    let settings = Settings {
+        stroke: None,
+        fill: Some("red".to_string()),
+        fill_rule: FillRule::NonZero,
+        ..Default::default()
+    };
+    let svg = render::svg(&ring, &domain(), &settings);
+
+    assert!(svg.contains(r#"fill-rule="nonzero""#));
+    let paths = paths(&svg);
+    assert_eq!(paths.len(), 1);
+
+    let mut areas: Vec<f64> = subpaths(paths[0]).iter().map(|s| signed_area(s)).collect();
+    areas.sort_by(|a, b| a.abs().total_cmp(&b.abs()));
+    assert_eq!(areas.len(), 2);
+    assert!(areas[0] * areas[1] < 0.0, "{areas:?}");
+}
+
+#[test]
+fn fill_is_closed_along_the_border_of_the_domain() {
+    let settings = Settings {
+        stroke: None,
+        fill: Some("black".to_string()),
+        ..Default::default()
+    };
+    let svg = render::svg(&Rectangle { w: 40.0, h: 10.0 }, &domain(), &settings);
+
+    let subpaths = subpaths(paths(&svg)[0]);
+    assert_eq!(subpaths.len(), 1);
+    // The band is cut off at the left and right border of the domain
+    let area = signed_area(&subpaths[0]).abs();
+    assert!((area - 20.0 * 10.0).abs() < 0.5, "{area}");
+}
+
+#[test]
+fn write_matches_string() {
+    let mut buffer = Vec::new();
+    render::svg::write(
+        &mut buffer,
+        &Circle { r: 5.0 },
+        &domain(),
+        &Settings::default(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        render::svg(&Circle { r: 5.0 }, &domain(), &Settings::default())
+    );
+}
@@ -0,0 +1,200 @@
+#![cfg(feature = "svg")]
+
+mod common;
+
+use common::{assert_sdfs_close, points, v};
+use signed_distance_fields::{
+    import::svg::{self, Error},
+    objects::{
+        primitives::{BezierSegment, Circle, FillRule, Rectangle},
+        SignedDistanceField, SignedDistanceFieldExt,
+    },
+    Vec2,
+};
+
+/// All control points of the segments
+fn control_points(segments: &[BezierSegment]) -> Vec<Vec2> {
+    segments
+        .iter()
+        .flat_map(|segment| match *segment {
+            BezierSegment::Linear(points) => points.to_vec(),
+            BezierSegment::Quadratic(curve) => vec![curve.p0, curve.p1, curve.p2],
+            BezierSegment::Cubic(curve) => vec![curve.p0, curve.p1, curve.p2, curve.p3],
+        })
+        .collect()
+}
+
+#[test]
+fn rectangles_from_paths() {
+    let rectangle = Rectangle { w: 4.0, h: 2.0 };
+
+    for d in [
+        "M -2 -1 H 2 V 1 H -2 Z",
+        "m-2-1h4v2h-4z",
+        "M-2,-1 2,-1 2,1 -2,1",
+        "M -2 -1 L 2 -1 L 2 1 L -2 1 L -2 -1",
+    ] {
+        let spline = svg::path(d, FillRule::NonZero).unwrap();
+        assert_eq!(spline.segments().len(), 4, "{d}");
+        assert_sdfs_close(&spline, &rectangle, points(15, 0.4), 1e-12);
+    }
+}
+
+#[test]
+fn relative_commands_match_absolute_ones() {
+    let absolute = "M 1 2 C 3 4 5 4 6 2 S 8 0 9 1 Q 10 3 8 5 T 4 6 A 2 1 30 0 1 1 4 Z";
+    let relative = "m 1 2 c 2 2 4 2 5 0 s 2 -2 3 -1 q 1 2 -1 4 t -4 1 a 2 1 30 0 1 -3 -2 z";
+
+    let absolute = svg::path(absolute, FillRule::NonZero).unwrap();
+    let relative = svg::path(relative, FillRule::NonZero).unwrap();
+    let (a, b) = (
+        control_points(absolute.segments()),
+        control_points(relative.segments()),
+    );
+    assert_eq!(a.len(), b.len());
+    for (a, b) in a.iter().zip(&b) {
+        assert!((*a - *b).length() < 1e-12, "{a:?} vs {b:?}");
+    }
+
+    // The mirrored control points of `S` and `T`
+    assert_eq!(
+        a[4..8],
+        [v(6.0, 2.0), v(7.0, 0.0), v(8.0, 0.0), v(9.0, 1.0)]
+    );
+    assert_eq!(a[11..14], [v(8.0, 5.0), v(6.0, 7.0), v(4.0, 6.0)]);
+}
+
+#[test]
+fn compact_numbers() {
+    let spline = svg::path("M0,0L.5.5l1e1-2-1E-1+1", FillRule::NonZero).unwrap();
+
+    assert_eq!(
+        control_points(spline.segments()),
+        [
+            v(0.0, 0.0),
+            v(0.5, 0.5),
+            v(0.5, 0.5),
+            v(10.5, -1.5),
+            v(10.5, -1.5),
+            v(10.4, -0.5),
+            v(10.4, -0.5),
+            v(0.0, 0.0),
+        ]
+    );
+}
+
+#[test]
+fn arcs_are_ellipses() {
+    let circle = svg::path(
+        "M 4 0 A 4 4 0 1 1 -4 0 A 4 4 0 1 1 4 0 Z",
+        FillRule::NonZero,
+    )
+    .unwrap();
+    assert_sdfs_close(&circle, &Circle { r: 4.0 }, points(15, 0.4), 1.5e-3);
+
+    // Radii that are too small are scaled up until the arc reaches its end
+    let small = svg::path("M 4 0 A 1 1 0 0 0 -4 0 A 1 1 0 0 0 4 0", FillRule::NonZero).unwrap();
+    assert_sdfs_close(&small, &Circle { r: 4.0 }, points(15, 0.4), 1.5e-3);
+}
+
+#[test]
+fn fill_rules() {
+    // Two squares that run the same way around
+    let d = "M -4 -4 H 4 V 4 H -4 Z M -2 -2 H 2 V 2 H -2 Z";
+    let non_zero = svg::path(d, FillRule::NonZero).unwrap();
+    let even_odd = svg::path(d, FillRule::EvenOdd).unwrap();
+
+    for p in points(15, 0.4) {
+        let inside = p.x.abs() < 4.0 && p.y.abs() < 4.0;
+        assert_eq!(non_zero.call(p) < 0.0, inside, "{p:?}");
+    }
+    assert_sdfs_close(
+        &even_odd,
+        &(Rectangle { w: 8.0, h: 8.0 } - Rectangle { w: 4.0, h: 4.0 }),
+        points(15, 0.4),
+        1e-12,
+    );
+    assert_eq!(even_odd.fill_rule(), FillRule::EvenOdd);
+}
+
+#[test]
+fn documents() {
+    let drawing = svg::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 100 100">
+            <defs><rect id="hidden" x="-50" y="-50" width="100" height="100"/></defs>
+            <g transform="translate(-3 2)">
+                <rect x="-1" y="-2" width="2" height="4" transform="rotate(90)"/>
+            </g>
+            <circle cx="3" cy="-2" r="1.5" fill="red"/>
+            <path d="M -5 -5 H 5 V 5 H -5 Z" fill="none"/>
+            <g style="fill-rule: evenodd">
+                <path d="M 3 1 H 5 V 5 H 3 Z M 3.5 2 H 4.5 V 4 H 3.5 Z"/>
+            </g>
+        </svg>"#,
+    )
+    .unwrap();
+    assert_eq!(drawing.shapes.len(), 3);
+    assert_eq!(drawing.shapes[2].fill_rule(), FillRule::EvenOdd);
+
+    let expected = Rectangle { w: 4.0, h: 2.0 }.translate(v(-3.0, 2.0))
+        | Circle { r: 1.5 }.translate(v(3.0, -2.0))
+        | (Rectangle { w: 2.0, h: 4.0 } - Rectangle { w: 1.0, h: 2.0 }).translate(v(4.0, 3.0));
+    assert_sdfs_close(&drawing, &expected, points(15, 0.4), 1e-3);
+}
+
+#[test]
+fn shapes_of_documents() {
+    let drawing = svg::parse(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" transform="scale(2)">
+            <ellipse cx="0" cy="0" rx="2" ry="1"/>
+            <polygon points="3,-1 5,-1 5,1 3,1"/>
+            <rect x="-5" y="-1" width="2" height="2" rx="1"/>
+        </svg>"#,
+    )
+    .unwrap();
+
+    let expected = Circle { r: 2.0 }.translate(v(-8.0, 0.0))
+        | Rectangle { w: 4.0, h: 4.0 }.translate(v(8.0, 0.0));
+    for p in [
+        v(-8.0, 0.0),
+        v(8.0, 0.0),
+        v(3.5, 0.0),
+        v(-3.5, 0.0),
+        v(0.0, 1.5),
+    ] {
+        assert!(drawing.call(p) < 0.0, "{p:?}");
+    }
+    for p in [v(0.0, 2.5), v(-5.0, 3.0), v(13.0, 0.0), v(-6.2, 3.8)] {
+        assert!(drawing.call(p) > 0.0, "{p:?}");
+    }
+    for p in [v(-12.0, 0.0), v(-8.0, 3.0), v(12.0, 1.0), v(9.0, -3.0)] {
+        assert!((drawing.call(p) - expected.call(p)).abs() < 1e-2, "{p:?}");
+    }
+}
+
+#[test]
+fn errors() {
+    for d in [
+        "0 0 L 1 1",
+        "M 0 0 L 1",
+        "M 0 0 Z 1 1",
+        "M 0 0 X 1 1",
+        "M 0 0 A 1 1 0 2 0 1 1",
+    ] {
+        assert!(
+            matches!(svg::path(d, FillRule::NonZero), Err(Error::Parse(_))),
+            "{d}"
+        );
+    }
+
+    assert!(matches!(svg::parse("<svg"), Err(Error::Xml(_))));
+    assert!(matches!(svg::parse("<html/>"), Err(Error::Parse(_))));
+    assert!(matches!(
+        svg::parse(r#"<svg><g transform="spin(3)"/></svg>"#),
+        Err(Error::Parse(_))
+    ));
+    assert!(matches!(
+        svg::read(&mut "<svg><circle r=\"1\"/></svg>".as_bytes()),
+        Ok(drawing) if drawing.shapes.len() == 1
+    ));
+}
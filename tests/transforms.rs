@@ -0,0 +1,191 @@
+mod common;
+
+use common::{assert_close, v};
+use signed_distance_fields::objects::{
+    operators::transforms::{Affine2, Matrix, Rotate, Scale, Transform2, Transformed, Translate},
+    primitives::{Circle, Rectangle},
+    SignedDistanceField,
+};
+
+#[test]
+fn uniform_scale_grows_shape_and_distances() {
+    let scaled = Scale {
+        scale: v(2.0, 2.0),
+        sdf: Circle { r: 1.0 },
+    };
+
+    assert_close(scaled.call(v(0.0, 0.0)), -2.0);
+    assert_close(scaled.call(v(2.0, 0.0)), 0.0);
+    assert_close(scaled.call(v(0.0, 5.0)), 3.0);
+}
+
+#[test]
+fn non_uniform_scale_is_a_lower_bound() {
+    let scaled = Scale {
+        scale: v(3.0, 1.0),
+        sdf: Circle { r: 1.0 },
+    };
+
+    // The ellipse has the half axes 3 and 1
+    assert_close(scaled.call(v(3.0, 0.0)), 0.0);
+    assert_close(scaled.call(v(0.0, 1.0)), 0.0);
+    assert!(scaled.call(v(5.0, 0.0)) <= 2.0);
+    assert!(scaled.call(v(0.0, 3.0)) <= 2.0);
+}
+
+#[test]
+fn matrix_transforms_the_shape() {
+    let stretched = Matrix::new([[2.0, 0.0], [0.0, 2.0]], Rectangle { w: 2.0, h: 1.0 });
+
+    assert_close(stretched.call(v(2.0, 0.0)), 0.0);
+    assert_close(stretched.call(v(0.0, 1.0)), 0.0);
+    assert_close(stretched.call(v(3.0, 0.0)), 1.0);
+}
+
+#[test]
+fn affine_rotation_and_translation_are_exact() {
+    let (sin, cos) = std::f64::consts::FRAC_PI_2.sin_cos();
+    let affine = Affine2::new(
+        [[cos, -sin], [sin, cos]],
+        v(5.0, 0.0),
+        Rectangle { w: 4.0, h: 2.0 },
+    );
+
+    // Rotated by 90°, so the rectangle is now 2 wide and 4 high
+    assert_close(affine.call(v(5.0, 0.0)), -1.0);
+    assert_close(affine.call(v(6.0, 0.0)), 0.0);
+    assert_close(affine.call(v(5.0, 2.0)), 0.0);
+    assert_close(affine.call(v(8.0, 0.0)), 2.0);
+}
+
+#[test]
+fn affine_shear_is_a_lower_bound() {
+    let matrix = [[1.0, 1.5], [0.0, 1.0]];
+    let affine = Affine2::new(matrix, v(1.0, -1.0), Circle { r: 1.0 });
+
+    // Densely sample the boundary of the sheared circle to get the true distance
+    let boundary: Vec<(f64, f64)> = (0..10_000)
+        .map(|i| {
+            let (sin, cos) = (i as f64 / 10_000.0 * std::f64::consts::TAU).sin_cos();
+            (
+                matrix[0][0] * cos + matrix[0][1] * sin + 1.0,
+                matrix[1][0] * cos + matrix[1][1] * sin - 1.0,
+            )
+        })
+        .collect();
+
+    for (x, y) in [(5.0, 0.0), (0.0, 3.0), (-2.0, 2.0), (4.0, -4.0)] {
+        let exact = boundary
+            .iter()
+            .map(|(bx, by)| ((x - bx).powi(2) + (y - by).powi(2)).sqrt())
+            .fold(f64::INFINITY, f64::min);
+        let d = affine.call(v(x, y));
+
+        assert!(d > 0.0);
+        assert!(d <= exact + 1e-3, "{d} is larger than {exact}");
+    }
+}
+
+#[test]
+fn transform_compose_and_inverse() {
+    let a = Transform2::new(v(1.0, -2.0), 0.7, 1.5);
+    let b = Transform2::from_shear(0.3, 0.0).compose(&Transform2::from_translation(v(0.5, 4.0)));
+    let p = v(-1.25, 3.5);
+
+    let composed = a.compose(&b).apply_point(p);
+    let sequential = a.apply_point(b.apply_point(p));
+    assert_close(composed.x, sequential.x);
+    assert_close(composed.y, sequential.y);
+
+    let back = (a * b).inverse().apply_point(composed);
+    assert_close(back.x, p.x);
+    assert_close(back.y, p.y);
+
+    let v = a.apply_vector(v(1.0, 0.0));
+    assert_close(v.length(), 1.5);
+    assert_close(a.distance_scale(), 1.5);
+}
+
+#[test]
+fn transformed_matches_nested_operators() {
+    let rectangle = Rectangle { w: 4.0, h: 2.0 };
+    let nested = Translate {
+        p: v(3.0, 1.0),
+        sdf: Scale {
+            scale: v(2.0, 2.0),
+            sdf: rectangle,
+        },
+    };
+    let transformed = Transformed::new(Transform2::IDENTITY, rectangle)
+        .scale(2.0)
+        .translate(v(3.0, 1.0));
+
+    for (x, y) in [(0.0, 0.0), (3.0, 1.0), (7.5, -2.0), (-4.0, 6.0)] {
+        assert_close(transformed.call(v(x, y)), nested.call(v(x, y)));
+    }
+}
+
+#[test]
+fn operators_convert_into_transformed() {
+    let rectangle = Rectangle { w: 4.0, h: 2.0 };
+    let rotate = Rotate {
+        alpha: 0.4,
+        sdf: rectangle,
+    };
+    let matrix = Matrix::new([[1.0, 0.5], [0.0, 2.0]], rectangle);
+
+    for (x, y) in [(0.0, 0.0), (1.5, 1.0), (-3.0, 2.0), (0.5, -4.0)] {
+        let p = v(x, y);
+        assert_close(Transformed::from(rotate.clone()).call(p), rotate.call(p));
+        assert_close(Transformed::from(matrix.clone()).call(p), matrix.call(p));
+    }
+}
+
+#[test]
+#[should_panic(expected = "invertible")]
+fn singular_affine_transforms_panic() {
+    Affine2::new([[1.0, 2.0], [2.0, 4.0]], v(0.0, 0.0), Circle { r: 1.0 });
+}
+
+#[test]
+#[should_panic(expected = "invertible")]
+fn singular_transforms_have_no_inverse() {
+    Transform2::from_scale(0.0).inverse();
+}
+
+#[test]
+#[should_panic(expected = "can't be zero")]
+fn zero_scales_panic() {
+    let flattened = Scale {
+        scale: v(2.0, 0.0),
+        sdf: Circle { r: 1.0 },
+    };
+
+    flattened.call(v(1.0, 1.0));
+}
+
+#[test]
+#[should_panic(expected = "invertible")]
+fn singular_matrices_panic_when_created() {
+    Matrix::new([[0.0, 0.0], [1.0, 1.0]], Circle { r: 1.0 });
+}
+
+#[test]
+fn rotate_turns_the_shape_clockwise() {
+    use signed_distance_fields::{objects::primitives::Line, Vec3};
+
+    let line = Line { l: 4.0 };
+    let rotated = Rotate {
+        alpha: std::f64::consts::FRAC_PI_4,
+        sdf: line,
+    };
+
+    // The line from (-2, 0) to (2, 0) now runs from (-√2, √2) to (√2, -√2)
+    assert_close(rotated.call(v(1.0, -1.0)), 0.0);
+    assert_close(rotated.call(v(1.0, 1.0)), 2.0_f64.sqrt());
+    assert_close(rotated.gradient(v(1.0, 1.0)).x, 0.5_f64.sqrt());
+
+    let around = Rotate::around(std::f64::consts::FRAC_PI_4, Vec3::new(0.0, 0.0, -1.0), line);
+    assert_close(around.call(v(1.0, 1.0)), 0.0);
+    assert_close(around.call(v(1.0, -1.0)), 2.0_f64.sqrt());
+}